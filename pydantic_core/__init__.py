@@ -1,4 +1,23 @@
-from ._pydantic_core import SchemaError, SchemaValidator, ValidationError, __version__
+from ._pydantic_core import (
+    SchemaError,
+    SchemaValidator,
+    ValidationError,
+    __version__,
+    list_all_errors,
+    register_schema_type,
+    set_errors_url,
+    set_validation_hooks,
+)
 from ._types import Schema
 
-__all__ = '__version__', 'SchemaValidator', 'ValidationError', 'SchemaError', 'Schema'
+__all__ = (
+    '__version__',
+    'SchemaValidator',
+    'ValidationError',
+    'SchemaError',
+    'Schema',
+    'register_schema_type',
+    'list_all_errors',
+    'set_errors_url',
+    'set_validation_hooks',
+)
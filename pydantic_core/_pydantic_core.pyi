@@ -1,20 +1,130 @@
-from typing import Any, Dict, List
+import os
+import sys
+from typing import Any, Awaitable, BinaryIO, Callable, Dict, List, Mapping, Optional, Tuple, Type, Union
 
-from pydantic_core._types import Schema
+if sys.version_info < (3, 8):
+    from typing_extensions import Literal
+else:
+    from typing import Literal
+
+from pydantic_core._types import Schema, ValidationLimits
 
 __version__: str
 
 class SchemaValidator:
-    def __init__(self, schema: Schema) -> None: ...
-    def validate_python(self, input: Any) -> Any: ...
-    def validate_json(self, input: str) -> Any: ...
+    def __init__(self, schema: Schema, counters: bool = False) -> None: ...
+    @staticmethod
+    def from_definitions(definitions: Dict[str, Schema]) -> Dict[str, 'SchemaValidator']: ...
+    def validate_python(
+        self,
+        input: Any,
+        warnings: Optional[Literal['collect', 'warn']] = None,
+        context: Any = None,
+        collect_errors: bool = False,
+        report_coercions: bool = False,
+        limits: Optional[ValidationLimits] = None,
+        by_alias: bool = True,
+        by_name: bool = False,
+    ) -> Any: ...
+    def validate_python_async(
+        self,
+        input: Any,
+        warnings: Optional[Literal['collect', 'warn']] = None,
+        context: Any = None,
+        collect_errors: bool = False,
+        report_coercions: bool = False,
+        limits: Optional[ValidationLimits] = None,
+        by_alias: bool = True,
+        by_name: bool = False,
+    ) -> Awaitable[Any]: ...
+    def validate_json(
+        self,
+        input: Union[str, bytes, bytearray, memoryview],
+        warnings: Optional[Literal['collect', 'warn']] = None,
+        context: Any = None,
+        collect_errors: bool = False,
+        report_coercions: bool = False,
+        limits: Optional[ValidationLimits] = None,
+        by_alias: bool = True,
+        by_name: bool = False,
+        return_input: bool = False,
+    ) -> Any: ...
+    def validate_json_items(
+        self,
+        input: Union[str, bytes, bytearray, memoryview, BinaryIO],
+        callback: Callable[[Any, Optional[ValidationError]], Any],
+        context: Any = None,
+    ) -> int: ...
+    def validate_strings(
+        self,
+        input: Union[str, Dict[str, Any], List[Any]],
+        warnings: Optional[Literal['collect', 'warn']] = None,
+        context: Any = None,
+        collect_errors: bool = False,
+        report_coercions: bool = False,
+        limits: Optional[ValidationLimits] = None,
+        by_alias: bool = True,
+        by_name: bool = False,
+    ) -> Any: ...
+    def validate_env(
+        self,
+        input: Mapping[str, str],
+        env_prefix: Optional[str] = None,
+        env_nested_delimiter: Optional[str] = None,
+        case_sensitive: bool = False,
+        secrets_dir: Union[str, 'os.PathLike[str]', None] = None,
+        warnings: Optional[Literal['collect', 'warn']] = None,
+        context: Any = None,
+        collect_errors: bool = False,
+        report_coercions: bool = False,
+        limits: Optional[ValidationLimits] = None,
+    ) -> Any: ...
+    def construct(self, input: Any) -> Any: ...
+    def isinstance_python(self, input: Any) -> bool: ...
+    def isinstance_json(self, input: Union[str, bytes, bytearray, memoryview]) -> bool: ...
     def validate_assignment(self, field: str, input: Any, data: Dict[str, Any]) -> Dict[str, Any]: ...
+    def debug_repr(self) -> str: ...
+    def core_schema(self) -> Dict[str, Any]: ...
+    def copy(
+        self,
+        strict: Optional[bool] = None,
+        title: Optional[str] = None,
+        extra_behavior: Optional[Literal['allow', 'ignore', 'forbid']] = None,
+    ) -> 'SchemaValidator': ...
+
+def register_schema_type(name: str, builder: Callable[[Dict[str, Any], Optional[Dict[str, Any]]], Schema]) -> None: ...
+def list_all_errors() -> List[Dict[str, Any]]: ...
+def set_errors_url(url: Optional[str] = None) -> None: ...
+def set_validation_hooks(
+    on_start: Optional[Callable[[str], None]] = None, on_end: Optional[Callable[[str, float, int], None]] = None
+) -> None: ...
 
 class SchemaError(ValueError):
     pass
 
+class PyLineError:
+    def __init__(
+        self,
+        kind: str,
+        location: List[Union[str, int]],
+        message: Optional[str],
+        input_value: Any,
+        context: Optional[Dict[str, Any]],
+        title: Optional[str] = None,
+        choices: Optional[List[int]] = None,
+    ) -> None: ...
+    def __reduce__(self) -> Tuple[Type['PyLineError'], Tuple[Any, ...]]: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
 class ValidationError(ValueError):
-    model_name: str
+    title: str
 
+    def __init__(self, line_errors: List[PyLineError], title: str) -> None: ...
+    def __reduce__(self) -> Tuple[Type['ValidationError'], Tuple[List[PyLineError], str]]: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
     def error_count(self) -> int: ...
-    def errors(self) -> List[Dict[str, Any]]: ...
+    def errors(self, include_url: bool = False) -> List[Dict[str, Any]]: ...
+    def group_errors(self) -> List[Dict[str, Any]]: ...
+    def pretty(self, truncate_length: int = 50, group_by_location: bool = False, include_url: bool = False) -> str: ...
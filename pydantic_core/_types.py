@@ -1,7 +1,7 @@
 from __future__ import annotations
 
 import sys
-from typing import Any, Callable, Dict, List, Sequence, Union
+from typing import Any, Callable, Dict, List, Sequence, Tuple, Type, Union
 
 if sys.version_info < (3, 11):
     from typing_extensions import NotRequired, Required
@@ -16,16 +16,58 @@ else:
 
 class AnySchema(TypedDict):
     type: Literal['any']
+    deep_copy: NotRequired[bool]
 
 
 class BoolSchema(TypedDict):
     type: Literal['bool']
     strict: NotRequired[bool]
+    disabled_coercions: NotRequired[List[LaxCoercion]]
+
+
+# `if` and `else` are keywords, hence the functional syntax
+ConditionalSchema = TypedDict(
+    'ConditionalSchema', {'type': Literal['conditional'], 'if': 'Schema', 'then': 'Schema', 'else': 'Schema'}
+)
+
+
+StrictCoercion = Literal['array_to_set', 'array_to_tuple', 'object_to_model', 'str_to_complex']
+# coercions allowed in strict mode for each input source, sources omitted keep their defaults:
+# none for python, all for JSON
+StrictCoercions = Dict[Literal['python', 'json'], List[StrictCoercion]]
+# conversions lax mode doesn't make, as `{from}_to_{to}`
+LaxCoercion = Literal[
+    'bytes_to_str',
+    'int_to_str',
+    'float_to_str',
+    'str_to_bool',
+    'bytes_to_bool',
+    'int_to_bool',
+    'str_to_int',
+    'bytes_to_int',
+    'bool_to_int',
+    'float_to_int',
+    'str_to_float',
+    'bytes_to_float',
+    'bool_to_float',
+]
 
 
 class ConfigSchema(TypedDict, total=False):
     strict: bool
+    strict_coercions: StrictCoercions
+    disabled_coercions: List[LaxCoercion]
     extra: Literal['allow', 'forbid', 'ignore']
+    sort_extra: bool
+    populate_by_name: bool
+    error_loc_by: Literal['name', 'alias', 'both']
+    union_error_strategy: Literal['all', 'best_match']
+
+
+class DefinitionsSchema(TypedDict):
+    type: Literal['definitions']
+    definitions: Dict[str, Schema]
+    schema: Schema
 
 
 class DictSchema(TypedDict, total=False):
@@ -34,6 +76,8 @@ class DictSchema(TypedDict, total=False):
     values: Schema  # default: AnySchema
     min_items: int
     max_items: int
+    strict: bool
+    strict_mapping: bool
 
 
 class FloatSchema(TypedDict, total=False):
@@ -43,10 +87,19 @@ class FloatSchema(TypedDict, total=False):
     ge: float
     lt: float
     gt: float
+    epsilon: float
     strict: bool
+    strict_protocols: bool
+    disabled_coercions: List[LaxCoercion]
     default: float
 
 
+class ComplexSchema(TypedDict, total=False):
+    type: Required[Literal['complex']]
+    strict: bool
+    strict_coercions: StrictCoercions
+
+
 # TODO: function could be typed based on mode
 class FunctionSchema(TypedDict):
     type: Literal['function']
@@ -63,6 +116,23 @@ class IntSchema(TypedDict, total=False):
     lt: int
     gt: int
     strict: bool
+    strict_protocols: bool
+    disabled_coercions: List[LaxCoercion]
+
+
+class IsInstanceSchema(TypedDict):
+    type: Literal['is-instance']
+    class_type: Union[type, Tuple[type, ...]]
+
+
+class IsSubclassSchema(TypedDict):
+    type: Literal['is-subclass']
+    class_type: Union[type, Tuple[type, ...]]
+
+
+class JsonSchema(TypedDict, total=False):
+    type: Required[Literal['json']]
+    schema: Schema
 
 
 class ListSchema(TypedDict, total=False):
@@ -70,6 +140,8 @@ class ListSchema(TypedDict, total=False):
     items: Schema  # default: AnySchema
     min_items: int
     max_items: int
+    strict: bool
+    parallel: bool
 
 
 class LiteralSchema(TypedDict):
@@ -81,16 +153,38 @@ class ModelClassSchema(TypedDict):
     type: Literal['model-class']
     class_type: type
     model: ModelSchema
+    strict: NotRequired[bool]
+    strict_coercions: NotRequired[StrictCoercions]
+    exact_type: NotRequired[bool]
+
+
+class MacAddressSchema(TypedDict, total=False):
+    type: Required[Literal['mac-address']]
+    strict: bool
 
 
 class ModelSchema(TypedDict):
     type: Literal['model']
     fields: Dict[str, Schema]
     name: NotRequired[str]
+    title: NotRequired[str]
     extra_validator: NotRequired[Schema]
+    sort_extra: NotRequired[bool]
+    populate_by_name: NotRequired[bool]
+    error_loc_by: NotRequired[Literal['name', 'alias', 'both']]
+    output: NotRequired[Literal['tuple', 'dict']]
+    output_class: NotRequired[Type[Any]]
+    output_factory: NotRequired[Callable[..., Any]]
     config: NotRequired[ConfigSchema]
 
 
+class MultiHostUrlSchema(TypedDict, total=False):
+    type: Required[Literal['multi-host-url']]
+    allowed_schemes: List[str]
+    default_port: int
+    strict: bool
+
+
 class NoneSchema(TypedDict):
     type: Literal['none']
 
@@ -117,7 +211,10 @@ class SetSchema(TypedDict):
     items: Schema
     min_items: NotRequired[int]
     max_items: NotRequired[int]
+    unique: NotRequired[bool]
+    item_key: NotRequired[Union[Literal['serialized'], Callable[[Any], Any]]]
     strict: NotRequired[bool]
+    strict_coercions: NotRequired[StrictCoercions]
 
 
 class StringSchema(TypedDict, total=False):
@@ -128,12 +225,37 @@ class StringSchema(TypedDict, total=False):
     strip_whitespace: bool
     to_lower: bool
     to_upper: bool
+    normalize: Literal['NFC', 'NFD', 'NFKC', 'NFKD']
+    coerce_numbers_to_str: bool
+    subclass: Literal['allow', 'convert', 'forbid']
+    intern: bool
+    disabled_coercions: List[LaxCoercion]
+    strict: bool
+
+
+class TimezoneNameSchema(TypedDict, total=False):
+    type: Required[Literal['timezone-name']]
     strict: bool
+    return_zoneinfo: bool
+
+
+class TupleSchema(TypedDict, total=False):
+    type: Required[Literal['tuple']]
+    prefix_items: List[Schema]
+    items: Schema
+    min_items: int
+    max_items: int
+    strict: bool
+    strict_coercions: StrictCoercions
 
 
 class UnionSchema(TypedDict):
     type: Literal['union']
     choices: List[Schema]
+    error_strategy: NotRequired[Literal['all', 'best_match']]
+    custom_error_message: NotRequired[str]
+    custom_error_title: NotRequired[str]
+    custom_error_mode: NotRequired[Literal['replace', 'prepend']]
     strict: NotRequired[bool]
     default: NotRequired[Any]
 
@@ -143,19 +265,25 @@ class UnionSchema(TypedDict):
 BareType = Literal[
     'any',
     'bool',
+    'complex',
     'dict',
     'float',
     'function',
     'int',
+    'json',
     'list',
+    'mac-address',
     'model',
     'model-class',
+    'multi-host-url',
     'none',
     'optional',
     'recursive-container',
     'recursive-reference',
     'set',
     'str',
+    'timezone-name',
+    'tuple',
     'union',
 ]
 
@@ -163,19 +291,40 @@ Schema = Union[
     BareType,
     AnySchema,
     BoolSchema,
+    ComplexSchema,
+    ConditionalSchema,
+    DefinitionsSchema,
     DictSchema,
     FloatSchema,
     FunctionSchema,
     IntSchema,
+    IsInstanceSchema,
+    IsSubclassSchema,
+    JsonSchema,
     ListSchema,
     LiteralSchema,
+    MacAddressSchema,
     ModelSchema,
     ModelClassSchema,
+    MultiHostUrlSchema,
     NoneSchema,
     OptionalSchema,
     RecursiveContainerSchema,
     RecursiveReferenceSchema,
     SetSchema,
     StringSchema,
+    TimezoneNameSchema,
+    TupleSchema,
     UnionSchema,
+    # classes defining `__get_pydantic_core_schema__`
+    Type[Any],
 ]
+
+
+# passed as `limits` to `validate_python` and `validate_json`, not part of the schema
+class ValidationLimits(TypedDict, total=False):
+    max_items_total: int
+    max_string_length: int
+    max_string_bytes_total: int
+    max_depth: int
+    timeout: float  # seconds
@@ -1,8 +1,12 @@
-use pyo3::exceptions::PyKeyError;
+use pyo3::create_exception;
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 use pyo3::FromPyObject;
 
+// raised by `SchemaDict` and converted to a `SchemaError` with the key's path in the schema by
+// `BuildContext::schema_error`, the arguments are the dict, the key and the error extracting its value if any
+create_exception!(_pydantic_core, SchemaKeyError, crate::SchemaError);
+
 pub trait SchemaDict<'py> {
     fn get_as<T>(&'py self, key: &str) -> PyResult<Option<T>>
     where
@@ -19,7 +23,7 @@ impl<'py> SchemaDict<'py> for PyDict {
         T: FromPyObject<'py>,
     {
         match self.get_item(key) {
-            Some(t) => Ok(Some(<T>::extract(t)?)),
+            Some(t) => Ok(Some(extract_key(self, key, t)?)),
             None => Ok(None),
         }
     }
@@ -29,12 +33,25 @@ impl<'py> SchemaDict<'py> for PyDict {
         T: FromPyObject<'py>,
     {
         match self.get_item(key) {
-            Some(t) => <T>::extract(t),
-            None => py_error!(PyKeyError; r#""{}" is required"#, key),
+            Some(t) => extract_key(self, key, t),
+            None => Err(key_error(self, key, None)),
         }
     }
 }
 
+/// Extract a value from the schema, errors are converted to a `SchemaKeyError`
+fn extract_key<'py, T>(dict: &'py PyDict, key: &str, value: &'py PyAny) -> PyResult<T>
+where
+    T: FromPyObject<'py>,
+{
+    <T>::extract(value).map_err(|err| key_error(dict, key, Some(err.to_string())))
+}
+
+fn key_error(dict: &PyDict, key: &str, cause: Option<String>) -> PyErr {
+    let dict: Py<PyDict> = dict.into();
+    SchemaKeyError::new_err((dict, key.to_string(), cause))
+}
+
 pub fn schema_or_config<'py, T>(
     schema: &'py PyDict,
     config: Option<&'py PyDict>,
@@ -53,6 +70,22 @@ where
     }
 }
 
+/// Combine the config of a parent schema with the config of a schema nested within it, keys set by `config`
+/// take precedence over those inherited from `parent`
+pub fn merge_config<'py>(parent: Option<&'py PyDict>, config: Option<&'py PyDict>) -> PyResult<Option<&'py PyDict>> {
+    match (parent, config) {
+        (Some(parent), Some(config)) => {
+            let merged = parent.copy()?;
+            for (key, value) in config.iter() {
+                merged.set_item(key, value)?;
+            }
+            Ok(Some(merged))
+        }
+        (parent, None) => Ok(parent),
+        (None, config) => Ok(config),
+    }
+}
+
 pub fn is_strict(schema: &PyDict, config: Option<&PyDict>) -> PyResult<bool> {
     Ok(schema_or_config(schema, config, "strict", "strict")?.unwrap_or(false))
 }
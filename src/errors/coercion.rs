@@ -0,0 +1,30 @@
+use pyo3::intern;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use super::collector::{Collected, Collector};
+use super::line_error::{location_as_py, Location};
+
+/// A lax mode coercion performed during validation, e.g. from `"123"` to `123`
+#[derive(Debug, Clone)]
+pub struct Coercion {
+    pub location: Location,
+    pub from_type: String,
+    pub to_type: &'static str,
+}
+
+impl Collected for Coercion {
+    fn location_mut(&mut self) -> &mut Location {
+        &mut self.location
+    }
+
+    fn as_dict(&self, py: Python) -> PyResult<PyObject> {
+        let dict = PyDict::new(py);
+        dict.set_item(intern!(py, "loc"), location_as_py(py, &self.location))?;
+        dict.set_item(intern!(py, "from_type"), &self.from_type)?;
+        dict.set_item(intern!(py, "to_type"), self.to_type)?;
+        Ok(dict.into_py(py))
+    }
+}
+
+pub type CoercionsCollector = Collector<Coercion>;
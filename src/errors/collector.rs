@@ -0,0 +1,76 @@
+use std::cell::RefCell;
+
+use pyo3::prelude::*;
+
+use super::line_error::{LocItem, Location, ValLineError};
+use super::validation_exception::PyLineError;
+
+/// Something recorded during validation without failing it, e.g. a warning
+pub trait Collected {
+    fn location_mut(&mut self) -> &mut Location;
+
+    fn as_dict(&self, py: Python) -> PyResult<PyObject>;
+}
+
+/// Records items during validation, like line errors, items are added with a location relative to the
+/// validator which found them, parent validators then prefix the location with the field name or index
+#[derive(Debug)]
+pub struct Collector<T>(RefCell<Vec<T>>);
+
+impl<T> Default for Collector<T> {
+    fn default() -> Self {
+        Self(RefCell::new(Vec::new()))
+    }
+}
+
+impl<T: Collected> Collector<T> {
+    pub fn push(&self, item: T) {
+        self.0.borrow_mut().push(item);
+    }
+
+    /// Used with `prefix_location` and `truncate` to find the items added after this point
+    pub fn checkpoint(&self) -> usize {
+        self.0.borrow().len()
+    }
+
+    pub fn prefix_location(&self, checkpoint: usize, loc_item: LocItem) {
+        for item in self.0.borrow_mut()[checkpoint..].iter_mut() {
+            item.location_mut().insert(0, loc_item.clone());
+        }
+    }
+
+    pub fn truncate(&self, checkpoint: usize) {
+        self.0.borrow_mut().truncate(checkpoint);
+    }
+
+    /// Remove all items, keeping the allocated capacity so the collector can be reused
+    pub fn clear(&mut self) {
+        self.0.get_mut().clear();
+    }
+
+    pub fn as_list(&self, py: Python) -> PyResult<PyObject> {
+        Ok(self
+            .0
+            .borrow()
+            .iter()
+            .map(|item| item.as_dict(py))
+            .collect::<PyResult<Vec<PyObject>>>()?
+            .into_py(py))
+    }
+
+    pub(super) fn items(&self) -> std::cell::Ref<Vec<T>> {
+        self.0.borrow()
+    }
+}
+
+/// Collects errors from invalid model fields when validating with `collect_errors=True`, models then drop the
+/// invalid fields instead of failing
+pub type ErrorsCollector = Collector<PyLineError>;
+
+impl ErrorsCollector {
+    pub fn extend(&self, py: Python, line_errors: Vec<ValLineError>) {
+        for line_error in line_errors {
+            self.push(PyLineError::new(py, line_error));
+        }
+    }
+}
@@ -1,23 +1,52 @@
-use strum::{Display, EnumMessage};
+use std::sync::RwLock;
 
-#[derive(Debug, Display, EnumMessage, Clone)]
+use pyo3::once_cell::GILOnceCell;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyString};
+use strum::{Display, EnumIter, EnumMessage, IntoEnumIterator};
+
+#[derive(Debug, Display, EnumMessage, EnumIter, Clone)]
 #[strum(serialize_all = "snake_case")]
 pub enum ErrorKind {
     #[strum(message = "Invalid input")]
     InvalidInput,
     #[strum(message = "Invalid JSON")]
     InvalidJson,
+    #[strum(message = "JSON input must be a string or bytes")]
+    JsonType,
+    // ---------------------
+    // limits set via the `limits` argument
+    #[strum(message = "Input must not have more than {max_items_total} items in total")]
+    LimitItemsTotal,
+    #[strum(message = "String must not be longer than {max_string_length} characters")]
+    LimitStringLength,
+    #[strum(message = "Input must not have more than {max_string_bytes_total} bytes of strings in total")]
+    LimitStringBytesTotal,
+    #[strum(message = "Input must not be nested more than {max_depth} levels deep")]
+    LimitDepth,
+    #[strum(message = "Validation took longer than {timeout} seconds")]
+    LimitTimeout,
+    #[strum(message = "Recursion error - cyclic reference detected")]
+    RecursionLoop,
     // ---------------------
     // model specific errors
     #[strum(message = "Field required")]
     Missing,
     #[strum(message = "Extra values are not permitted")]
     ExtraForbidden,
+    #[strum(message = "Field is frozen")]
+    FrozenField,
     #[strum(message = "Model keys must be strings")]
     InvalidKey,
     #[strum(message = "Value must be an instance of {class_name}")]
     ModelType,
     // ---------------------
+    // isinstance/issubclass errors
+    #[strum(message = "Value must be an instance of {class_name}")]
+    IsInstanceOf,
+    #[strum(message = "Value must be a subclass of {class_name}")]
+    IsSubclassOf,
+    // ---------------------
     // None errors
     #[strum(message = "Value must be None/null")]
     NoneRequired,
@@ -30,12 +59,24 @@ pub enum ErrorKind {
     StrType,
     #[strum(message = "Value must be a valid string, unable to parse raw data as a unicode string")]
     StrUnicode,
+    #[strum(message = "Value must be a plain string, not a subclass of str")]
+    StrSubclass,
     #[strum(message = "String must have at least {min_length} characters")]
     StrTooShort,
     #[strum(message = "String must have at most {max_length} characters")]
     StrTooLong,
     #[strum(message = "String must match pattern '{pattern}'")]
     StrPatternMismatch,
+    #[strum(message = "Value must be a valid IANA timezone name")]
+    TimezoneName,
+    #[strum(message = "Value must be a valid MAC address")]
+    MacAddress,
+    // ---------------------
+    // URL errors
+    #[strum(message = "Value must be a valid URL, {error}")]
+    UrlParsing,
+    #[strum(message = "URL scheme must be one of: {expected_schemes}")]
+    UrlScheme,
     // ---------------------
     // dict errors
     #[strum(message = "Value must be a valid dictionary")]
@@ -64,6 +105,16 @@ pub enum ErrorKind {
     SetTooShort,
     #[strum(message = "Set must have at most {max_length} items")]
     SetTooLong,
+    #[strum(message = "Set items must be unique, this item is a duplicate of item {first_index}")]
+    SetDuplicate,
+    // ---------------------
+    // tuple errors
+    #[strum(message = "Value must be a valid tuple")]
+    TupleType,
+    #[strum(message = "Tuple must have at least {min_length} items")]
+    TupleTooShort,
+    #[strum(message = "Tuple must have at most {max_length} items")]
+    TupleTooLong,
     // ---------------------
     // bool errors
     #[strum(message = "Value must be a valid boolean")]
@@ -107,12 +158,22 @@ pub enum ErrorKind {
     #[strum(message = "Value must be less than or equal to {le}")]
     FloatLessThanEqual,
     // ---------------------
+    // complex errors
+    #[strum(message = "Value must be a valid complex number")]
+    ComplexType,
+    #[strum(message = "Value must be a valid complex number, unable to parse string as a complex number")]
+    ComplexParsing,
+    // ---------------------
     // python errors from functions (the messages here will not be used as we sett message in these cases)
     #[strum(message = "Invalid value")]
     ValueError,
     #[strum(message = "Assertion failed")]
     AssertionError,
     // ---------------------
+    // unions with a `custom_error_message`, which is used instead of this message
+    #[strum(message = "Value must match one of the union's choices")]
+    UnionError,
+    // ---------------------
     // literals
     #[strum(serialize = "literal_error", message = "Value must be {expected}")]
     LiteralSingleError,
@@ -125,3 +186,69 @@ impl Default for ErrorKind {
         ErrorKind::InvalidInput
     }
 }
+
+static KIND_NAMES: GILOnceCell<Vec<Py<PyString>>> = GILOnceCell::new();
+
+/// Documentation URLs for errors are this followed by the error kind, it can be overridden by setting
+/// `PYDANTIC_CORE_ERRORS_URL` when building, or at runtime with `set_errors_url`, e.g. to link to internal
+/// documentation
+const ERRORS_URL: &str = match option_env!("PYDANTIC_CORE_ERRORS_URL") {
+    Some(url) => url,
+    None => concat!("https://errors.pydantic.dev/", env!("CARGO_PKG_VERSION"), "/v/"),
+};
+
+static ERRORS_URL_OVERRIDE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Set the URL error kinds are appended to in the URLs of errors, replacing `ERRORS_URL`, e.g. to link to
+/// internal documentation; calling without an argument restores the default
+#[pyfunction(url = "None")]
+pub fn set_errors_url(url: Option<String>) {
+    if let Ok(mut current) = ERRORS_URL_OVERRIDE.write() {
+        *current = url;
+    }
+}
+
+impl ErrorKind {
+    pub fn url(&self) -> String {
+        match ERRORS_URL_OVERRIDE.read().as_deref() {
+            Ok(Some(url)) => format!("{}{}", url, self),
+            _ => format!("{}{}", ERRORS_URL, self),
+        }
+    }
+
+    /// The name of this kind as an interned python string, these are created once so building `errors()`
+    /// doesn't need to format and allocate a new string for every error
+    pub fn py_name<'py>(&self, py: Python<'py>) -> &'py PyString {
+        let names = KIND_NAMES.get_or_init(py, || {
+            ErrorKind::iter()
+                .map(|kind| PyString::intern(py, &kind.to_string()).into())
+                .collect()
+        });
+        // variants are iterated in definition order, so the discriminant is the index
+        names[self.clone() as usize].as_ref(py)
+    }
+}
+
+/// List all error kinds with their message template and the context fields used in the template,
+/// e.g. for generating documentation
+#[pyfunction]
+pub fn list_all_errors(py: Python) -> PyResult<&PyList> {
+    let errors = PyList::empty(py);
+    for kind in ErrorKind::iter() {
+        let template = match kind.get_message() {
+            Some(message) => message.to_string(),
+            None => kind.to_string(),
+        };
+        let context_fields: Vec<&str> = template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(field, _)| field))
+            .collect();
+        let error = PyDict::new(py);
+        error.set_item("kind", kind.py_name(py))?;
+        error.set_item("message_template", &template)?;
+        error.set_item("context_fields", context_fields)?;
+        errors.append(error)?;
+    }
+    Ok(errors)
+}
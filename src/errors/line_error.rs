@@ -1,7 +1,9 @@
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyDict, PyList, PyTuple};
+use smallvec::SmallVec;
 
 use crate::input::ToPy;
 
@@ -14,14 +16,53 @@ use super::kinds::ErrorKind;
 pub enum LocItem {
     S(String),
     I(usize),
+    /// Any other dict key, e.g. a tuple or float, kept so `loc` contains the key itself, along with its
+    /// repr which is used to display and compare locations
+    K(PyObject, String),
+    /// Marks an error in validating a dict key rather than its value, rendered as `[key]`
+    Key,
+    /// The union choice an error came from, e.g. `Model[1]`, rendered and converted to python like `S` but never
+    /// equal to it; when errors are grouped, the errors of each choice are nested as alternatives under the location
+    /// before it
+    Choice(String),
 }
 // we could use the From trait to make creating Location's much easier, would it be worth it?
 
+const KEY_MARKER: &str = "[key]";
+
+impl PartialEq for LocItem {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (LocItem::S(a), LocItem::S(b)) | (LocItem::Choice(a), LocItem::Choice(b)) => a == b,
+            (LocItem::I(a), LocItem::I(b)) => a == b,
+            (LocItem::K(_, a), LocItem::K(_, b)) => a == b,
+            (LocItem::Key, LocItem::Key) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for LocItem {}
+
+impl Hash for LocItem {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            LocItem::S(s) | LocItem::Choice(s) => s.hash(state),
+            LocItem::I(i) => i.hash(state),
+            LocItem::K(_, repr) => repr.hash(state),
+            LocItem::Key => (),
+        }
+    }
+}
+
 impl fmt::Display for LocItem {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            LocItem::S(s) => write!(f, "{}", s),
+            LocItem::S(s) | LocItem::Choice(s) => write!(f, "{}", s),
             LocItem::I(i) => write!(f, "{}", i),
+            LocItem::K(_, repr) => write!(f, "{}", repr),
+            LocItem::Key => write!(f, "{}", KEY_MARKER),
         }
     }
 }
@@ -29,7 +70,22 @@ impl fmt::Display for LocItem {
 /// Error locations are represented by a vector of `LocItem`s.
 /// e.g. if the error occurred in the third member of a list called `foo`,
 /// the location would be `["foo", 2]`.
-pub type Location = Vec<LocItem>;
+/// Locations are rarely deep, so they're stored inline to avoid allocating a vector for every error.
+pub type Location = SmallVec<[LocItem; 4]>;
+
+pub fn location_as_py(py: Python, location: &[LocItem]) -> PyObject {
+    let mut loc: Vec<PyObject> = Vec::with_capacity(location.len());
+    for item in location {
+        let item: PyObject = match item {
+            LocItem::S(key) | LocItem::Choice(key) => key.into_py(py),
+            LocItem::I(index) => index.into_py(py),
+            LocItem::K(key, _) => key.clone_ref(py),
+            LocItem::Key => KEY_MARKER.into_py(py),
+        };
+        loc.push(item);
+    }
+    loc.into_py(py)
+}
 
 /// A `ValLineError` is a single error that occurred during validation which is converted to a `PyLineError`
 /// to eventually form a `ValidationError`.
@@ -41,26 +97,43 @@ pub struct ValLineError<'a> {
     pub message: Option<String>,
     pub input_value: InputValue<'a>,
     pub context: Context,
+    /// the exception raised by a function validator which caused this error, if any
+    pub cause: Option<PyErr>,
+    /// the title of the model in which this error occurred, set only for models nested within another model
+    pub title: Option<String>,
 }
 
 impl<'a> ValLineError<'a> {
-    pub fn with_prefix_location(mut self, location: &Location) -> Self {
-        if self.location.is_empty() {
-            self.location = location.clone();
-        } else {
-            // TODO we could perhaps instead store "reverse_location" in the ValLineError, then reverse it in
-            // `PyLineError` so we could just extend here.
-            self.location = [location.clone(), self.location].concat();
+    /// Convert the input value to a python object so the error no longer borrows the input, see
+    /// `ValError::into_owned`
+    pub fn into_owned<'b>(self, py: Python) -> ValLineError<'b> {
+        ValLineError {
+            kind: self.kind,
+            location: self.location,
+            message: self.message,
+            input_value: self.input_value.into_owned(py),
+            context: self.context,
+            cause: self.cause,
+            title: self.title,
         }
+    }
+
+    pub fn with_prefix_location(mut self, location: &Location) -> Self {
+        // TODO we could perhaps instead store "reverse_location" in the ValLineError, then reverse it in
+        // `PyLineError` so we could just extend here.
+        self.location.insert_many(0, location.iter().cloned());
         self
     }
 }
 
+/// The input which caused an error, usually a reference to the input so errors which are discarded
+/// (e.g. by unions or lax fallbacks) never pay for converting it to a python object
 #[derive(Debug)]
 pub enum InputValue<'a> {
     None,
     InputRef(&'a dyn ToPy),
-    PyObject(PyObject),
+    /// a python object owned by the error, it doesn't borrow the input so can outlive it
+    Owned(PyObject),
 }
 
 impl Default for InputValue<'_> {
@@ -74,12 +147,21 @@ impl<'a> InputValue<'a> {
         match self {
             Self::None => py.None(),
             Self::InputRef(input) => input.to_py(py),
-            Self::PyObject(py_obj) => py_obj.into_py(py),
+            Self::Owned(py_obj) => py_obj.clone_ref(py),
+        }
+    }
+
+    /// Only references need converting, owned values are moved into the new `InputValue` as they are
+    pub fn into_owned<'b>(self, py: Python) -> InputValue<'b> {
+        match self {
+            Self::None => InputValue::None,
+            Self::InputRef(input) => InputValue::Owned(input.to_py(py)),
+            Self::Owned(py_obj) => InputValue::Owned(py_obj),
         }
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Context(Vec<(String, ContextValue)>);
 
 impl Context {
@@ -94,7 +176,11 @@ impl Context {
     pub fn render(&self, template: String) -> String {
         let mut rendered = template;
         for (key, value) in &self.0 {
-            rendered = rendered.replace(&format!("{{{}}}", key), &value.to_string());
+            let value = match value {
+                ContextValue::L(_, rendered_list) => rendered_list.clone(),
+                value => value.to_string(),
+            };
+            rendered = rendered.replace(&format!("{{{}}}", key), &value);
         }
         rendered
     }
@@ -112,12 +198,14 @@ impl fmt::Display for Context {
     }
 }
 
-// maybe this is overkill and we should just use fmt::Display an convert to string when creating Context?
+/// Context values keep their type in `errors()` so clients can build their own messages from them
 #[derive(Debug, Clone)]
 pub enum ContextValue {
     S(String),
-    I(i64),
+    I(i128),
     F(f64),
+    /// A list of values, e.g. the choices of a literal, along with how it's rendered in messages
+    L(Vec<ContextValue>, String),
 }
 
 impl fmt::Display for ContextValue {
@@ -126,8 +214,64 @@ impl fmt::Display for ContextValue {
             ContextValue::S(v) => write!(f, "{}", v),
             ContextValue::I(v) => write!(f, "{}", v),
             ContextValue::F(v) => write!(f, "{}", v),
+            // items are written as they would be in python, e.g. `1, '1'`
+            ContextValue::L(items, _) => write!(f, "{}", join_reprs(items)),
+        }
+    }
+}
+
+// how lists are rendered in messages isn't kept when errors are pickled, so it's ignored here
+impl PartialEq for ContextValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ContextValue::S(a), ContextValue::S(b)) => a == b,
+            (ContextValue::I(a), ContextValue::I(b)) => a == b,
+            (ContextValue::F(a), ContextValue::F(b)) => a == b,
+            (ContextValue::L(a, _), ContextValue::L(b, _)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+fn join_items(items: &[ContextValue]) -> String {
+    items.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(", ")
+}
+
+fn join_reprs(items: &[ContextValue]) -> String {
+    items.iter().map(item_repr).collect::<Vec<String>>().join(", ")
+}
+
+fn item_repr(value: &ContextValue) -> String {
+    match value {
+        ContextValue::S(v) => str_repr(v),
+        ContextValue::L(items, _) => format!("[{}]", join_reprs(items)),
+        value => value.to_string(),
+    }
+}
+
+/// A string quoted and escaped as python's `repr` would
+fn str_repr(value: &str) -> String {
+    let quote = match value.contains('\'') && !value.contains('"') {
+        true => '"',
+        false => '\'',
+    };
+    let mut repr = String::with_capacity(value.len() + 2);
+    repr.push(quote);
+    for c in value.chars() {
+        match c {
+            '\\' => repr.push_str("\\\\"),
+            '\n' => repr.push_str("\\n"),
+            '\r' => repr.push_str("\\r"),
+            '\t' => repr.push_str("\\t"),
+            c if c == quote => {
+                repr.push('\\');
+                repr.push(c);
+            }
+            c => repr.push(c),
         }
     }
+    repr.push(quote);
+    repr
 }
 
 impl From<String> for ContextValue {
@@ -144,13 +288,19 @@ impl From<&str> for ContextValue {
 
 impl From<i64> for ContextValue {
     fn from(int: i64) -> Self {
+        Self::I(int.into())
+    }
+}
+
+impl From<i128> for ContextValue {
+    fn from(int: i128) -> Self {
         Self::I(int)
     }
 }
 
 impl From<usize> for ContextValue {
     fn from(u: usize) -> Self {
-        Self::I(u as i64)
+        Self::I(u as i128)
     }
 }
 
@@ -160,13 +310,41 @@ impl From<f64> for ContextValue {
     }
 }
 
+impl<'a> FromPyObject<'a> for ContextValue {
+    fn extract(obj: &'a PyAny) -> PyResult<Self> {
+        if let Ok(int) = obj.extract::<i128>() {
+            Ok(Self::I(int))
+        } else if let Ok(float) = obj.extract::<f64>() {
+            Ok(Self::F(float))
+        } else if obj.cast_as::<PyList>().is_ok() || obj.cast_as::<PyTuple>().is_ok() {
+            let items: Vec<ContextValue> = obj.iter()?.map(|item| item?.extract()).collect::<PyResult<_>>()?;
+            let rendered = join_items(&items);
+            Ok(Self::L(items, rendered))
+        } else {
+            Ok(Self::S(obj.str()?.to_string()))
+        }
+    }
+}
+
 impl ToPyObject for ContextValue {
     fn to_object(&self, py: Python) -> PyObject {
         match self {
             ContextValue::S(v) => v.into_py(py),
             ContextValue::I(v) => v.into_py(py),
             ContextValue::F(v) => v.into_py(py),
+            ContextValue::L(items, _) => PyList::new(py, items).into_py(py),
+        }
+    }
+}
+
+impl<'a> FromPyObject<'a> for Context {
+    fn extract(obj: &'a PyAny) -> PyResult<Self> {
+        let dict: &PyDict = obj.cast_as()?;
+        let mut items: Vec<(String, ContextValue)> = Vec::with_capacity(dict.len());
+        for (key, value) in dict.iter() {
+            items.push((key.extract()?, value.extract()?));
         }
+        Ok(Self(items))
     }
 }
 
@@ -1,12 +1,18 @@
+mod coercion;
+mod collector;
 mod kinds;
 mod line_error;
 mod val_error;
+mod val_warning;
 mod validation_exception;
 
-pub use self::kinds::ErrorKind;
-pub use self::line_error::{Context, InputValue, LocItem, Location, ValLineError};
+pub use self::coercion::{Coercion, CoercionsCollector};
+pub use self::collector::ErrorsCollector;
+pub use self::kinds::{list_all_errors, set_errors_url, ErrorKind};
+pub use self::line_error::{Context, ContextValue, InputValue, LocItem, Location, ValLineError};
 pub use self::val_error::{as_internal, ValError, ValResult};
-pub use self::validation_exception::{as_validation_err, ValidationError};
+pub use self::val_warning::{ValWarning, WarningCategory, WarningsCollector};
+pub use self::validation_exception::{as_validation_err, PyLineError, ValidationError};
 
 /// Utility for concisely creating a `ValLineError`
 /// can either take just `py` and a `value` (the given value) in which case kind `ErrorKind::ValueError` is used as kind
@@ -36,6 +36,20 @@ impl<'a> Error for ValError<'a> {
     }
 }
 
+impl<'a> ValError<'a> {
+    /// Convert line errors so they no longer borrow the input, this is required where validators create
+    /// new input which doesn't outlive validation, e.g. function and json validators; it's done only once the
+    /// error escapes the inner validator so errors handled internally are never converted
+    pub fn into_owned<'b>(self, py: Python) -> ValError<'b> {
+        match self {
+            ValError::LineErrors(line_errors) => {
+                ValError::LineErrors(line_errors.into_iter().map(|e| e.into_owned(py)).collect())
+            }
+            ValError::InternalErr(err) => ValError::InternalErr(err),
+        }
+    }
+}
+
 pub fn as_internal<'a>(err: PyErr) -> ValError<'a> {
     ValError::InternalErr(err)
 }
@@ -0,0 +1,74 @@
+use pyo3::intern;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use super::collector::{Collected, Collector};
+use super::line_error::{location_as_py, Location};
+
+/// The python warning class used when warnings are emitted
+#[derive(Debug, Clone, Copy)]
+pub enum WarningCategory {
+    User,
+    // e.g. input for deprecated fields
+    Deprecation,
+}
+
+impl WarningCategory {
+    fn name(self) -> &'static str {
+        match self {
+            Self::User => "UserWarning",
+            Self::Deprecation => "DeprecationWarning",
+        }
+    }
+}
+
+/// A non-fatal problem found during validation, warnings don't cause validation to fail
+#[derive(Debug, Clone)]
+pub struct ValWarning {
+    pub location: Location,
+    pub message: String,
+    pub category: WarningCategory,
+}
+
+impl ValWarning {
+    fn pretty(&self) -> String {
+        if self.location.is_empty() {
+            self.message.clone()
+        } else {
+            let loc = self
+                .location
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<String>>()
+                .join(" -> ");
+            format!("{}: {}", loc, self.message)
+        }
+    }
+}
+
+impl Collected for ValWarning {
+    fn location_mut(&mut self) -> &mut Location {
+        &mut self.location
+    }
+
+    fn as_dict(&self, py: Python) -> PyResult<PyObject> {
+        let dict = PyDict::new(py);
+        dict.set_item(intern!(py, "loc"), location_as_py(py, &self.location))?;
+        dict.set_item(intern!(py, "message"), &self.message)?;
+        Ok(dict.into_py(py))
+    }
+}
+
+pub type WarningsCollector = Collector<ValWarning>;
+
+impl WarningsCollector {
+    /// Emit each warning as a python warning of its category, e.g. `UserWarning`
+    pub fn emit(&self, py: Python) -> PyResult<()> {
+        let builtins = py.import("builtins")?;
+        for warning in self.items().iter() {
+            let category = builtins.getattr(warning.category.name())?;
+            PyErr::warn(py, category, &warning.pretty(), 1)?;
+        }
+        Ok(())
+    }
+}
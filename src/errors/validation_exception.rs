@@ -1,21 +1,33 @@
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
 use std::error::Error;
 use std::fmt;
 use std::fmt::Write;
+use std::hash::{Hash, Hasher};
 
+use indexmap::IndexMap;
+use once_cell::sync::OnceCell;
 use pyo3::exceptions::PyValueError;
+use pyo3::intern;
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{PyDict, PyList};
 use pyo3::PyErrArguments;
 
-use strum::EnumMessage;
+use strum::{EnumMessage, IntoEnumIterator};
+use unicode_segmentation::UnicodeSegmentation;
 
+use crate::build_tools::py_error;
+use crate::input::ToLocItem;
+
+use super::collector::Collected;
 use super::kinds::ErrorKind;
-use super::line_error::{Context, LocItem, Location, ValLineError};
+use super::line_error::{location_as_py, Context, InputValue, LocItem, Location, ValLineError};
 
 use super::ValError;
 
-#[pyclass(extends=PyValueError)]
-#[derive(Debug)]
+#[pyclass(extends=PyValueError, module = "pydantic_core._pydantic_core")]
+#[derive(Debug, PartialEq, Hash)]
 pub struct ValidationError {
     line_errors: Vec<PyLineError>,
     title: String,
@@ -23,17 +35,28 @@ pub struct ValidationError {
 
 pub fn as_validation_err(py: Python, model_name: &str, error: ValError) -> PyErr {
     match error {
-        ValError::LineErrors(raw_errors) => {
+        ValError::LineErrors(mut raw_errors) => {
+            // if one or more errors were caused by an exception in a function validator, the first such exception
+            // becomes the `__cause__` of the `ValidationError`
+            let cause = raw_errors.iter_mut().find_map(|e| e.cause.take());
             let line_errors: Vec<PyLineError> = raw_errors.into_iter().map(|e| PyLineError::new(py, e)).collect();
-            ValidationError::new_err((line_errors, model_name.to_string()))
+            let err = ValidationError::new_err((line_errors, model_name.to_string()));
+            if cause.is_some() {
+                err.set_cause(py, cause);
+            }
+            err
         }
         ValError::InternalErr(err) => err,
     }
 }
 
+/// Default maximum number of characters of `input_value` to show in the string representation of an error
+const DEFAULT_TRUNCATE_LENGTH: usize = 50;
+
 impl fmt::Display for ValidationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.display(None))
+        let display = Python::with_gil(|py| self.display(py, DEFAULT_TRUNCATE_LENGTH, false, false));
+        write!(f, "{}", display)
     }
 }
 
@@ -46,20 +69,159 @@ impl ValidationError {
         PyErr::new::<ValidationError, A>(args)
     }
 
-    fn display(&self, py: Option<Python>) -> String {
+    /// The errors of a `ValidationError` raised by a function validator, which become errors of that validator
+    pub fn val_line_errors<'a>(&self, py: Python) -> Vec<ValLineError<'a>> {
+        self.line_errors.iter().map(|e| e.val_line_error(py)).collect()
+    }
+
+    /// Each line error is rendered once, since that needs the repr of its input value, later calls only format the
+    /// rendered errors
+    fn display(&self, py: Python, truncate_length: usize, group_by_location: bool, include_url: bool) -> String {
         let count = self.line_errors.len();
         let plural = if count == 1 { "" } else { "s" };
-        let line_errors = self
-            .line_errors
+        // without grouping, every error is a group of its own so has its location shown
+        let groups: Vec<RenderedGroup> = match group_by_location {
+            true => self
+                .grouped_line_errors()
+                .into_iter()
+                .map(|group| group.rendered(py))
+                .collect(),
+            false => self
+                .line_errors
+                .iter()
+                .map(|e| RenderedGroup {
+                    location: location_string(&e.location),
+                    errors: vec![e.rendered(py)],
+                    alternatives: Vec::new(),
+                })
+                .collect(),
+        };
+        let line_errors = groups
             .iter()
-            .map(|i| i.pretty(py))
+            .map(|group| display_group(group, truncate_length, include_url))
             .collect::<Result<Vec<_>, _>>()
             .unwrap_or_else(|err| vec![format!("[error formatting line errors: {}]", err)])
             .join("\n");
-        format!(
-            "{} validation error{} for {}\n{}",
-            count, plural, self.title, line_errors
-        )
+        let title = &self.title;
+        format!("{} validation error{} for {}\n{}", count, plural, title, line_errors)
+    }
+
+    /// Line errors grouped by their location up to the first union choice, see `LineErrorGroup`; groups are
+    /// ordered by the first occurrence of each location
+    fn grouped_line_errors(&self) -> Vec<LineErrorGroup> {
+        let mut groups: IndexMap<&[LocItem], LineErrorGroup> = IndexMap::new();
+        for line_error in &self.line_errors {
+            let location = &line_error.location;
+            let choice_index = location
+                .iter()
+                .position(|item| matches!(item, LocItem::Choice(_)))
+                .unwrap_or(location.len());
+            let (path, alternative) = location.split_at(choice_index);
+            let group = groups.entry(path).or_insert_with(|| LineErrorGroup {
+                location: path,
+                errors: Vec::new(),
+                alternatives: IndexMap::new(),
+            });
+            match alternative.is_empty() {
+                true => group.errors.push(line_error),
+                false => group.alternatives.entry(alternative).or_default().push(line_error),
+            }
+        }
+        groups.into_values().collect()
+    }
+}
+
+/// Line errors for the same field: the errors at its location, and the errors from each choice of a union at that
+/// location as alternatives, by their location from the choice on, e.g. errors at `a -> int` and `a -> str` are
+/// both in the group for `a`
+struct LineErrorGroup<'a> {
+    location: &'a [LocItem],
+    errors: Vec<&'a PyLineError>,
+    alternatives: IndexMap<&'a [LocItem], Vec<&'a PyLineError>>,
+}
+
+impl<'a> LineErrorGroup<'a> {
+    fn rendered(&self, py: Python) -> RenderedGroup<'a> {
+        RenderedGroup {
+            location: location_string(self.location),
+            errors: self.errors.iter().map(|e| e.rendered(py)).collect(),
+            alternatives: self
+                .alternatives
+                .iter()
+                .map(|(location, line_errors)| {
+                    let location = location_string(location).unwrap_or_default();
+                    (location, line_errors.iter().map(|e| e.rendered(py)).collect())
+                })
+                .collect(),
+        }
+    }
+
+    fn as_py(&self, py: Python) -> PyResult<PyObject> {
+        let errors_as_py = |line_errors: &[&PyLineError]| {
+            line_errors
+                .iter()
+                .map(|e| e.as_dict_without_loc(py))
+                .collect::<PyResult<Vec<PyObject>>>()
+        };
+        let group = PyDict::new(py);
+        group.set_item(intern!(py, "loc"), location_as_py(py, self.location))?;
+        group.set_item(intern!(py, "errors"), errors_as_py(&self.errors)?)?;
+        if !self.alternatives.is_empty() {
+            let mut alternatives: Vec<PyObject> = Vec::with_capacity(self.alternatives.len());
+            for (location, line_errors) in &self.alternatives {
+                let alternative = PyDict::new(py);
+                alternative.set_item(intern!(py, "loc"), location_as_py(py, location))?;
+                alternative.set_item(intern!(py, "errors"), errors_as_py(line_errors)?)?;
+                alternatives.push(alternative.into_py(py));
+            }
+            group.set_item(intern!(py, "alternatives"), alternatives)?;
+        }
+        Ok(group.into_py(py))
+    }
+}
+
+/// A `LineErrorGroup` as shown by `pretty()`, built with the GIL so it can be displayed without it
+struct RenderedGroup<'a> {
+    location: Option<String>,
+    errors: Vec<&'a RenderedLineError>,
+    alternatives: Vec<(String, Vec<&'a RenderedLineError>)>,
+}
+
+/// The group's location is shown once before the details of its errors, each alternative's location is shown
+/// indented below it followed by the details of its errors indented further
+fn display_group(group: &RenderedGroup, truncate_length: usize, include_url: bool) -> Result<String, fmt::Error> {
+    let mut output: Vec<String> = Vec::with_capacity(group.errors.len() + 1);
+    // a group without a location, e.g. a union at the top level, has its alternatives shown like locations
+    let indent = match group.location {
+        Some(ref loc) => {
+            output.push(loc.clone());
+            "  "
+        }
+        None => "",
+    };
+    for line_error in &group.errors {
+        output.push(line_error.pretty_details(truncate_length, include_url)?);
+    }
+    for (location, line_errors) in &group.alternatives {
+        output.push(format!("{}{}", indent, location));
+        for line_error in line_errors {
+            let details = line_error.pretty_details(truncate_length, include_url)?;
+            output.extend(details.lines().map(|line| format!("{}{}", indent, line)));
+        }
+    }
+    Ok(output.join("\n"))
+}
+
+fn location_string(location: &[LocItem]) -> Option<String> {
+    match location.is_empty() {
+        true => None,
+        false => Some(
+            location
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<String>>()
+                .join(" -> "),
+        ),
     }
 }
 
@@ -78,6 +240,11 @@ impl ValidationError {
         Self { line_errors, title }
     }
 
+    fn __reduce__(&self, py: Python) -> PyObject {
+        let args = (self.line_errors.clone(), self.title.clone());
+        (py.get_type::<Self>(), args).into_py(py)
+    }
+
     #[getter]
     fn title(&self) -> String {
         self.title.clone()
@@ -87,41 +254,82 @@ impl ValidationError {
         self.line_errors.len()
     }
 
-    fn errors(&self, py: Python) -> PyResult<PyObject> {
+    #[args(include_url = "false")]
+    fn errors(&self, py: Python, include_url: bool) -> PyResult<PyObject> {
         Ok(self
             .line_errors
             .iter()
-            .map(|e| e.as_dict(py))
+            .map(|e| e.as_dict_with_url(py, include_url))
             .collect::<PyResult<Vec<PyObject>>>()?
             .into_py(py))
     }
 
+    fn group_errors(&self, py: Python) -> PyResult<PyObject> {
+        let groups = self
+            .grouped_line_errors()
+            .iter()
+            .map(|group| group.as_py(py))
+            .collect::<PyResult<Vec<PyObject>>>()?;
+        Ok(groups.into_py(py))
+    }
+
+    #[args(
+        truncate_length = "DEFAULT_TRUNCATE_LENGTH",
+        group_by_location = "false",
+        include_url = "false"
+    )]
+    fn pretty(&self, py: Python, truncate_length: usize, group_by_location: bool, include_url: bool) -> String {
+        self.display(py, truncate_length, group_by_location, include_url)
+    }
+
     fn __repr__(&self, py: Python) -> String {
-        self.display(Some(py))
+        self.display(py, DEFAULT_TRUNCATE_LENGTH, false, false)
     }
 
     fn __str__(&self, py: Python) -> String {
         self.__repr__(py)
     }
+
+    fn __richcmp__(&self, other: &PyAny, op: CompareOp, py: Python) -> PyObject {
+        match (op, other.extract::<PyRef<Self>>()) {
+            (CompareOp::Eq, Ok(other)) => (*self == *other).into_py(py),
+            (CompareOp::Ne, Ok(other)) => (*self != *other).into_py(py),
+            _ => py.NotImplemented(),
+        }
+    }
+
+    fn __hash__(&self) -> isize {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish() as isize
+    }
 }
 
-macro_rules! truncate_input_value {
-    ($out:expr, $value:expr) => {
-        if $value.len() > 50 {
-            write!(
-                $out,
-                ", input_value={}...{}",
-                &$value[0..25],
-                &$value[$value.len() - 24..]
-            )?;
-        } else {
-            write!($out, ", input_value={}", $value)?;
+/// Shorten `value` to at most `max_length` characters by keeping its start and end, slicing is done on
+/// grapheme boundaries so characters built from several code points, e.g. accented letters, emoji sequences
+/// and flags, are never split
+fn truncate_input_value(value: &str, max_length: usize) -> Cow<str> {
+    match value.char_indices().nth(max_length) {
+        Some(_) => {
+            let start_chars = max_length / 2;
+            let end_chars = max_length.saturating_sub(start_chars + 1);
+            let start_end = value.char_indices().nth(start_chars).map_or(value.len(), |(i, _)| i);
+            let end_start = match end_chars {
+                0 => value.len(),
+                _ => value.char_indices().rev().nth(end_chars - 1).map_or(0, |(i, _)| i),
+            };
+            // the start may only shrink and the end may only move forward, so the result is never longer
+            let boundaries: Vec<usize> = value.grapheme_indices(true).map(|(i, _)| i).collect();
+            let start_end = boundaries.iter().rev().find(|i| **i <= start_end).map_or(0, |i| *i);
+            let end_start = boundaries.iter().find(|i| **i >= end_start).map_or(value.len(), |i| *i);
+            Cow::Owned(format!("{}...{}", &value[..start_end], &value[end_start..]))
         }
-    };
+        None => Cow::Borrowed(value),
+    }
 }
 
 /// `PyLineError` are the public version of `ValLineError`, as help and used in `ValidationError`s
-#[pyclass]
+#[pyclass(module = "pydantic_core._pydantic_core")]
 #[derive(Debug, Clone)]
 pub struct PyLineError {
     kind: ErrorKind,
@@ -129,6 +337,8 @@ pub struct PyLineError {
     message: Option<String>,
     input_value: PyObject,
     context: Context,
+    title: Option<String>,
+    rendered: OnceCell<RenderedLineError>,
 }
 
 impl PyLineError {
@@ -139,17 +349,49 @@ impl PyLineError {
             message: raw_error.message,
             input_value: raw_error.input_value.to_py(py),
             context: raw_error.context,
+            title: raw_error.title,
+            rendered: OnceCell::new(),
         }
     }
 
-    pub fn as_dict(&self, py: Python) -> PyResult<PyObject> {
+    fn val_line_error<'a>(&self, py: Python) -> ValLineError<'a> {
+        ValLineError {
+            kind: self.kind.clone(),
+            location: self.location.clone(),
+            message: self.message.clone(),
+            input_value: InputValue::Owned(self.input_value.clone_ref(py)),
+            context: self.context.clone(),
+            cause: None,
+            title: self.title.clone(),
+        }
+    }
+
+    fn as_dict_without_loc(&self, py: Python) -> PyResult<PyObject> {
         let dict = PyDict::new(py);
-        dict.set_item("kind", self.kind())?;
-        dict.set_item("loc", self.location(py))?;
-        dict.set_item("message", self.message())?;
-        dict.set_item("input_value", &self.input_value)?;
+        dict.set_item(intern!(py, "kind"), self.kind.py_name(py))?;
+        self.add_details(py, dict)?;
+        Ok(dict.into_py(py))
+    }
+
+    fn add_details(&self, py: Python, dict: &PyDict) -> PyResult<()> {
+        dict.set_item(intern!(py, "message"), self.message())?;
+        dict.set_item(intern!(py, "input_value"), &self.input_value)?;
         if !self.context.is_empty() {
-            dict.set_item("context", &self.context)?;
+            dict.set_item(intern!(py, "context"), &self.context)?;
+        }
+        if let Some(ref title) = self.title {
+            dict.set_item(intern!(py, "title"), title)?;
+        }
+        Ok(())
+    }
+
+    fn as_dict_with_url(&self, py: Python, include_url: bool) -> PyResult<PyObject> {
+        let dict = PyDict::new(py);
+        dict.set_item(intern!(py, "kind"), self.kind.py_name(py))?;
+        dict.set_item(intern!(py, "loc"), location_as_py(py, &self.location))?;
+        self.add_details(py, dict)?;
+        if include_url {
+            dict.set_item(intern!(py, "url"), self.kind.url())?;
         }
         Ok(dict.into_py(py))
     }
@@ -158,18 +400,6 @@ impl PyLineError {
         self.kind.to_string()
     }
 
-    fn location(&self, py: Python) -> PyObject {
-        let mut loc: Vec<PyObject> = Vec::with_capacity(self.location.len());
-        for location in &self.location {
-            let item: PyObject = match location {
-                LocItem::S(key) => key.into_py(py),
-                LocItem::I(index) => index.into_py(py),
-            };
-            loc.push(item);
-        }
-        loc.into_py(py)
-    }
-
     fn message(&self) -> String {
         let raw = self.raw_message();
         if self.context.is_empty() {
@@ -191,42 +421,183 @@ impl PyLineError {
         }
     }
 
-    fn pretty(&self, py: Option<Python>) -> Result<String, fmt::Error> {
-        let mut output = String::with_capacity(200);
-        if !self.location.is_empty() {
-            let loc = self
-                .location
-                .iter()
-                .map(|i| i.to_string())
-                .collect::<Vec<String>>()
-                .join(" -> ");
-            writeln!(output, "{}", &loc)?;
-        }
+    fn rendered(&self, py: Python) -> &RenderedLineError {
+        self.rendered.get_or_init(|| RenderedLineError::new(py, self))
+    }
+}
 
-        write!(output, "  {} [kind={}", self.message(), self.kind())?;
+/// The strings shown for a line error in `ValidationError.__repr__` and `pretty()`, rendered once per error
+#[derive(Debug, Clone)]
+struct RenderedLineError {
+    message: String,
+    kind: String,
+    context: Option<String>,
+    title: Option<String>,
+    input_repr: String,
+    input_type: Option<String>,
+    url: String,
+}
 
-        if !self.context.is_empty() {
-            write!(output, ", context={}", self.context)?;
+impl RenderedLineError {
+    fn new(py: Python, line_error: &PyLineError) -> Self {
+        let input_value = line_error.input_value.as_ref(py);
+        Self {
+            message: line_error.message(),
+            kind: line_error.kind(),
+            context: match line_error.context.is_empty() {
+                true => None,
+                false => Some(line_error.context.to_string()),
+            },
+            title: line_error.title.clone(),
+            input_repr: repr(input_value).unwrap_or_else(|_| input_value.to_string()),
+            input_type: input_value.get_type().name().ok().map(|name| name.to_string()),
+            url: line_error.kind.url(),
         }
-        if let Some(py) = py {
-            let input_value = self.input_value.as_ref(py);
-            let input_str = match repr(input_value) {
-                Ok(s) => s,
-                Err(_) => input_value.to_string(),
-            };
-            truncate_input_value!(output, input_str);
+    }
 
-            if let Ok(type_) = input_value.get_type().name() {
-                write!(output, ", input_type={}", type_)?;
-            }
-        } else {
-            truncate_input_value!(output, self.input_value.to_string());
+    fn pretty_details(&self, truncate_length: usize, include_url: bool) -> Result<String, fmt::Error> {
+        let mut output = String::with_capacity(200);
+        write!(output, "  {} [kind={}", self.message, self.kind)?;
+
+        if let Some(ref context) = self.context {
+            write!(output, ", context={}", context)?;
+        }
+        if let Some(ref title) = self.title {
+            write!(output, ", title={}", title)?;
+        }
+        let input_str = truncate_input_value(&self.input_repr, truncate_length);
+        write!(output, ", input_value={}", input_str)?;
+        if let Some(ref input_type) = self.input_type {
+            write!(output, ", input_type={}", input_type)?;
         }
         output.push(']');
+        if include_url {
+            write!(output, "\n    For further information visit {}", self.url)?;
+        }
         Ok(output)
     }
 }
 
+/// Line errors are compared by kind, location, message and context, input values are ignored since they're often
+/// not comparable, e.g. after pickling
+impl PartialEq for PyLineError {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind() == other.kind()
+            && self.location == other.location
+            && self.message() == other.message()
+            && self.context == other.context
+    }
+}
+
+impl Hash for PyLineError {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind().hash(state);
+        self.location.hash(state);
+        self.message().hash(state);
+        // context values may be floats, which can't be hashed, but their string form can be
+        self.context.to_string().hash(state);
+    }
+}
+
+impl Collected for PyLineError {
+    fn location_mut(&mut self) -> &mut Location {
+        &mut self.location
+    }
+
+    fn as_dict(&self, py: Python) -> PyResult<PyObject> {
+        self.as_dict_with_url(py, false)
+    }
+}
+
+#[pymethods]
+impl PyLineError {
+    /// `choices` are the indexes of the items in `location` which are union choices
+    #[new]
+    #[args(title = "None", choices = "None")]
+    fn py_new(
+        kind: &str,
+        location: &PyList,
+        message: Option<String>,
+        input_value: PyObject,
+        context: Option<Context>,
+        title: Option<String>,
+        choices: Option<Vec<usize>>,
+    ) -> PyResult<Self> {
+        let kind = match ErrorKind::iter().find(|k| k.to_string() == kind) {
+            Some(kind) => kind,
+            None => return py_error!(PyValueError; "Invalid error kind: '{}'", kind),
+        };
+        let mut location: Location = location.iter().map(|item| item.to_loc()).collect();
+        for index in choices.unwrap_or_default() {
+            match location.get_mut(index) {
+                Some(LocItem::S(choice)) => {
+                    let choice = std::mem::take(choice);
+                    location[index] = LocItem::Choice(choice);
+                }
+                _ => return py_error!(PyValueError; "Invalid choice {}, not the index of a str in location", index),
+            }
+        }
+        Ok(Self {
+            kind,
+            location,
+            message,
+            input_value,
+            context: context.unwrap_or_default(),
+            title,
+            rendered: OnceCell::new(),
+        })
+    }
+
+    /// the message is pickled already rendered since some kinds share the same name, input values which can't
+    /// be pickled are replaced by their repr
+    fn __reduce__(&self, py: Python) -> PyObject {
+        let choices: Vec<usize> = self
+            .location
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| matches!(item, LocItem::Choice(_)))
+            .map(|(index, _)| index)
+            .collect();
+        let args = (
+            self.kind(),
+            location_as_py(py, &self.location),
+            self.message(),
+            picklable_input_value(py, &self.input_value),
+            self.context.to_object(py),
+            self.title.clone(),
+            choices,
+        );
+        (py.get_type::<Self>(), args).into_py(py)
+    }
+
+    fn __richcmp__(&self, other: &PyAny, op: CompareOp, py: Python) -> PyObject {
+        match (op, other.extract::<PyRef<Self>>()) {
+            (CompareOp::Eq, Ok(other)) => (*self == *other).into_py(py),
+            (CompareOp::Ne, Ok(other)) => (*self != *other).into_py(py),
+            _ => py.NotImplemented(),
+        }
+    }
+
+    fn __hash__(&self) -> isize {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish() as isize
+    }
+}
+
+fn picklable_input_value(py: Python, input_value: &PyObject) -> PyObject {
+    let dumped = py
+        .import("pickle")
+        .and_then(|pickle| pickle.call_method1("dumps", (input_value,)));
+    match dumped {
+        Ok(_) => input_value.clone_ref(py),
+        Err(_) => match repr(input_value.as_ref(py)) {
+            Ok(s) => s.into_py(py),
+            Err(_) => py.None(),
+        },
+    }
+}
+
 fn repr(v: &PyAny) -> PyResult<String> {
     v.repr()?.extract()
 }
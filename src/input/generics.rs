@@ -1,7 +1,8 @@
 use enum_dispatch::enum_dispatch;
 use indexmap::map::Iter;
 
-use pyo3::types::{PyAny, PyDict, PyFrozenSet, PyList, PySet, PyTuple};
+use pyo3::prelude::*;
+use pyo3::types::{PyAny, PyDict, PyFrozenSet, PyList, PySet, PyString, PyTuple};
 use pyo3::{ffi, AsPyPointer};
 
 use super::parse_json::{JsonArray, JsonInput, JsonObject};
@@ -192,6 +193,53 @@ impl<'a> SequenceNext<'a> for JsonArrayIterator<'a> {
     }
 }
 
+/// A key to find in a `GenericMapping`, created once, e.g. per model field when the validator is built:
+/// python dicts are searched with an interned python string, whose hash is cached, and JSON objects with the rust
+/// string, so JSON lookups are pure rust string comparisons which never create python objects.
+/// Integer keys, e.g. for sparse-index style payloads, are found in python dicts by the int, or failing that by its
+/// numeric string, and in JSON objects by the numeric string since JSON keys are always strings
+#[derive(Debug, Clone)]
+pub struct LookupKey {
+    key: String,
+    py_key: Py<PyString>,
+    py_int_key: Option<PyObject>,
+}
+
+impl LookupKey {
+    pub fn new(py: Python, key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            py_key: PyString::intern(py, key).into(),
+            py_int_key: None,
+        }
+    }
+
+    pub fn new_int(py: Python, key: i64) -> Self {
+        Self {
+            py_int_key: Some(key.into_py(py)),
+            ..Self::new(py, &key.to_string())
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.key
+    }
+
+    pub fn py_key(&self) -> &Py<PyString> {
+        &self.py_key
+    }
+
+    pub fn py_get<'a>(&self, dict: &'a PyDict) -> Option<&'a PyAny> {
+        let py = dict.py();
+        if let Some(ref py_int_key) = self.py_int_key {
+            if let Some(value) = dict.get_item(py_int_key.as_ref(py)) {
+                return Some(value);
+            }
+        }
+        dict.get_item(self.py_key.as_ref(py))
+    }
+}
+
 #[enum_dispatch]
 pub enum GenericMapping<'a> {
     PyDict(&'a PyDict),
@@ -203,7 +251,7 @@ pub enum GenericMapping<'a> {
 pub trait MappingLenIter<'a> {
     fn generic_len(&self) -> usize;
 
-    fn generic_get(&self, key: &str) -> Option<&'a dyn Input>;
+    fn generic_get(&self, key: &LookupKey) -> Option<&'a dyn Input>;
 
     fn generic_iter(&self) -> GenericMappingIter<'a>;
 }
@@ -215,8 +263,8 @@ impl<'a> MappingLenIter<'a> for &'a PyDict {
     }
 
     #[inline]
-    fn generic_get(&self, key: &str) -> Option<&'a dyn Input> {
-        self.get_item(key).map(|v| v as &dyn Input)
+    fn generic_get(&self, key: &LookupKey) -> Option<&'a dyn Input> {
+        key.py_get(self).map(|v| v as &dyn Input)
     }
 
     #[inline]
@@ -232,8 +280,8 @@ impl<'a> MappingLenIter<'a> for &'a JsonObject {
     }
 
     #[inline]
-    fn generic_get(&self, key: &str) -> Option<&'a dyn Input> {
-        self.get(key).map(|v| v as &dyn Input)
+    fn generic_get(&self, key: &LookupKey) -> Option<&'a dyn Input> {
+        self.get(key.as_str()).map(|v| v as &dyn Input)
     }
 
     #[inline]
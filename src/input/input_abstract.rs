@@ -4,14 +4,34 @@ use pyo3::types::PyType;
 
 use crate::errors::ValResult;
 
-use super::{GenericMapping, GenericSequence, ToLocItem, ToPy};
+use super::{
+    EitherString, GenericMapping, GenericSequence, LaxType, NumericArray, StrictCoercion, StrictCoercions, ToLocItem,
+    ToPy,
+};
+
+/// Where input came from, strict mode is interpreted per source: JSON has no equivalent of many python types
+/// (e.g. model instances), so for JSON input strict mode requires the JSON type which represents the expected type,
+/// see `StrictCoercions`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputSource {
+    Python,
+    Json,
+}
 
 pub trait Input: fmt::Debug + ToPy + ToLocItem {
+    fn source(&self) -> InputSource;
+
     fn is_none(&self) -> bool;
 
-    fn strict_str(&self) -> ValResult<String>;
+    fn strict_str<'data>(&'data self) -> ValResult<EitherString<'data>>;
+
+    fn lax_str<'data>(&'data self) -> ValResult<EitherString<'data>>;
+
+    /// Get a string to be parsed as JSON, only strings, and bytes for python input, are allowed
+    fn json_str<'data>(&'data self) -> ValResult<EitherString<'data>>;
 
-    fn lax_str(&self) -> ValResult<String>;
+    /// The type of the input if lax mode may convert it to another type, see `LaxCoercions`
+    fn lax_type(&self) -> Option<LaxType>;
 
     fn strict_bool(&self) -> ValResult<bool>;
 
@@ -19,16 +39,58 @@ pub trait Input: fmt::Debug + ToPy + ToLocItem {
 
     fn strict_int(&self) -> ValResult<i64>;
 
+    /// Like `strict_int` but only `int`s are accepted, not other objects implementing `__index__`
+    fn exact_int(&self) -> ValResult<i64> {
+        self.strict_int()
+    }
+
     fn lax_int(&self) -> ValResult<i64>;
 
     fn strict_float(&self) -> ValResult<f64>;
 
+    /// Like `strict_float` but only `float`s and `int`s are accepted, not other objects implementing `__float__`
+    fn exact_float(&self) -> ValResult<f64> {
+        self.strict_float()
+    }
+
     fn lax_float(&self) -> ValResult<f64>;
 
+    /// The real and imaginary parts of a complex number
+    fn strict_complex(&self) -> ValResult<(f64, f64)>;
+
+    /// Parse a string as a complex number, e.g. `1+2j`, other input is an error
+    fn str_complex(&self) -> ValResult<(f64, f64)>;
+
+    /// `strict_complex`, also accepting strings if `coercions` allows it
+    fn strict_complex_coerced(&self, coercions: &StrictCoercions) -> ValResult<(f64, f64)> {
+        match self.strict_complex() {
+            Err(_) if self.allows_strict_coercion(coercions, StrictCoercion::StrToComplex) => self.str_complex(),
+            result => result,
+        }
+    }
+
+    fn lax_complex(&self) -> ValResult<(f64, f64)>;
+
+    /// Whether the input is an instance of `class`, including instances of subclasses
     fn strict_model_check(&self, class: &PyType) -> ValResult<bool>;
 
+    /// Like `strict_model_check` but only instances of exactly `class` are accepted
+    fn exact_model_check(&self, class: &PyType) -> ValResult<bool> {
+        self.strict_model_check(class)
+    }
+
+    /// Whether `coercions` allows `coercion` in strict mode for input from this input's source
+    fn allows_strict_coercion(&self, coercions: &StrictCoercions, coercion: StrictCoercion) -> bool {
+        coercions.allows(self.source(), coercion)
+    }
+
     fn strict_dict<'data>(&'data self) -> ValResult<GenericMapping<'data>>;
 
+    /// Like `strict_dict` but other mappings, e.g. instances of `collections.abc.Mapping` subclasses, are accepted
+    fn strict_mapping<'data>(&'data self) -> ValResult<GenericMapping<'data>> {
+        self.strict_dict()
+    }
+
     fn lax_dict<'data>(&'data self, _try_instance: bool) -> ValResult<GenericMapping<'data>> {
         self.strict_dict()
     }
@@ -41,7 +103,38 @@ pub trait Input: fmt::Debug + ToPy + ToLocItem {
 
     fn strict_set<'data>(&'data self) -> ValResult<GenericSequence<'data>>;
 
+    /// `strict_set`, also accepting arrays if `coercions` allows it
+    fn strict_set_coerced<'data>(&'data self, coercions: &StrictCoercions) -> ValResult<GenericSequence<'data>> {
+        match self.strict_set() {
+            Err(err) if self.allows_strict_coercion(coercions, StrictCoercion::ArrayToSet) => {
+                self.strict_list().map_err(|_| err)
+            }
+            result => result,
+        }
+    }
+
     fn lax_set<'data>(&'data self) -> ValResult<GenericSequence<'data>> {
         self.strict_set()
     }
+
+    fn strict_tuple<'data>(&'data self) -> ValResult<GenericSequence<'data>>;
+
+    /// `strict_tuple`, also accepting arrays if `coercions` allows it
+    fn strict_tuple_coerced<'data>(&'data self, coercions: &StrictCoercions) -> ValResult<GenericSequence<'data>> {
+        match self.strict_tuple() {
+            Err(err) if self.allows_strict_coercion(coercions, StrictCoercion::ArrayToTuple) => {
+                self.strict_list().map_err(|_| err)
+            }
+            result => result,
+        }
+    }
+
+    fn lax_tuple<'data>(&'data self) -> ValResult<GenericSequence<'data>> {
+        self.strict_tuple()
+    }
+
+    /// Numeric arrays are accepted by lax lists and tuples, `None` if this isn't one
+    fn lax_numeric_array(&self) -> Option<NumericArray> {
+        None
+    }
 }
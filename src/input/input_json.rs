@@ -3,31 +3,54 @@ use pyo3::types::PyType;
 use crate::errors::{err_val_error, ErrorKind, InputValue, ValResult};
 
 use super::generics::{GenericMapping, GenericSequence};
-use super::input_abstract::Input;
+use super::input_abstract::{Input, InputSource};
+use super::lax_coercions::LaxType;
 use super::parse_json::JsonInput;
-use super::shared::{float_as_int, int_as_bool, str_as_bool, str_as_int};
+use super::return_enums::EitherString;
+use super::shared::{float_as_int, int_as_bool, pair_as_complex, str_as_bool, str_as_complex, str_as_int};
 
 impl Input for JsonInput {
+    fn source(&self) -> InputSource {
+        InputSource::Json
+    }
+
     fn is_none(&self) -> bool {
         matches!(self, JsonInput::Null)
     }
 
-    fn strict_str(&self) -> ValResult<String> {
+    fn strict_str<'data>(&'data self) -> ValResult<EitherString<'data>> {
         match self {
-            JsonInput::String(s) => Ok(s.to_string()),
+            JsonInput::String(s) => Ok(s.as_str().into()),
             _ => err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::StrType),
         }
     }
 
-    fn lax_str(&self) -> ValResult<String> {
+    fn lax_str<'data>(&'data self) -> ValResult<EitherString<'data>> {
         match self {
-            JsonInput::String(s) => Ok(s.to_string()),
-            JsonInput::Int(int) => Ok(int.to_string()),
-            JsonInput::Float(float) => Ok(float.to_string()),
+            JsonInput::String(s) => Ok(s.as_str().into()),
+            JsonInput::Int(int) => Ok(int.to_string().into()),
+            JsonInput::Float(float) => Ok(float.to_string().into()),
             _ => err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::StrType),
         }
     }
 
+    fn json_str<'data>(&'data self) -> ValResult<EitherString<'data>> {
+        match self {
+            JsonInput::String(s) => Ok(s.as_str().into()),
+            _ => err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::JsonType),
+        }
+    }
+
+    fn lax_type(&self) -> Option<LaxType> {
+        match self {
+            JsonInput::String(_) => Some(LaxType::Str),
+            JsonInput::Bool(_) => Some(LaxType::Bool),
+            JsonInput::Int(_) => Some(LaxType::Int),
+            JsonInput::Float(_) => Some(LaxType::Float),
+            _ => None,
+        }
+    }
+
     fn strict_bool(&self) -> ValResult<bool> {
         match self {
             JsonInput::Bool(b) => Ok(*b),
@@ -89,6 +112,26 @@ impl Input for JsonInput {
         }
     }
 
+    fn strict_complex(&self) -> ValResult<(f64, f64)> {
+        err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::ComplexType)
+    }
+
+    fn str_complex(&self) -> ValResult<(f64, f64)> {
+        match self {
+            JsonInput::String(str) => str_as_complex(self, str),
+            _ => err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::ComplexType),
+        }
+    }
+
+    fn lax_complex(&self) -> ValResult<(f64, f64)> {
+        match self {
+            JsonInput::Float(f) => Ok((*f, 0.0)),
+            JsonInput::Int(i) => Ok((*i as f64, 0.0)),
+            JsonInput::Array(array) => pair_as_complex(self, array.iter()),
+            _ => self.str_complex(),
+        }
+    }
+
     fn strict_model_check(&self, _class: &PyType) -> ValResult<bool> {
         Ok(false)
     }
@@ -108,26 +151,54 @@ impl Input for JsonInput {
     }
 
     fn strict_set<'data>(&'data self) -> ValResult<GenericSequence<'data>> {
-        // we allow a list here since otherwise it would be impossible to create a set from JSON
+        // JSON has no sets, in strict mode arrays are accepted if `StrictCoercion::ArrayToSet` is allowed
+        err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::SetType)
+    }
+
+    fn lax_set<'data>(&'data self) -> ValResult<GenericSequence<'data>> {
         match self {
             JsonInput::Array(a) => Ok(a.into()),
             _ => err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::SetType),
         }
     }
+
+    fn strict_tuple<'data>(&'data self) -> ValResult<GenericSequence<'data>> {
+        // JSON has no tuples, in strict mode arrays are accepted if `StrictCoercion::ArrayToTuple` is allowed
+        err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::TupleType)
+    }
+
+    fn lax_tuple<'data>(&'data self) -> ValResult<GenericSequence<'data>> {
+        match self {
+            JsonInput::Array(a) => Ok(a.into()),
+            _ => err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::TupleType),
+        }
+    }
 }
 
 /// Required for Dict keys so the string can behave like an Input
 impl Input for String {
+    fn source(&self) -> InputSource {
+        InputSource::Json
+    }
+
     fn is_none(&self) -> bool {
         false
     }
 
-    fn strict_str(&self) -> ValResult<String> {
-        Ok(self.clone())
+    fn strict_str<'data>(&'data self) -> ValResult<EitherString<'data>> {
+        Ok(self.as_str().into())
+    }
+
+    fn lax_str<'data>(&'data self) -> ValResult<EitherString<'data>> {
+        Ok(self.as_str().into())
+    }
+
+    fn json_str<'data>(&'data self) -> ValResult<EitherString<'data>> {
+        Ok(self.as_str().into())
     }
 
-    fn lax_str(&self) -> ValResult<String> {
-        Ok(self.clone())
+    fn lax_type(&self) -> Option<LaxType> {
+        Some(LaxType::Str)
     }
 
     fn strict_bool(&self) -> ValResult<bool> {
@@ -160,6 +231,18 @@ impl Input for String {
         }
     }
 
+    fn strict_complex(&self) -> ValResult<(f64, f64)> {
+        err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::ComplexType)
+    }
+
+    fn str_complex(&self) -> ValResult<(f64, f64)> {
+        str_as_complex(self, self)
+    }
+
+    fn lax_complex(&self) -> ValResult<(f64, f64)> {
+        self.str_complex()
+    }
+
     fn strict_model_check(&self, _class: &PyType) -> ValResult<bool> {
         Ok(false)
     }
@@ -175,4 +258,8 @@ impl Input for String {
     fn strict_set<'data>(&'data self) -> ValResult<GenericSequence<'data>> {
         err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::SetType)
     }
+
+    fn strict_tuple<'data>(&'data self) -> ValResult<GenericSequence<'data>> {
+        err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::TupleType)
+    }
 }
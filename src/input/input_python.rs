@@ -1,53 +1,90 @@
+use std::borrow::Cow;
 use std::str::from_utf8;
 
+use pyo3::buffer::{Element, PyBuffer};
+use pyo3::conversion::AsPyPointer;
+use pyo3::exceptions::PyAttributeError;
+use pyo3::ffi;
+use pyo3::intern;
+use pyo3::once_cell::GILOnceCell;
 use pyo3::prelude::*;
-use pyo3::types::{PyBytes, PyDict, PyFrozenSet, PyInt, PyList, PyMapping, PySet, PyString, PyTuple, PyType};
+use pyo3::types::{
+    PyBool, PyByteArray, PyBytes, PyComplex, PyDict, PyFloat, PyFrozenSet, PyInt, PyList, PyMapping, PySet, PyString,
+    PyTuple, PyType,
+};
 
 use crate::errors::{as_internal, err_val_error, ErrorKind, InputValue, ValResult};
 
 use super::generics::{GenericMapping, GenericSequence};
-use super::input_abstract::Input;
-use super::shared::{float_as_int, int_as_bool, str_as_bool, str_as_int};
+use super::input_abstract::{Input, InputSource};
+use super::lax_coercions::LaxType;
+use super::return_enums::{EitherString, NumericArray};
+use super::shared::{float_as_int, int_as_bool, pair_as_complex, str_as_bool, str_as_complex, str_as_int};
 
 impl Input for PyAny {
+    fn source(&self) -> InputSource {
+        InputSource::Python
+    }
+
     fn is_none(&self) -> bool {
         self.is_none()
     }
 
-    fn strict_str(&self) -> ValResult<String> {
+    fn strict_str<'data>(&'data self) -> ValResult<EitherString<'data>> {
         if let Ok(py_str) = self.cast_as::<PyString>() {
-            py_str.extract().map_err(as_internal)
+            Ok(py_str.into())
         } else {
             err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::StrType)
         }
     }
 
-    fn lax_str(&self) -> ValResult<String> {
+    fn lax_str<'data>(&'data self) -> ValResult<EitherString<'data>> {
         if let Ok(py_str) = self.cast_as::<PyString>() {
-            py_str.extract().map_err(as_internal)
-        } else if let Ok(bytes) = self.cast_as::<PyBytes>() {
-            let str = match from_utf8(bytes.as_bytes()) {
-                Ok(s) => s.to_string(),
-                Err(_) => {
-                    return err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::StrUnicode)
-                }
-            };
-            Ok(str)
+            Ok(py_str.into())
+        } else if let Some(bytes) = maybe_as_bytes(self) {
+            Ok(EitherString::Cow(bytes_as_str(self, bytes, ErrorKind::StrUnicode)?))
         } else if self.extract::<bool>().is_ok() {
             // do this before int and float parsing as `False` is cast to `0` and we don't want False to
             // be returned as a string
             err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::StrType)
         } else if let Ok(int) = self.cast_as::<PyInt>() {
             let int = i64::extract(int).map_err(as_internal)?;
-            Ok(int.to_string())
+            Ok(int.to_string().into())
         } else if let Ok(float) = f64::extract(self) {
             // don't cast_as here so Decimals are covered - internally f64:extract uses PyFloat_AsDouble
-            Ok(float.to_string())
+            Ok(float.to_string().into())
         } else {
             err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::StrType)
         }
     }
 
+    fn json_str<'data>(&'data self) -> ValResult<EitherString<'data>> {
+        if let Ok(py_str) = self.cast_as::<PyString>() {
+            Ok(py_str.into())
+        } else if let Some(bytes) = maybe_as_bytes(self) {
+            Ok(EitherString::Cow(bytes_as_str(self, bytes, ErrorKind::StrUnicode)?))
+        } else {
+            err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::JsonType)
+        }
+    }
+
+    fn lax_type(&self) -> Option<LaxType> {
+        // bool before int as bools are ints
+        if self.cast_as::<PyString>().is_ok() {
+            Some(LaxType::Str)
+        } else if self.cast_as::<PyBool>().is_ok() {
+            Some(LaxType::Bool)
+        } else if self.cast_as::<PyInt>().is_ok() {
+            Some(LaxType::Int)
+        } else if self.cast_as::<PyFloat>().is_ok() {
+            Some(LaxType::Float)
+        } else if maybe_as_bytes(self).is_some() {
+            Some(LaxType::Bytes)
+        } else {
+            None
+        }
+    }
+
     fn strict_bool(&self) -> ValResult<bool> {
         if let Ok(bool) = self.extract::<bool>() {
             Ok(bool)
@@ -69,18 +106,27 @@ impl Input for PyAny {
     }
 
     fn strict_int(&self) -> ValResult<i64> {
-        // bool check has to come before int check as bools would be cast to ints below
-        if self.extract::<bool>().is_ok() {
+        // bool check has to come before int check as bools would be cast to ints below,
+        // cast_as avoids building a python exception for every non-bool
+        if self.cast_as::<PyBool>().is_ok() {
             err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::IntType)
-        } else if let Ok(int) = self.extract::<i64>() {
+        } else if let Some(int) = maybe_as_int(self) {
             Ok(int)
         } else {
             err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::IntType)
         }
     }
 
+    fn exact_int(&self) -> ValResult<i64> {
+        if self.cast_as::<PyInt>().is_ok() {
+            self.strict_int()
+        } else {
+            err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::IntType)
+        }
+    }
+
     fn lax_int(&self) -> ValResult<i64> {
-        if let Ok(int) = self.extract::<i64>() {
+        if let Some(int) = maybe_as_int(self) {
             Ok(int)
         } else if let Some(str) = _maybe_as_string(self, ErrorKind::IntParsing)? {
             str_as_int(self, &str)
@@ -92,18 +138,26 @@ impl Input for PyAny {
     }
 
     fn strict_float(&self) -> ValResult<f64> {
-        if self.extract::<bool>().is_ok() {
+        if self.cast_as::<PyBool>().is_ok() {
             err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::FloatType)
-        } else if let Ok(float) = self.extract::<f64>() {
+        } else if let Some(float) = maybe_as_float(self) {
             Ok(float)
         } else {
             err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::FloatType)
         }
     }
 
+    fn exact_float(&self) -> ValResult<f64> {
+        if self.cast_as::<PyFloat>().is_ok() || self.cast_as::<PyInt>().is_ok() {
+            self.strict_float()
+        } else {
+            err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::FloatType)
+        }
+    }
+
     fn lax_float(&self) -> ValResult<f64> {
-        if let Ok(int) = self.extract::<f64>() {
-            Ok(int)
+        if let Some(float) = maybe_as_float(self) {
+            Ok(float)
         } else if let Some(str) = _maybe_as_string(self, ErrorKind::FloatParsing)? {
             match str.parse() {
                 Ok(i) => Ok(i),
@@ -114,7 +168,40 @@ impl Input for PyAny {
         }
     }
 
+    fn strict_complex(&self) -> ValResult<(f64, f64)> {
+        if let Ok(complex) = self.cast_as::<PyComplex>() {
+            Ok((complex.real(), complex.imag()))
+        } else {
+            err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::ComplexType)
+        }
+    }
+
+    fn str_complex(&self) -> ValResult<(f64, f64)> {
+        match _maybe_as_string(self, ErrorKind::ComplexParsing)? {
+            Some(str) => str_as_complex(self, &str),
+            None => err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::ComplexType),
+        }
+    }
+
+    fn lax_complex(&self) -> ValResult<(f64, f64)> {
+        if let Ok(complex) = self.strict_complex() {
+            Ok(complex)
+        } else if let Ok(float) = self.strict_float() {
+            Ok((float, 0.0))
+        } else if let Ok(list) = self.cast_as::<PyList>() {
+            pair_as_complex(self, list.iter())
+        } else if let Ok(tuple) = self.cast_as::<PyTuple>() {
+            pair_as_complex(self, tuple.iter())
+        } else {
+            self.str_complex()
+        }
+    }
+
     fn strict_model_check(&self, class: &PyType) -> ValResult<bool> {
+        self.is_instance(class).map_err(as_internal)
+    }
+
+    fn exact_model_check(&self, class: &PyType) -> ValResult<bool> {
         self.get_type().eq(class).map_err(as_internal)
     }
 
@@ -126,23 +213,22 @@ impl Input for PyAny {
         }
     }
 
+    fn strict_mapping<'data>(&'data self) -> ValResult<GenericMapping<'data>> {
+        if let Ok(dict) = self.cast_as::<PyDict>() {
+            Ok(dict.into())
+        } else if is_mapping(self).map_err(as_internal)? {
+            let mapping: &PyMapping = self.cast_as().map_err(|e| as_internal(e.into()))?;
+            convert_mapping(self, mapping)
+        } else {
+            err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::DictType)
+        }
+    }
+
     fn lax_dict<'data>(&'data self, try_instance: bool) -> ValResult<GenericMapping<'data>> {
         if let Ok(dict) = self.cast_as::<PyDict>() {
             Ok(dict.into())
         } else if let Ok(mapping) = self.cast_as::<PyMapping>() {
-            // this is ugly, but we'd have to do it in `input_iter` anyway
-            // we could perhaps use an indexmap instead of a python dict?
-            let dict = match mapping_as_dict(mapping) {
-                Ok(dict) => dict,
-                Err(err) => {
-                    return err_val_error!(
-                        input_value = InputValue::InputRef(self),
-                        message = Some(err.to_string()),
-                        kind = ErrorKind::DictFromMapping
-                    )
-                }
-            };
-            Ok(dict.into())
+            convert_mapping(self, mapping)
         } else if try_instance {
             let inner_dict = match instance_as_dict(self) {
                 Ok(dict) => dict,
@@ -203,6 +289,128 @@ impl Input for PyAny {
             err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::SetType)
         }
     }
+
+    fn strict_tuple<'data>(&'data self) -> ValResult<GenericSequence<'data>> {
+        if let Ok(tuple) = self.cast_as::<PyTuple>() {
+            Ok(tuple.into())
+        } else {
+            err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::TupleType)
+        }
+    }
+
+    fn lax_tuple<'data>(&'data self) -> ValResult<GenericSequence<'data>> {
+        if let Ok(tuple) = self.cast_as::<PyTuple>() {
+            Ok(tuple.into())
+        } else if let Ok(list) = self.cast_as::<PyList>() {
+            Ok(list.into())
+        } else if let Ok(set) = self.cast_as::<PySet>() {
+            Ok(set.into())
+        } else if let Ok(frozen_set) = self.cast_as::<PyFrozenSet>() {
+            Ok(frozen_set.into())
+        } else {
+            err_val_error!(input_value = InputValue::InputRef(self), kind = ErrorKind::TupleType)
+        }
+    }
+
+    fn lax_numeric_array(&self) -> Option<NumericArray> {
+        // Safety: `self` is a valid object
+        if unsafe { ffi::PyObject_CheckBuffer(self.as_ptr()) } == 0 {
+            return None;
+        }
+        // bytes and bytearrays are buffers too, but they're strings not arrays of ints
+        if self.cast_as::<PyBytes>().is_ok() || self.cast_as::<PyByteArray>().is_ok() {
+            return None;
+        }
+        if let Some(ints) = buffer_to_vec::<i64>(self) {
+            Some(NumericArray::Int(ints))
+        } else if let Some(floats) = buffer_to_vec::<f64>(self) {
+            Some(NumericArray::Float(floats))
+        } else if let Some(ints) = buffer_to_vec::<i32>(self) {
+            Some(NumericArray::Int(ints.into_iter().map(i64::from).collect()))
+        } else if let Some(ints) = buffer_to_vec::<u32>(self) {
+            Some(NumericArray::Int(ints.into_iter().map(i64::from).collect()))
+        } else if let Some(ints) = buffer_to_vec::<i16>(self) {
+            Some(NumericArray::Int(ints.into_iter().map(i64::from).collect()))
+        } else if let Some(ints) = buffer_to_vec::<u16>(self) {
+            Some(NumericArray::Int(ints.into_iter().map(i64::from).collect()))
+        } else if let Some(ints) = buffer_to_vec::<i8>(self) {
+            Some(NumericArray::Int(ints.into_iter().map(i64::from).collect()))
+        } else if let Some(ints) = buffer_to_vec::<u8>(self) {
+            Some(NumericArray::Int(ints.into_iter().map(i64::from).collect()))
+        } else if let Some(ints) = buffer_to_vec::<u64>(self) {
+            // arrays with items beyond the range of i64 aren't supported
+            let ints: Result<Vec<i64>, _> = ints.into_iter().map(i64::try_from).collect();
+            ints.ok().map(NumericArray::Int)
+        } else {
+            buffer_to_vec::<f32>(self).map(|floats| NumericArray::Float(floats.into_iter().map(f64::from).collect()))
+        }
+    }
+}
+
+/// The items of `v` if it's a one-dimensional buffer of `T`
+fn buffer_to_vec<T: Element + Copy>(v: &PyAny) -> Option<Vec<T>> {
+    let buffer = PyBuffer::<T>::get(v).ok()?;
+    match buffer.dimensions() {
+        1 => buffer.to_vec(v.py()).ok(),
+        _ => None,
+    }
+}
+
+/// `int`s and other objects implementing `__index__`, e.g. numpy integers; the type's slot is checked first so
+/// other input doesn't build a python exception
+fn maybe_as_int(v: &PyAny) -> Option<i64> {
+    // Safety: `v` is a valid object
+    match unsafe { ffi::PyIndex_Check(v.as_ptr()) } {
+        0 => None,
+        _ => v.extract().ok(),
+    }
+}
+
+/// `float`s and other objects implementing `__float__` or `__index__`, e.g. ints, numpy scalars and `Decimal`s
+fn maybe_as_float(v: &PyAny) -> Option<f64> {
+    if let Ok(float) = v.cast_as::<PyFloat>() {
+        return Some(float.value());
+    }
+    // Safety: `v` is a valid object so its type is too, `tp_as_number` is checked for null before it's read
+    let is_number = unsafe {
+        let number = (*ffi::Py_TYPE(v.as_ptr())).tp_as_number;
+        !number.is_null() && ((*number).nb_float.is_some() || (*number).nb_index.is_some())
+    };
+    match is_number {
+        true => v.extract().ok(),
+        false => None,
+    }
+}
+
+fn convert_mapping<'data>(input: &'data PyAny, mapping: &'data PyMapping) -> ValResult<'data, GenericMapping<'data>> {
+    // this is ugly, but we'd have to do it in `input_iter` anyway
+    // we could perhaps use an indexmap instead of a python dict?
+    match mapping_as_dict(mapping) {
+        Ok(dict) => Ok(dict.into()),
+        Err(err) => err_val_error!(
+            input_value = InputValue::InputRef(input),
+            message = Some(err.to_string()),
+            kind = ErrorKind::DictFromMapping
+        ),
+    }
+}
+
+static MAPPING_TYPE: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+/// Whether `v` is an instance of `collections.abc.Mapping`
+fn is_mapping(v: &PyAny) -> PyResult<bool> {
+    let py = v.py();
+    let mapping_type = match MAPPING_TYPE.get(py) {
+        Some(mapping_type) => mapping_type.as_ref(py),
+        None => {
+            let mapping_type: &PyType = py
+                .import(intern!(py, "collections.abc"))?
+                .getattr(intern!(py, "Mapping"))?
+                .cast_as()?;
+            MAPPING_TYPE.get_or_init(py, || mapping_type.into()).as_ref(py)
+        }
+    };
+    v.is_instance(mapping_type)
 }
 
 fn mapping_as_dict(mapping: &PyMapping) -> PyResult<&PyDict> {
@@ -217,30 +425,98 @@ fn mapping_as_dict(mapping: &PyMapping) -> PyResult<&PyDict> {
     Ok(dict)
 }
 
-/// This is equivalent to `GetterDict` in pydantic v1
+/// This is equivalent to `GetterDict` in pydantic v1, attributes are read from, in order:
+/// * the fields of attrs classes, listed by `__attrs_attrs__`
+/// * the `__slots__` of the class and its bases, slots which haven't been set are skipped
+/// * the instance's `__dict__`
+/// * `dir()`, for properties and attributes hidden by a custom `__dir__`, methods and other callables are skipped
+///
+/// names starting with `_` are always skipped
 fn instance_as_dict(instance: &PyAny) -> PyResult<&PyDict> {
-    let dict = PyDict::new(instance.py());
-    for k_any in instance.dir() {
-        let k_str: &str = k_any.extract()?;
-        if !k_str.starts_with('_') {
-            let v = instance.getattr(k_any)?;
-            dict.set_item(k_any, v)?;
+    let py = instance.py();
+    let dict = PyDict::new(py);
+    let class = instance.get_type();
+    if let Ok(attrs_fields) = class.getattr(intern!(py, "__attrs_attrs__")) {
+        for field in attrs_fields.iter()? {
+            add_attribute(dict, instance, field?.getattr(intern!(py, "name"))?, false)?;
+        }
+    }
+    for base in class.getattr(intern!(py, "__mro__"))?.iter()? {
+        // looked up in the class's own `__dict__` since `getattr` would find the `__slots__` of a base class
+        let class_dict = base?.getattr(intern!(py, "__dict__"))?;
+        let slots = match class_dict.get_item(intern!(py, "__slots__")) {
+            Ok(slots) => slots,
+            Err(_) => continue,
+        };
+        if slots.cast_as::<PyString>().is_ok() {
+            add_attribute(dict, instance, slots, false)?;
+        } else {
+            for name in slots.iter()? {
+                add_attribute(dict, instance, name?, false)?;
+            }
+        }
+    }
+    if let Ok(instance_dict) = instance.getattr(intern!(py, "__dict__")) {
+        if let Ok(instance_dict) = instance_dict.cast_as::<PyDict>() {
+            for name in instance_dict.keys() {
+                add_attribute(dict, instance, name, false)?;
+            }
         }
     }
+    for name in instance.dir() {
+        add_attribute(dict, instance, name, true)?;
+    }
     Ok(dict)
 }
 
+fn add_attribute(dict: &PyDict, instance: &PyAny, name: &PyAny, skip_callable: bool) -> PyResult<()> {
+    let name_str: &str = name.extract()?;
+    if name_str.starts_with('_') || dict.contains(name)? {
+        return Ok(());
+    }
+    match instance.getattr(name_str) {
+        Ok(value) if skip_callable && value.is_callable() => Ok(()),
+        Ok(value) => dict.set_item(name, value),
+        Err(err) if err.is_instance_of::<PyAttributeError>(instance.py()) => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
 /// Utility for extracting a string from a PyAny, if possible.
 fn _maybe_as_string(v: &PyAny, unicode_error: ErrorKind) -> ValResult<Option<String>> {
     if let Ok(str) = v.extract::<String>() {
         Ok(Some(str))
-    } else if let Ok(bytes) = v.cast_as::<PyBytes>() {
-        let str = match from_utf8(bytes.as_bytes()) {
-            Ok(s) => s.to_string(),
-            Err(_) => return err_val_error!(input_value = InputValue::InputRef(v), kind = unicode_error),
-        };
-        Ok(Some(str))
+    } else if let Some(bytes) = maybe_as_bytes(v) {
+        Ok(Some(bytes_as_str(v, bytes, unicode_error)?.into_owned()))
     } else {
         Ok(None)
     }
 }
+
+/// The contents of `bytes`, `bytearray` or `memoryview` input, so buffers needn't be converted to `bytes` before
+/// validation, other objects supporting the buffer protocol can be wrapped in a `memoryview`;
+/// only `bytes` are borrowed, the others are mutable so they're copied
+fn maybe_as_bytes(v: &PyAny) -> Option<Cow<[u8]>> {
+    if let Ok(bytes) = v.cast_as::<PyBytes>() {
+        Some(Cow::Borrowed(bytes.as_bytes()))
+    } else if let Ok(byte_array) = v.cast_as::<PyByteArray>() {
+        Some(Cow::Owned(byte_array.to_vec()))
+    } else if unsafe { ffi::PyMemoryView_Check(v.as_ptr()) } == 1 {
+        // fails unless the memoryview's items are bytes
+        let buffer = PyBuffer::<u8>::get(v).ok()?;
+        buffer.to_vec(v.py()).ok().map(Cow::Owned)
+    } else {
+        None
+    }
+}
+
+fn bytes_as_str<'a>(v: &'a PyAny, bytes: Cow<'a, [u8]>, unicode_error: ErrorKind) -> ValResult<'a, Cow<'a, str>> {
+    let str = match bytes {
+        Cow::Borrowed(bytes) => from_utf8(bytes).map(Cow::Borrowed).ok(),
+        Cow::Owned(bytes) => String::from_utf8(bytes).map(Cow::Owned).ok(),
+    };
+    match str {
+        Some(str) => Ok(str),
+        None => err_val_error!(input_value = InputValue::InputRef(v), kind = unicode_error),
+    }
+}
@@ -0,0 +1,102 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::build_tools::{py_error, schema_or_config};
+use crate::errors::{err_val_error, ErrorKind, InputValue, ValResult};
+
+use super::Input;
+
+/// The types lax mode converts between, see `Input::lax_type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaxType {
+    Str,
+    // `bytes`, `bytearray` or `memoryview`
+    Bytes,
+    Bool,
+    Int,
+    Float,
+}
+
+impl LaxType {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Str => "str",
+            Self::Bytes => "bytes",
+            Self::Bool => "bool",
+            Self::Int => "int",
+            Self::Float => "float",
+        }
+    }
+
+    fn type_error(self) -> ErrorKind {
+        match self {
+            // nothing is converted to bytes, bytes are only converted from
+            Self::Str | Self::Bytes => ErrorKind::StrType,
+            Self::Bool => ErrorKind::BoolType,
+            Self::Int => ErrorKind::IntType,
+            Self::Float => ErrorKind::FloatType,
+        }
+    }
+}
+
+/// Each conversion lax mode makes as `(from, to)`, named e.g. `bytes_to_str`, ints to floats aren't included as
+/// even strict mode accepts them
+const COERCIONS: [(LaxType, LaxType); 13] = [
+    (LaxType::Bytes, LaxType::Str),
+    (LaxType::Int, LaxType::Str),
+    (LaxType::Float, LaxType::Str),
+    (LaxType::Str, LaxType::Bool),
+    (LaxType::Bytes, LaxType::Bool),
+    (LaxType::Int, LaxType::Bool),
+    (LaxType::Str, LaxType::Int),
+    (LaxType::Bytes, LaxType::Int),
+    (LaxType::Bool, LaxType::Int),
+    (LaxType::Float, LaxType::Int),
+    (LaxType::Str, LaxType::Float),
+    (LaxType::Bytes, LaxType::Float),
+    (LaxType::Bool, LaxType::Float),
+];
+
+/// Lax mode conversions which are disabled, set with a list of names like `['bytes_to_str', 'float_to_int']` as
+/// `disabled_coercions` on the schema or in config, so lax mode can be tightened without going fully strict;
+/// input which would need a disabled coercion fails with the usual type error, e.g. `int_type`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LaxCoercions {
+    disabled: u16,
+}
+
+impl LaxCoercions {
+    pub fn from_schema(schema: &PyDict, config: Option<&PyDict>) -> PyResult<Self> {
+        let names: &PyList = match schema_or_config(schema, config, "disabled_coercions", "disabled_coercions")? {
+            Some(names) => names,
+            None => return Ok(Self::default()),
+        };
+        let mut disabled = 0;
+        for name in names.iter() {
+            let name: &str = name.extract()?;
+            let is_match = |(from, to): &(LaxType, LaxType)| format!("{}_to_{}", from.name(), to.name()) == name;
+            match COERCIONS.iter().position(is_match) {
+                Some(index) => disabled |= 1 << index,
+                None => return py_error!("Invalid lax coercion: '{}'", name),
+            }
+        }
+        Ok(Self { disabled })
+    }
+
+    /// An error if converting `input` to `to` is disabled, otherwise the input can be validated in lax mode as usual
+    pub fn check<'data>(&self, input: &'data dyn Input, to: LaxType) -> ValResult<'data, ()> {
+        if self.disabled == 0 {
+            return Ok(());
+        }
+        let from = match input.lax_type() {
+            Some(from) => from,
+            None => return Ok(()),
+        };
+        match COERCIONS.iter().position(|coercion| *coercion == (from, to)) {
+            Some(index) if self.disabled & (1 << index) != 0 => {
+                err_val_error!(input_value = InputValue::InputRef(input), kind = to.type_error())
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -2,13 +2,21 @@ mod generics;
 mod input_abstract;
 mod input_json;
 mod input_python;
+mod lax_coercions;
 mod parse_json;
+mod return_enums;
 mod shared;
+mod strict_coercions;
 mod to_loc_item;
 mod to_py;
 
-pub use generics::{GenericMapping, GenericSequence, MappingLenIter, SequenceLenIter};
-pub use input_abstract::Input;
-pub use parse_json::JsonInput;
+pub use generics::{GenericMapping, GenericSequence, LookupKey, MappingLenIter, SequenceLenIter};
+pub use input_abstract::{Input, InputSource};
+pub use lax_coercions::{LaxCoercions, LaxType};
+pub use parse_json::{
+    parse_json_array_items, parse_json_array_items_from_reader, parse_json_bytes, JsonInput, JsonObject,
+};
+pub use return_enums::{EitherString, NumericArray};
+pub use strict_coercions::{StrictCoercion, StrictCoercions};
 pub use to_loc_item::ToLocItem;
 pub use to_py::ToPy;
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::io::{self, BufReader, Read};
 
 use indexmap::IndexMap;
 use serde::de::{Deserialize, DeserializeSeed, Error as SerdeError, MapAccess, SeqAccess, Visitor};
@@ -166,3 +167,194 @@ impl<'de> Visitor<'de> for KeyDeserializer {
         Ok(s)
     }
 }
+
+/// Parse JSON from bytes, as RFC 8259 allows a UTF-8 byte order mark is ignored, and text starting with a UTF-16
+/// BOM is decoded as UTF-16, all other input must be UTF-8
+pub fn parse_json_bytes(bytes: &[u8]) -> Result<JsonInput, String> {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => serde_json::from_slice(rest).map_err(|e| e.to_string()),
+        [0xFF, 0xFE, rest @ ..] => parse_json_utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => parse_json_utf16(rest, u16::from_be_bytes),
+        _ => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+    }
+}
+
+fn parse_json_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Result<JsonInput, String> {
+    let json_str = decode_utf16(bytes, from_bytes)?;
+    serde_json::from_str(&json_str).map_err(|e| e.to_string())
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Result<String, String> {
+    let chunks = bytes.chunks_exact(2);
+    if !chunks.remainder().is_empty() {
+        return Err("invalid utf-16: odd number of bytes".to_string());
+    }
+    let code_units: Vec<u16> = chunks.map(|c| from_bytes([c[0], c[1]])).collect();
+    String::from_utf16(&code_units).map_err(|e| format!("invalid utf-16: {}", e))
+}
+
+/// Parse a JSON array one item at a time, `on_item` is called with each item as soon as it's parsed so the whole
+/// array is never held in memory; byte order marks are handled as by `parse_json_bytes`.
+/// The outer error is the first error from `on_item`, which stops parsing, the inner error is invalid JSON
+/// (including JSON which isn't an array), items before the invalid JSON have already been passed to `on_item`
+pub fn parse_json_array_items<E>(
+    bytes: &[u8],
+    on_item: impl FnMut(JsonInput) -> Result<(), E>,
+) -> Result<Result<(), String>, E> {
+    let decoded: String;
+    let bytes = match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => rest,
+        [0xFF, 0xFE, rest @ ..] | [0xFE, 0xFF, rest @ ..] => {
+            let from_bytes = match bytes[0] {
+                0xFF => u16::from_le_bytes,
+                _ => u16::from_be_bytes,
+            };
+            decoded = match decode_utf16(rest, from_bytes) {
+                Ok(decoded) => decoded,
+                Err(err) => return Ok(Err(err)),
+            };
+            decoded.as_bytes()
+        }
+        _ => bytes,
+    };
+    parse_items(serde_json::Deserializer::from_slice(bytes), on_item)
+}
+
+/// As `parse_json_array_items` but reading the JSON from `reader` as it's parsed, so the document is never held in
+/// memory either; errors reading are returned as invalid JSON
+pub fn parse_json_array_items_from_reader<E>(
+    reader: impl Read,
+    on_item: impl FnMut(JsonInput) -> Result<(), E>,
+) -> Result<Result<(), String>, E> {
+    let mut reader = BufReader::new(reader);
+    let mut bom = [0; 3];
+    let mut bom_len = 0;
+    while bom_len < bom.len() {
+        match reader.read(&mut bom[bom_len..]) {
+            Ok(0) => break,
+            Ok(n) => bom_len += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+            Err(err) => return Ok(Err(err.to_string())),
+        }
+    }
+    let start = &bom[..bom_len];
+    match start {
+        [0xEF, 0xBB, 0xBF] => parse_items(serde_json::Deserializer::from_reader(reader), on_item),
+        [0xFF, 0xFE, rest @ ..] | [0xFE, 0xFF, rest @ ..] => {
+            let from_bytes = match start[0] {
+                0xFF => u16::from_le_bytes,
+                _ => u16::from_be_bytes,
+            };
+            let reader = Utf16Reader {
+                inner: rest.chain(reader),
+                from_bytes,
+                encoded: [0; 4],
+                encoded_range: (0, 0),
+            };
+            parse_items(serde_json::Deserializer::from_reader(BufReader::new(reader)), on_item)
+        }
+        _ => parse_items(serde_json::Deserializer::from_reader(start.chain(reader)), on_item),
+    }
+}
+
+fn parse_items<'de, R, E>(
+    mut deserializer: serde_json::Deserializer<R>,
+    on_item: impl FnMut(JsonInput) -> Result<(), E>,
+) -> Result<Result<(), String>, E>
+where
+    R: serde_json::de::Read<'de>,
+{
+    let mut visitor = ItemsVisitor { on_item, error: None };
+    let result = serde::Deserializer::deserialize_seq(&mut deserializer, &mut visitor).and_then(|_| deserializer.end());
+    match visitor.error {
+        Some(err) => Err(err),
+        None => Ok(result.map_err(|e| e.to_string())),
+    }
+}
+
+/// Decodes UTF-16 from `inner` to UTF-8 one char at a time
+struct Utf16Reader<R> {
+    inner: R,
+    from_bytes: fn([u8; 2]) -> u16,
+    // the UTF-8 of the last char decoded and the part of it not read yet
+    encoded: [u8; 4],
+    encoded_range: (usize, usize),
+}
+
+impl<R: Read> Utf16Reader<R> {
+    /// The next code unit, `None` at the end of `inner`
+    fn read_unit(&mut self) -> io::Result<Option<u16>> {
+        let mut unit = [0; 2];
+        let mut read = 0;
+        while read < unit.len() {
+            match self.inner.read(&mut unit[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(invalid_utf16("odd number of bytes")),
+                Ok(n) => read += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(Some((self.from_bytes)(unit)))
+    }
+}
+
+impl<R: Read> Read for Utf16Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.encoded_range.0 == self.encoded_range.1 {
+            let first = match self.read_unit()? {
+                Some(unit) => unit,
+                None => return Ok(0),
+            };
+            // a high surrogate is the first of a pair
+            let second = match first {
+                0xD800..=0xDBFF => self.read_unit()?,
+                _ => None,
+            };
+            let c = match char::decode_utf16(std::iter::once(first).chain(second)).next() {
+                Some(Ok(c)) => c,
+                _ => return Err(invalid_utf16("unpaired surrogate found")),
+            };
+            self.encoded_range = (0, c.encode_utf8(&mut self.encoded).len());
+        }
+        let (start, end) = self.encoded_range;
+        let len = buf.len().min(end - start);
+        buf[..len].copy_from_slice(&self.encoded[start..start + len]);
+        self.encoded_range.0 += len;
+        Ok(len)
+    }
+}
+
+fn invalid_utf16(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid utf-16: {}", message))
+}
+
+struct ItemsVisitor<F, E> {
+    on_item: F,
+    error: Option<E>,
+}
+
+impl<'de, F, E> Visitor<'de> for &mut ItemsVisitor<F, E>
+where
+    F: FnMut(JsonInput) -> Result<(), E>,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON array")
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<(), V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        while let Some(item) = tri!(visitor.next_element()) {
+            if let Err(err) = (self.on_item)(item) {
+                self.error = Some(err);
+                // the message is never seen, `error` is returned instead
+                return Err(V::Error::custom("stopped"));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use std::borrow::Cow;
+
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+
+use crate::errors::{as_internal, ValResult};
+
+/// A string returned from `Input`, either a rust string (borrowed from the input where possible) or the
+/// original python `str`, so the common case of validating a `str` and returning it needs no copies
+#[derive(Debug)]
+pub enum EitherString<'a> {
+    Cow(Cow<'a, str>),
+    Py(&'a PyString),
+}
+
+impl<'a> EitherString<'a> {
+    pub fn as_str(&self) -> ValResult<'a, &str> {
+        match self {
+            Self::Cow(cow) => Ok(cow),
+            Self::Py(py_str) => py_str.to_str().map_err(as_internal),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for EitherString<'a> {
+    fn from(data: &'a str) -> Self {
+        Self::Cow(Cow::Borrowed(data))
+    }
+}
+
+impl<'a> From<String> for EitherString<'a> {
+    fn from(data: String) -> Self {
+        Self::Cow(Cow::Owned(data))
+    }
+}
+
+impl<'a> From<&'a PyString> for EitherString<'a> {
+    fn from(py_str: &'a PyString) -> Self {
+        Self::Py(py_str)
+    }
+}
+
+impl<'a> IntoPy<PyObject> for EitherString<'a> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            Self::Cow(cow) => cow.as_ref().into_py(py),
+            Self::Py(py_str) => py_str.into_py(py),
+        }
+    }
+}
+
+/// The items of a one-dimensional numeric buffer, e.g. an `array.array` or numpy array, copied out in one go
+/// rather than extracted from a python object per item
+#[derive(Debug)]
+pub enum NumericArray {
+    Int(Vec<i64>),
+    Float(Vec<f64>),
+}
+
+impl NumericArray {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Int(ints) => ints.len(),
+            Self::Float(floats) => floats.len(),
+        }
+    }
+}
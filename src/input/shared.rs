@@ -74,3 +74,56 @@ pub fn float_as_int(input: &dyn Input, float: f64) -> ValResult<i64> {
         Ok(float as i64)
     }
 }
+
+/// Parse a complex number as python's `complex()` does, e.g. `1+2j`, `-2.5J`, `(1-1e3j)` or `3`,
+/// returning the real and imaginary parts
+pub fn str_as_complex<'a>(input: &'a dyn Input, str: &str) -> ValResult<'a, (f64, f64)> {
+    match parse_complex(str) {
+        Some(complex) => Ok(complex),
+        None => err_val_error!(
+            input_value = InputValue::InputRef(input),
+            kind = ErrorKind::ComplexParsing
+        ),
+    }
+}
+
+/// A `[real, imag]` pair of numbers as a complex number
+pub fn pair_as_complex<'a, 'i, T: Input + 'i>(
+    input: &'a dyn Input,
+    mut items: impl ExactSizeIterator<Item = &'i T>,
+) -> ValResult<'a, (f64, f64)> {
+    if items.len() == 2 {
+        let real = items.next().map(|item| item.strict_float());
+        let imag = items.next().map(|item| item.strict_float());
+        if let (Some(Ok(real)), Some(Ok(imag))) = (real, imag) {
+            return Ok((real, imag));
+        }
+    }
+    err_val_error!(input_value = InputValue::InputRef(input), kind = ErrorKind::ComplexType)
+}
+
+fn parse_complex(str: &str) -> Option<(f64, f64)> {
+    let mut str = str.trim();
+    if let Some(inner) = str.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        str = inner.trim();
+    }
+    let imag = match str.strip_suffix('j').or_else(|| str.strip_suffix('J')) {
+        Some(imag) => imag,
+        None => return Some((str.parse().ok()?, 0.0)),
+    };
+    // the sign starting the imaginary part, signs directly after an exponent marker belong to the exponent
+    let bytes = imag.as_bytes();
+    let split = (1..bytes.len())
+        .rev()
+        .find(|&i| matches!(bytes[i], b'+' | b'-') && !matches!(bytes[i - 1], b'e' | b'E'));
+    let (real, imag) = match split {
+        Some(split) => (imag[..split].parse().ok()?, &imag[split..]),
+        None => (0.0, imag),
+    };
+    let imag = match imag {
+        "" | "+" => 1.0,
+        "-" => -1.0,
+        imag => imag.parse().ok()?,
+    };
+    Some((real, imag))
+}
@@ -0,0 +1,104 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::build_tools::{py_error, schema_or_config};
+
+use super::input_abstract::InputSource;
+
+/// Coercions from another type which strict mode may still allow, they exist because JSON has no equivalent of
+/// some python types, so without them strict mode couldn't accept those types from JSON at all
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrictCoercion {
+    /// `list` or JSON array to `set`
+    ArrayToSet,
+    /// `list` or JSON array to `tuple`
+    ArrayToTuple,
+    /// `dict` or JSON object to an instance of a model class
+    ObjectToModel,
+    /// `str` to `complex`, e.g. `"1+2j"`
+    StrToComplex,
+}
+
+impl StrictCoercion {
+    const ALL: [Self; 4] = [
+        Self::ArrayToSet,
+        Self::ArrayToTuple,
+        Self::ObjectToModel,
+        Self::StrToComplex,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::ArrayToSet => "array_to_set",
+            Self::ArrayToTuple => "array_to_tuple",
+            Self::ObjectToModel => "object_to_model",
+            Self::StrToComplex => "str_to_complex",
+        }
+    }
+
+    fn from_name(name: &str) -> PyResult<Self> {
+        match Self::ALL.into_iter().find(|coercion| coercion.name() == name) {
+            Some(coercion) => Ok(coercion),
+            None => py_error!("Invalid strict coercion: '{}'", name),
+        }
+    }
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// Which `StrictCoercion`s are allowed in strict mode for each input source, by default:
+///
+/// | coercion          | python | JSON |
+/// |-------------------|--------|------|
+/// | `array_to_set`    | no     | yes  |
+/// | `array_to_tuple`  | no     | yes  |
+/// | `object_to_model` | no     | yes  |
+/// | `str_to_complex`  | no     | yes  |
+///
+/// The coercions allowed for a source can be replaced with `strict_coercions`, e.g.
+/// `{'python': ['array_to_tuple'], 'json': []}`, on the schema or in config
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrictCoercions {
+    python: u8,
+    json: u8,
+}
+
+impl Default for StrictCoercions {
+    fn default() -> Self {
+        // each coercion has its own bit, so the sum is all bits set
+        let all: u8 = StrictCoercion::ALL.iter().map(|c| c.bit()).sum();
+        Self { python: 0, json: all }
+    }
+}
+
+impl StrictCoercions {
+    pub fn from_schema(schema: &PyDict, config: Option<&PyDict>) -> PyResult<Self> {
+        let mut coercions = Self::default();
+        let overrides: &PyDict = match schema_or_config(schema, config, "strict_coercions", "strict_coercions")? {
+            Some(overrides) => overrides,
+            None => return Ok(coercions),
+        };
+        for (source, names) in overrides.iter() {
+            let names: &PyList = names.extract()?;
+            let bits = names.iter().try_fold(0, |bits, name| {
+                Ok::<u8, PyErr>(bits | StrictCoercion::from_name(name.extract()?)?.bit())
+            })?;
+            match source.extract::<&str>()? {
+                "python" => coercions.python = bits,
+                "json" => coercions.json = bits,
+                source => return py_error!("Invalid strict_coercions source: '{}'", source),
+            }
+        }
+        Ok(coercions)
+    }
+
+    pub fn allows(&self, source: InputSource, coercion: StrictCoercion) -> bool {
+        let bits = match source {
+            InputSource::Python => self.python,
+            InputSource::Json => self.json,
+        };
+        bits & coercion.bit() != 0
+    }
+}
@@ -1,3 +1,4 @@
+use pyo3::types::PyBool;
 use pyo3::{PyAny, PyResult};
 
 use super::parse_json::JsonInput;
@@ -33,14 +34,15 @@ impl ToLocItem for PyAny {
     fn to_loc(&self) -> LocItem {
         if let Ok(key_str) = self.extract::<String>() {
             LocItem::S(key_str)
-        } else if let Ok(key_int) = self.extract::<usize>() {
+        } else if let (Err(_), Ok(key_int)) = (self.cast_as::<PyBool>(), self.extract::<usize>()) {
             LocItem::I(key_int)
         } else {
             // best effort is to use repr
-            match repr_string(self) {
-                Ok(s) => LocItem::S(s),
-                Err(_) => LocItem::S(format!("{:?}", self)),
-            }
+            let repr = match repr_string(self) {
+                Ok(s) => s,
+                Err(_) => format!("{:?}", self),
+            };
+            LocItem::K(self.into(), repr)
         }
     }
 }
@@ -50,3 +52,15 @@ fn repr_string(py_any: &PyAny) -> PyResult<String> {
     let repr: String = repr_result.extract()?;
     Ok(repr)
 }
+
+impl ToLocItem for LocItem {
+    fn to_loc(&self) -> LocItem {
+        self.clone()
+    }
+}
+
+impl ToLocItem for usize {
+    fn to_loc(&self) -> LocItem {
+        LocItem::I(*self)
+    }
+}
@@ -14,6 +14,8 @@ use pyo3::create_exception;
 use pyo3::exceptions::PyException;
 use pyo3::prelude::*;
 
+// only the extension module picks the allocator, a crate depending on this one keeps its own
+#[cfg(feature = "extension-module")]
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
@@ -22,6 +24,17 @@ mod errors;
 mod input;
 mod validators;
 
+// the public rust API for defining validators in other crates
+#[cfg(feature = "extensions")]
+pub use errors::{ErrorKind, InputValue, ValError, ValLineError, ValResult};
+#[cfg(feature = "extensions")]
+pub use input::{Input, JsonInput, ToPy};
+#[cfg(feature = "extensions")]
+pub use validators::{
+    build_validator, register_validator, set_hooks, BuildContext, BuildValidator, CombinedValidator, DynValidator,
+    ExtensionValidator, Extra, SchemaValidator, ValidationHooks, Validator,
+};
+
 create_exception!(_pydantic_core, SchemaError, PyException);
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -32,5 +45,10 @@ fn _pydantic_core(py: Python, m: &PyModule) -> PyResult<()> {
     m.add("SchemaError", py.get_type::<SchemaError>())?;
     m.add("__version__", VERSION)?;
     m.add_class::<validators::SchemaValidator>()?;
+    m.add_class::<errors::PyLineError>()?;
+    m.add_function(wrap_pyfunction!(validators::register_schema_type, m)?)?;
+    m.add_function(wrap_pyfunction!(validators::set_validation_hooks, m)?)?;
+    m.add_function(wrap_pyfunction!(errors::list_all_errors, m)?)?;
+    m.add_function(wrap_pyfunction!(errors::set_errors_url, m)?)?;
     Ok(())
 }
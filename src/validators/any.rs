@@ -1,24 +1,35 @@
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
-use crate::errors::ValResult;
-use crate::input::Input;
+use crate::build_tools::SchemaDict;
+use crate::errors::{as_internal, ValResult};
+use crate::input::{Input, InputSource, JsonInput};
 
+use super::pure::{PureExtra, PureOutput};
 use super::{BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
 
-/// This might seem useless, but it's useful in DictValidator to avoid Option<Validator> a lot
+/// This might seem useless, but it's useful in DictValidator to avoid Option<Validator> a lot;
+/// with `deep_copy` python input is copied so the output can't alias mutable containers in the input
 #[derive(Debug, Clone)]
-pub struct AnyValidator;
+pub struct AnyValidator {
+    /// `copy.deepcopy` if `deep_copy` is set
+    deep_copy: Option<PyObject>,
+}
 
 impl BuildValidator for AnyValidator {
     const EXPECTED_TYPE: &'static str = "any";
 
     fn build(
-        _schema: &PyDict,
+        schema: &PyDict,
         _config: Option<&PyDict>,
         _build_context: &mut BuildContext,
     ) -> PyResult<CombinedValidator> {
-        Ok(Self.into())
+        let py = schema.py();
+        let deep_copy = match schema.get_as("deep_copy")?.unwrap_or(false) {
+            true => Some(py.import("copy")?.getattr("deepcopy")?.into_py(py)),
+            false => None,
+        };
+        Ok(Self { deep_copy }.into())
     }
 }
 
@@ -30,10 +41,25 @@ impl Validator for AnyValidator {
         _extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        Ok(input.to_py(py))
+        match self.deep_copy {
+            // JSON input is always converted to new objects, so it never needs copying
+            Some(ref deep_copy) if input.source() == InputSource::Python => {
+                deep_copy.call1(py, (input.to_py(py),)).map_err(as_internal)
+            }
+            _ => Ok(input.to_py(py)),
+        }
     }
 
     fn get_name(&self, _py: Python) -> String {
         Self::EXPECTED_TYPE.to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        _extra: PureExtra,
+        _slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        Some(PureOutput::Json(input))
+    }
 }
@@ -0,0 +1,71 @@
+use pyo3::prelude::*;
+use smallvec::smallvec;
+
+use crate::errors::{LocItem, ValError, ValLineError, ValResult};
+use crate::input::NumericArray;
+
+use super::{CombinedValidator, Extra, Validator};
+
+/// Validate the items of a numeric array, `validator_for` gives the validator for each index.
+/// Items without a validator and items validated by plain int and float validators are converted straight from
+/// their rust values, other items are converted to python objects and validated as usual.
+pub fn validate_array_items<'s, 'data>(
+    py: Python<'data>,
+    array: &NumericArray,
+    validator_for: impl Fn(usize) -> Option<&'s CombinedValidator>,
+    extra: &Extra,
+    slots: &'data [CombinedValidator],
+) -> ValResult<'data, Vec<PyObject>> {
+    let mut output: Vec<PyObject> = Vec::with_capacity(array.len());
+    let mut errors: Vec<ValLineError> = Vec::new();
+    for index in 0..array.len() {
+        let validator = match validator_for(index) {
+            Some(validator) => validator,
+            None => {
+                output.push(item_to_py(py, array, index));
+                continue;
+            }
+        };
+        let fast_item = match (array, validator) {
+            (NumericArray::Int(ints), CombinedValidator::Int(_) | CombinedValidator::StrictInt(_)) => {
+                Some(ints[index].into_py(py))
+            }
+            (NumericArray::Int(ints), CombinedValidator::Float(_) | CombinedValidator::StrictFloat(_)) => {
+                Some((ints[index] as f64).into_py(py))
+            }
+            (NumericArray::Float(floats), CombinedValidator::Float(_) | CombinedValidator::StrictFloat(_)) => {
+                Some(floats[index].into_py(py))
+            }
+            _ => None,
+        };
+        if let Some(item) = fast_item {
+            output.push(item);
+            continue;
+        }
+
+        let item: &'data PyAny = item_to_py(py, array, index).into_ref(py);
+        let checkpoint = extra.checkpoint();
+        let result = validator.validate(py, item, extra, slots);
+        extra.prefix_location(checkpoint, &index);
+        match result {
+            Ok(item) => output.push(item),
+            Err(ValError::LineErrors(line_errors)) => {
+                let loc = smallvec![LocItem::I(index)];
+                errors.extend(line_errors.into_iter().map(|err| err.with_prefix_location(&loc)));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    if errors.is_empty() {
+        Ok(output)
+    } else {
+        Err(ValError::LineErrors(errors))
+    }
+}
+
+pub fn item_to_py(py: Python, array: &NumericArray, index: usize) -> PyObject {
+    match array {
+        NumericArray::Int(ints) => ints[index].into_py(py),
+        NumericArray::Float(floats) => floats[index].into_py(py),
+    }
+}
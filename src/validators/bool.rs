@@ -3,8 +3,9 @@ use pyo3::types::PyDict;
 
 use crate::build_tools::is_strict;
 use crate::errors::ValResult;
-use crate::input::Input;
+use crate::input::{Input, JsonInput, LaxCoercions, LaxType};
 
+use super::pure::{PureExtra, PureOutput};
 use super::{BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
 
 #[derive(Debug, Clone)]
@@ -21,7 +22,12 @@ impl BuildValidator for BoolValidator {
         if is_strict(schema, config)? {
             StrictBoolValidator::build()
         } else {
-            Ok(Self.into())
+            let lax_coercions = LaxCoercions::from_schema(schema, config)?;
+            if lax_coercions != LaxCoercions::default() {
+                Ok(ConstrainedBoolValidator { lax_coercions }.into())
+            } else {
+                Ok(Self.into())
+            }
         }
     }
 }
@@ -31,12 +37,13 @@ impl Validator for BoolValidator {
         &'s self,
         py: Python<'data>,
         input: &'data dyn Input,
-        _extra: &Extra,
+        extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
         // TODO in theory this could be quicker if we used PyBool rather than going to a bool
         // and back again, might be worth profiling?
-        Ok(input.lax_bool()?.into_py(py))
+        let value = extra.lax_with_report(py, input, "bool", |i| i.strict_bool(), |i| i.lax_bool())?;
+        Ok(value.into_py(py))
     }
 
     fn validate_strict<'s, 'data>(
@@ -52,6 +59,18 @@ impl Validator for BoolValidator {
     fn get_name(&self, _py: Python) -> String {
         Self::EXPECTED_TYPE.to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        _extra: PureExtra,
+        _slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        match input {
+            JsonInput::Bool(b) => Some(PureOutput::Bool(*b)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -77,4 +96,50 @@ impl Validator for StrictBoolValidator {
     fn get_name(&self, _py: Python) -> String {
         "strict-bool".to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        _extra: PureExtra,
+        _slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        match input {
+            JsonInput::Bool(b) => Some(PureOutput::Bool(*b)),
+            _ => None,
+        }
+    }
+}
+
+/// Used in lax mode when some coercions are disabled, see `LaxCoercions`
+#[derive(Debug, Clone)]
+pub struct ConstrainedBoolValidator {
+    lax_coercions: LaxCoercions,
+}
+
+impl Validator for ConstrainedBoolValidator {
+    fn validate<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        _slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        self.lax_coercions.check(input, LaxType::Bool)?;
+        let value = extra.lax_with_report(py, input, "bool", |i| i.strict_bool(), |i| i.lax_bool())?;
+        Ok(value.into_py(py))
+    }
+
+    fn validate_strict<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        _extra: &Extra,
+        _slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        Ok(input.strict_bool()?.into_py(py))
+    }
+
+    fn get_name(&self, _py: Python) -> String {
+        "constrained-bool".to_string()
+    }
 }
@@ -0,0 +1,64 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyComplex, PyDict};
+
+use crate::build_tools::is_strict;
+use crate::errors::ValResult;
+use crate::input::{Input, StrictCoercions};
+
+use super::{BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
+
+/// Validates complex numbers, in strict mode only `complex` is accepted, plus strings like `"1+2j"` from JSON
+/// unless `str_to_complex` is removed from `strict_coercions`; lax mode also accepts numbers, strings
+/// and `[real, imag]` pairs
+#[derive(Debug, Clone)]
+pub struct ComplexValidator {
+    strict: bool,
+    strict_coercions: StrictCoercions,
+}
+
+impl BuildValidator for ComplexValidator {
+    const EXPECTED_TYPE: &'static str = "complex";
+
+    fn build(
+        schema: &PyDict,
+        config: Option<&PyDict>,
+        _build_context: &mut BuildContext,
+    ) -> PyResult<CombinedValidator> {
+        Ok(Self {
+            strict: is_strict(schema, config)?,
+            strict_coercions: StrictCoercions::from_schema(schema, config)?,
+        }
+        .into())
+    }
+}
+
+impl Validator for ComplexValidator {
+    fn validate<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        _slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        let (real, imag) = match self.strict {
+            true => input.strict_complex_coerced(&self.strict_coercions)?,
+            false => extra.lax_with_report(py, input, "complex", |i| i.strict_complex(), |i| i.lax_complex())?,
+        };
+        Ok(PyComplex::from_doubles(py, real, imag).into_py(py))
+    }
+
+    fn validate_strict<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        _extra: &Extra,
+        _slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        let (real, imag) = input.strict_complex_coerced(&self.strict_coercions)?;
+        Ok(PyComplex::from_doubles(py, real, imag).into_py(py))
+    }
+
+    fn get_name(&self, _py: Python) -> String {
+        Self::EXPECTED_TYPE.to_string()
+    }
+}
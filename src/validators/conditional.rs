@@ -0,0 +1,106 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::build_tools::SchemaDict;
+use crate::errors::{as_validation_err, ValError};
+use crate::input::Input;
+
+use super::{build_validator, BuildContext, BuildValidator, CombinedValidator, Extra, ValResult, Validator};
+
+/// Validates against `then` if the input is valid according to `if`, otherwise against `else`
+#[derive(Debug, Clone)]
+pub struct ConditionalValidator {
+    if_validator: Box<CombinedValidator>,
+    then_validator: Box<CombinedValidator>,
+    else_validator: Box<CombinedValidator>,
+}
+
+impl BuildValidator for ConditionalValidator {
+    const EXPECTED_TYPE: &'static str = "conditional";
+
+    fn build(
+        schema: &PyDict,
+        config: Option<&PyDict>,
+        build_context: &mut BuildContext,
+    ) -> PyResult<CombinedValidator> {
+        let mut sub_validator = |key: &str| -> PyResult<Box<CombinedValidator>> {
+            let sub_schema: &PyAny = schema.get_as_req(key)?;
+            Ok(Box::new(build_validator(sub_schema, config, build_context)?.0))
+        };
+        Ok(Self {
+            if_validator: sub_validator("if")?,
+            then_validator: sub_validator("then")?,
+            else_validator: sub_validator("else")?,
+        }
+        .into())
+    }
+}
+
+impl Validator for ConditionalValidator {
+    fn validate<'data>(
+        &self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        match self.matches_if(py, input, extra, slots)? {
+            true => self.then_validator.validate(py, input, extra, slots),
+            false => self.else_validator.validate(py, input, extra, slots),
+        }
+    }
+
+    fn validate_strict<'data>(
+        &self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        match self.matches_if(py, input, extra, slots)? {
+            true => self.then_validator.validate_strict(py, input, extra, slots),
+            false => self.else_validator.validate_strict(py, input, extra, slots),
+        }
+    }
+
+    /// `if` is still checked to choose between `then` and `else`
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        let matches_if = self
+            .matches_if(py, input, &Extra::default(), slots)
+            .map_err(|err| as_validation_err(py, Self::EXPECTED_TYPE, err))?;
+        match matches_if {
+            true => self.then_validator.construct(py, input, slots),
+            false => self.else_validator.construct(py, input, slots),
+        }
+    }
+
+    fn get_name(&self, _py: Python) -> String {
+        Self::EXPECTED_TYPE.to_string()
+    }
+}
+
+impl ConditionalValidator {
+    /// The `if` schema is only a predicate: its output is dropped, and anything it added to
+    /// warnings, coercions or collected errors is discarded
+    fn matches_if<'data>(
+        &self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, bool> {
+        // like in unions, a model predicate mustn't succeed by dropping invalid fields
+        let if_extra = Extra {
+            collected_errors: None,
+            ..*extra
+        };
+        let checkpoint = if_extra.checkpoint();
+        let result = self.if_validator.validate(py, input, &if_extra, slots);
+        if_extra.discard(checkpoint);
+        match result {
+            Ok(_) => Ok(true),
+            Err(ValError::LineErrors(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use pyo3::prelude::*;
+
+use crate::errors::ValResult;
+use crate::input::Input;
+
+use super::{CombinedValidator, Extra, Validator};
+
+/// Wraps every validator when a `SchemaValidator` is built with `counters=True`, counting how many times the
+/// validator ran so `debug_repr()` can show which parts of a schema are hot
+#[derive(Clone)]
+pub struct CountedValidator {
+    /// shared by clones, e.g. the copies of definitions used by `from_definitions`
+    count: Arc<AtomicUsize>,
+    validator: Box<CombinedValidator>,
+}
+
+impl CountedValidator {
+    pub fn wrap(validator: CombinedValidator) -> CombinedValidator {
+        Self {
+            count: Arc::default(),
+            validator: Box::new(validator),
+        }
+        .into()
+    }
+
+    pub fn inner(&self) -> &CombinedValidator {
+        &self.validator
+    }
+}
+
+impl fmt::Debug for CountedValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Counted")
+            .field("count", &self.count.load(Ordering::Relaxed))
+            .field("validator", &self.validator)
+            .finish()
+    }
+}
+
+impl Validator for CountedValidator {
+    fn validate<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.validator.validate(py, input, extra, slots)
+    }
+
+    fn validate_strict<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.validator.validate_strict(py, input, extra, slots)
+    }
+
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        self.validator.construct(py, input, slots)
+    }
+
+    fn get_name(&self, py: Python) -> String {
+        self.validator.get_name(py)
+    }
+}
@@ -0,0 +1,45 @@
+use pyo3::exceptions::PyTypeError;
+use pyo3::once_cell::GILOnceCell;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::build_tools::py_error;
+
+use super::{build_validator, BuildContext, CombinedValidator};
+
+/// Schema types registered from python, mapping the type name to its builder
+static CUSTOM_TYPES: GILOnceCell<Py<PyDict>> = GILOnceCell::new();
+
+fn custom_types(py: Python) -> &PyDict {
+    CUSTOM_TYPES.get_or_init(py, || PyDict::new(py).into()).as_ref(py)
+}
+
+/// Register a new schema type, when a schema with this type is built, `builder` is called with the schema dict
+/// and config, and must return a core schema (generally using function validators) which is built in its place;
+/// built-in types take precedence, so they can't be overridden
+#[pyfunction]
+pub fn register_schema_type(py: Python, name: &str, builder: &PyAny) -> PyResult<()> {
+    if !builder.is_callable() {
+        return py_error!(PyTypeError; "builder must be callable");
+    }
+    custom_types(py).set_item(name, builder)
+}
+
+/// Build a validator for a type registered with `register_schema_type`, `None` if `type_` hasn't been registered
+pub fn build_custom_type(
+    py: Python,
+    type_: &str,
+    schema: &PyDict,
+    config: Option<&PyDict>,
+    build_context: &mut BuildContext,
+) -> PyResult<Option<CombinedValidator>> {
+    match custom_types(py).get_item(type_) {
+        Some(builder) => {
+            let core_schema = builder.call1((schema, config))?;
+            let validator = build_validator(core_schema, config, build_context)?.0;
+            build_context.delegate_core_schema(core_schema);
+            Ok(Some(validator))
+        }
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,26 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::build_tools::SchemaDict;
+
+use super::{build_validator, BuildContext, BuildValidator, CombinedValidator};
+
+/// Named schemas which can be referenced anywhere within `schema` via `recursive-ref`, this doesn't create a
+/// validator of its own, instead the validator for `schema` is returned
+#[derive(Debug, Clone)]
+pub struct DefinitionsBuilder;
+
+impl BuildValidator for DefinitionsBuilder {
+    const EXPECTED_TYPE: &'static str = "definitions";
+
+    fn build(
+        schema: &PyDict,
+        config: Option<&PyDict>,
+        build_context: &mut BuildContext,
+    ) -> PyResult<CombinedValidator> {
+        let definitions: &PyDict = schema.get_as_req("definitions")?;
+        build_context.add_definitions(definitions, config)?;
+        let sub_schema: &PyAny = schema.get_as_req("schema")?;
+        Ok(build_validator(sub_schema, config, build_context)?.0)
+    }
+}
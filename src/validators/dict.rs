@@ -1,11 +1,15 @@
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
+use smallvec::smallvec;
 
-use crate::build_tools::{is_strict, SchemaDict};
-use crate::errors::{as_internal, context, err_val_error, ErrorKind, InputValue, ValError, ValLineError, ValResult};
-use crate::input::{GenericMapping, Input, MappingLenIter, ToLocItem};
+use crate::build_tools::{is_strict, schema_or_config, SchemaDict};
+use crate::errors::{
+    as_internal, context, err_val_error, ErrorKind, InputValue, LocItem, ValError, ValLineError, ValResult,
+};
+use crate::input::{GenericMapping, Input, JsonInput, MappingLenIter};
 
 use super::any::AnyValidator;
+use super::pure::{PureExtra, PureOutput};
 use super::{build_validator, BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
 
 #[derive(Debug, Clone)]
@@ -16,6 +20,8 @@ pub struct DictValidator {
     min_items: Option<usize>,
     max_items: Option<usize>,
     try_instance_as_dict: bool,
+    // in strict mode, also accept instances of `collections.abc.Mapping`
+    strict_mapping: bool,
 }
 
 impl BuildValidator for DictValidator {
@@ -39,6 +45,7 @@ impl BuildValidator for DictValidator {
             min_items: schema.get_as("min_items")?,
             max_items: schema.get_as("max_items")?,
             try_instance_as_dict: schema.get_as("try_instance_as_dict")?.unwrap_or(false),
+            strict_mapping: schema_or_config(schema, config, "strict_mapping", "dict_strict_mapping")?.unwrap_or(false),
         }
         .into())
     }
@@ -52,8 +59,8 @@ impl Validator for DictValidator {
         extra: &Extra,
         slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        let dict = match self.strict {
-            true => input.strict_dict()?,
+        let dict = match self.strict || extra.overrides.strict {
+            true => self.strict_dict(input)?,
             false => input.lax_dict(self.try_instance_as_dict)?,
         };
         self._validation_logic(py, input, dict, extra, slots)
@@ -66,15 +73,63 @@ impl Validator for DictValidator {
         extra: &Extra,
         slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        self._validation_logic(py, input, input.strict_dict()?, extra, slots)
+        self._validation_logic(py, input, self.strict_dict(input)?, extra, slots)
+    }
+
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        let dict = match input.cast_as::<PyDict>() {
+            Ok(dict) => dict,
+            Err(_) => return Ok(input.into_py(py)),
+        };
+        let output = PyDict::new(py);
+        for (key, value) in dict.iter() {
+            output.set_item(
+                self.key_validator.construct(py, key, slots)?,
+                self.value_validator.construct(py, value, slots)?,
+            )?;
+        }
+        Ok(output.into_py(py))
     }
 
     fn get_name(&self, _py: Python) -> String {
         Self::EXPECTED_TYPE.to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        extra: PureExtra,
+        slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        let dict = match input {
+            JsonInput::Object(dict) => dict,
+            _ => return None,
+        };
+        let too_short = matches!(self.min_items, Some(min_length) if dict.len() < min_length);
+        let too_long = matches!(self.max_items, Some(max_length) if dict.len() > max_length);
+        // JSON keys are strings, which these validators use unchanged
+        let keys_unchanged = matches!(
+            *self.key_validator,
+            CombinedValidator::Any(_) | CombinedValidator::Str(_) | CombinedValidator::StrictStr(_)
+        );
+        if too_short || too_long || !keys_unchanged {
+            return None;
+        }
+        dict.iter()
+            .map(|(key, value)| Some((key.as_str(), self.value_validator.validate_pure(value, extra, slots)?)))
+            .collect::<Option<_>>()
+            .map(PureOutput::Dict)
+    }
 }
 
 impl DictValidator {
+    fn strict_dict<'data>(&self, input: &'data dyn Input) -> ValResult<'data, GenericMapping<'data>> {
+        match self.strict_mapping {
+            true => input.strict_mapping(),
+            false => input.strict_dict(),
+        }
+    }
+
     fn _validation_logic<'s, 'data>(
         &'s self,
         py: Python<'data>,
@@ -101,14 +156,18 @@ impl DictValidator {
                 );
             }
         }
+        let _depth = extra.enter_container(input, dict.generic_len())?;
         let output = PyDict::new(py);
         let mut errors: Vec<ValLineError> = Vec::new();
 
         for (key, value) in dict.generic_iter() {
-            let output_key = match self.key_validator.validate(py, key, extra, slots) {
+            let checkpoint = extra.checkpoint();
+            let result = self.key_validator.validate(py, key, extra, slots);
+            extra.prefix_location(checkpoint, &LocItem::Key);
+            let output_key = match result {
                 Ok(value) => Some(value),
                 Err(ValError::LineErrors(line_errors)) => {
-                    let loc = vec![key.to_loc(), "[key]".to_loc()];
+                    let loc = smallvec![key.to_loc(), LocItem::Key];
                     for err in line_errors {
                         errors.push(err.with_prefix_location(&loc));
                     }
@@ -116,10 +175,12 @@ impl DictValidator {
                 }
                 Err(err) => return Err(err),
             };
-            let output_value = match self.value_validator.validate(py, value, extra, slots) {
+            let result = self.value_validator.validate(py, value, extra, slots);
+            extra.prefix_location(checkpoint, key);
+            let output_value = match result {
                 Ok(value) => Some(value),
                 Err(ValError::LineErrors(line_errors)) => {
-                    let loc = vec![key.to_loc()];
+                    let loc = smallvec![key.to_loc()];
                     for err in line_errors {
                         errors.push(err.with_prefix_location(&loc));
                     }
@@ -132,10 +193,12 @@ impl DictValidator {
             }
         }
 
-        if errors.is_empty() {
-            Ok(output.into())
-        } else {
+        if !errors.is_empty() {
             Err(ValError::LineErrors(errors))
+        } else if extra.check_only {
+            Ok(py.None())
+        } else {
+            Ok(output.into())
         }
     }
 }
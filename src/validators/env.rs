@@ -0,0 +1,225 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyMapping, PyString};
+
+use crate::build_tools::py_error;
+use crate::input::{JsonInput, JsonObject};
+
+use super::model::ModelValidator;
+use super::CombinedValidator;
+
+/// Environment variables by their normalized name, see `EnvSource::normalize`, with the name as given and the value
+type EnvVars = IndexMap<String, (String, String)>;
+
+/// How `SchemaValidator.validate_env` finds the value of each model field in a flat map of environment variables
+#[derive(Debug)]
+pub struct EnvSource<'a> {
+    prefix: &'a str,
+    nested_delimiter: Option<&'a str>,
+    case_sensitive: bool,
+    secrets_dir: Option<PathBuf>,
+}
+
+impl<'a> EnvSource<'a> {
+    pub fn new(
+        prefix: &'a str,
+        nested_delimiter: Option<&'a str>,
+        case_sensitive: bool,
+        secrets_dir: Option<PathBuf>,
+    ) -> PyResult<Self> {
+        if nested_delimiter == Some("") {
+            return py_error!(PyValueError; "env_nested_delimiter must not be empty");
+        }
+        if let Some(ref dir) = secrets_dir {
+            if !dir.is_dir() {
+                return py_error!(PyValueError; "secrets_dir \"{}\" is not a directory", dir.display());
+            }
+        }
+        Ok(Self {
+            prefix,
+            nested_delimiter,
+            case_sensitive,
+            secrets_dir,
+        })
+    }
+
+    /// Build the input for the model validated by `validator`, each field is keyed by its alias or name:
+    /// * the value is the variable named `prefix + alias-or-name`, or the first of the field's `env` names which
+    ///   is set, `prefix` isn't added to `env` names
+    /// * otherwise with a nested delimiter, variables named e.g. `{name}{delimiter}{key}{delimiter}{key}` are
+    ///   combined into nested objects, see `insert_nested`
+    /// * otherwise the content of the file with the variable's name in `secrets_dir`
+    ///
+    /// Other variables are ignored, fields found nowhere are omitted so defaults apply as usual
+    pub fn model_input(
+        &self,
+        validator: &CombinedValidator,
+        slots: &[CombinedValidator],
+        env: &PyAny,
+    ) -> PyResult<JsonInput> {
+        let model = match find_model(validator, slots) {
+            Some(model) => model,
+            None => return py_error!(PyTypeError; "validate_env requires a model schema"),
+        };
+        let vars = self.read_env(env)?;
+        let mut object = JsonObject::new();
+        for (lookup_key, env_names, field_validator) in model.env_fields() {
+            let env_names = match env_names {
+                Some(env_names) => env_names.iter().map(|name| self.normalize(name)).collect(),
+                None => vec![self.normalize(&format!("{}{}", self.prefix, lookup_key))],
+            };
+            let field_model = find_model(field_validator, slots);
+            if let Some(value) = self.field_value(&vars, &env_names, field_model, slots)? {
+                object.insert(lookup_key.to_string(), value);
+            }
+        }
+        Ok(JsonInput::Object(object))
+    }
+
+    fn read_env(&self, env: &PyAny) -> PyResult<EnvVars> {
+        let mapping: &PyMapping = match env.cast_as() {
+            Ok(mapping) => mapping,
+            Err(_) => {
+                let type_name = env.get_type().name()?;
+                return py_error!(PyTypeError; "validate_env input should be a mapping, got {}", type_name);
+            }
+        };
+        let mut vars = IndexMap::with_capacity(mapping.len()?);
+        for item in mapping.items()?.iter()? {
+            let (key, value): (&PyAny, &PyAny) = item?.extract()?;
+            match (key.cast_as::<PyString>(), value.cast_as::<PyString>()) {
+                (Ok(key), Ok(value)) => {
+                    let key = key.to_str()?;
+                    vars.insert(self.normalize(key), (key.to_string(), value.to_str()?.to_string()))
+                }
+                _ => return py_error!(PyTypeError; "validate_env keys and values should be str"),
+            };
+        }
+        Ok(vars)
+    }
+
+    /// The value of a field, `model` is the field's model if it has one, used to key nested objects
+    fn field_value(
+        &self,
+        vars: &EnvVars,
+        env_names: &[String],
+        model: Option<&ModelValidator>,
+        slots: &[CombinedValidator],
+    ) -> PyResult<Option<JsonInput>> {
+        for env_name in env_names {
+            if let Some((_, value)) = vars.get(env_name) {
+                return Ok(Some(JsonInput::String(value.clone())));
+            }
+            if let Some(delimiter) = self.nested_delimiter {
+                let nested_prefix = format!("{}{}", env_name, delimiter);
+                let mut nested = JsonObject::new();
+                for (key, value) in vars.values() {
+                    // the path is taken from the name as given, so keys which aren't fields keep their case
+                    if matches!(key.get(..nested_prefix.len()), Some(head) if self.normalize(head) == nested_prefix) {
+                        let path: Vec<&str> = key[nested_prefix.len()..].split(delimiter).collect();
+                        self.insert_nested(&mut nested, &path, value, model, slots);
+                    }
+                }
+                if !nested.is_empty() {
+                    return Ok(Some(JsonInput::Object(nested)));
+                }
+            }
+        }
+        if let Some(ref dir) = self.secrets_dir {
+            for env_name in env_names {
+                if let Some(path) = self.find_secret(dir, env_name)? {
+                    let secret = fs::read_to_string(path)?;
+                    return Ok(Some(JsonInput::String(secret.trim().to_string())));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// The file in `dir` named `env_name`, ignoring case unless `case_sensitive`
+    fn find_secret(&self, dir: &Path, env_name: &str) -> PyResult<Option<PathBuf>> {
+        let path = dir.join(env_name);
+        if path.is_file() {
+            return Ok(Some(path));
+        }
+        if !self.case_sensitive {
+            for entry in fs::read_dir(dir)? {
+                let path = entry?.path();
+                let matches = match path.file_name() {
+                    Some(file_name) => file_name.to_string_lossy().to_lowercase() == env_name,
+                    None => false,
+                };
+                if matches && path.is_file() {
+                    return Ok(Some(path));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Insert `value` at `path` creating objects as required. Keys matching a field of `model`, ignoring case unless
+    /// `case_sensitive`, are replaced by the field's alias or name, so e.g. `DB__HOSTNAME` sets the `hostName` field
+    /// of the `db` model, other keys are used as given. Where variables conflict, e.g. `A__B=1` and `A__B__C=2`, the
+    /// more deeply nested variable is used
+    fn insert_nested(
+        &self,
+        object: &mut JsonObject,
+        path: &[&str],
+        value: &str,
+        model: Option<&ModelValidator>,
+        slots: &[CombinedValidator],
+    ) {
+        let (key, rest) = match path.split_first() {
+            Some(first) => first,
+            None => return,
+        };
+        let field = model.and_then(|model| {
+            model
+                .env_fields()
+                .find(|(lookup_key, _, _)| self.normalize(lookup_key) == self.normalize(key))
+        });
+        let key = match field {
+            Some((lookup_key, _, _)) => lookup_key.to_string(),
+            None => key.to_string(),
+        };
+        if rest.is_empty() {
+            object
+                .entry(key)
+                .or_insert_with(|| JsonInput::String(value.to_string()));
+            return;
+        }
+        let entry = object
+            .entry(key)
+            .or_insert_with(|| JsonInput::Object(JsonObject::new()));
+        if !matches!(entry, JsonInput::Object(_)) {
+            *entry = JsonInput::Object(JsonObject::new());
+        }
+        if let JsonInput::Object(inner) = entry {
+            let field_model = field.and_then(|(_, _, validator)| find_model(validator, slots));
+            self.insert_nested(inner, rest, value, field_model, slots);
+        }
+    }
+
+    fn normalize(&self, name: &str) -> String {
+        match self.case_sensitive {
+            true => name.to_string(),
+            false => name.to_lowercase(),
+        }
+    }
+}
+
+/// The model validated by `validator`, including models which are optional or shared with identical schemas
+fn find_model<'a>(validator: &'a CombinedValidator, slots: &'a [CombinedValidator]) -> Option<&'a ModelValidator> {
+    match validator {
+        CombinedValidator::Model(model) => Some(model),
+        CombinedValidator::ModelClass(model_class) => find_model(model_class.model_validator(), slots),
+        CombinedValidator::Optional(optional) => find_model(optional.inner(), slots),
+        CombinedValidator::Shared(shared) => find_model(slots.get(shared.validator_id())?, slots),
+        CombinedValidator::Counted(counted) => find_model(counted.inner(), slots),
+        _ => None,
+    }
+}
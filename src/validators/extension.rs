@@ -0,0 +1,110 @@
+use std::fmt::Debug;
+use std::sync::{Arc, RwLock};
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::errors::ValResult;
+use crate::input::Input;
+
+use super::{BuildContext, CombinedValidator, Extra, Validator};
+
+/// Object safe equivalent of `Validator`, implemented by validators defined outside this crate
+pub trait DynValidator: Send + Sync + Debug {
+    fn validate<'data>(
+        &self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject>;
+
+    fn validate_strict<'data>(
+        &self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        self.validate(py, input, extra, slots)
+    }
+
+    fn get_name(&self, py: Python) -> String;
+}
+
+/// Wraps a validator defined outside this crate so it can be part of `CombinedValidator`, `BuildValidator::build`
+/// implementations for such validators should return `ExtensionValidator::new(validator).into()`
+#[derive(Debug, Clone)]
+pub struct ExtensionValidator {
+    validator: Arc<dyn DynValidator>,
+}
+
+#[cfg(feature = "extensions")]
+impl ExtensionValidator {
+    pub fn new<T: DynValidator + 'static>(validator: T) -> Self {
+        Self {
+            validator: Arc::new(validator),
+        }
+    }
+}
+
+impl Validator for ExtensionValidator {
+    fn validate<'data>(
+        &self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        self.validator.validate(py, input, extra, slots)
+    }
+
+    fn validate_strict<'data>(
+        &self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        self.validator.validate_strict(py, input, extra, slots)
+    }
+
+    fn get_name(&self, py: Python) -> String {
+        self.validator.get_name(py)
+    }
+}
+
+type BuildFn = fn(&PyDict, Option<&PyDict>, &mut BuildContext) -> PyResult<CombinedValidator>;
+
+/// Schema types registered by other crates, see `register_validator`
+static EXTENSIONS: RwLock<Vec<(&str, BuildFn)>> = RwLock::new(Vec::new());
+
+/// Register a validator defined in another crate, schemas with `type` set to `T::EXPECTED_TYPE` are then built
+/// using `T::build`; this must be called before building any `SchemaValidator` which uses the type, built-in types
+/// take precedence
+#[cfg(feature = "extensions")]
+pub fn register_validator<T: super::BuildValidator>() {
+    let mut extensions = EXTENSIONS.write().unwrap_or_else(|e| e.into_inner());
+    extensions.retain(|(type_, _)| *type_ != T::EXPECTED_TYPE);
+    extensions.push((T::EXPECTED_TYPE, T::build));
+}
+
+/// Build a validator for a type registered with `register_validator`, `None` if `type_` hasn't been registered
+pub fn build_extension_type(
+    type_: &str,
+    schema: &PyDict,
+    config: Option<&PyDict>,
+    build_context: &mut BuildContext,
+) -> PyResult<Option<CombinedValidator>> {
+    let build_fn = {
+        let extensions = EXTENSIONS.read().unwrap_or_else(|e| e.into_inner());
+        extensions
+            .iter()
+            .find(|(t, _)| *t == type_)
+            .map(|(_, build_fn)| *build_fn)
+    };
+    match build_fn {
+        Some(build_fn) => Ok(Some(build_fn(schema, config, build_context)?)),
+        None => Ok(None),
+    }
+}
@@ -1,14 +1,17 @@
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
-use crate::build_tools::{is_strict, SchemaDict};
-use crate::errors::{context, err_val_error, ErrorKind, InputValue, ValResult};
-use crate::input::Input;
+use crate::build_tools::{is_strict, py_error, schema_or_config, SchemaDict};
+use crate::errors::{context, err_val_error, Context, ErrorKind, InputValue, ValResult};
+use crate::input::{Input, JsonInput, LaxCoercions, LaxType};
 
+use super::pure::{PureExtra, PureOutput};
 use super::{BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
 
 #[derive(Debug, Clone)]
-pub struct FloatValidator;
+pub struct FloatValidator {
+    strict_protocols: bool,
+}
 
 impl BuildValidator for FloatValidator {
     const EXPECTED_TYPE: &'static str = "float";
@@ -22,13 +25,17 @@ impl BuildValidator for FloatValidator {
             || schema.get_item("le").is_some()
             || schema.get_item("lt").is_some()
             || schema.get_item("ge").is_some()
-            || schema.get_item("gt").is_some();
+            || schema.get_item("gt").is_some()
+            || LaxCoercions::from_schema(schema, config)? != LaxCoercions::default();
         if use_constrained {
             ConstrainedFloatValidator::build(schema, config)
         } else if is_strict(schema, config)? {
-            StrictFloatValidator::build()
+            StrictFloatValidator::build(schema, config)
         } else {
-            Ok(Self.into())
+            Ok(Self {
+                strict_protocols: strict_protocols(schema, config)?,
+            }
+            .into())
         }
     }
 }
@@ -38,10 +45,11 @@ impl Validator for FloatValidator {
         &'s self,
         py: Python<'data>,
         input: &'data dyn Input,
-        _extra: &Extra,
+        extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        Ok(input.lax_float()?.into_py(py))
+        let value = extra.lax_with_report(py, input, "float", |i| i.strict_float(), |i| i.lax_float())?;
+        Ok(value.into_py(py))
     }
 
     fn validate_strict<'s, 'data>(
@@ -51,20 +59,38 @@ impl Validator for FloatValidator {
         _extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        Ok(input.strict_float()?.into_py(py))
+        Ok(strict_float(input, self.strict_protocols)?.into_py(py))
     }
 
     fn get_name(&self, _py: Python) -> String {
         Self::EXPECTED_TYPE.to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        _extra: PureExtra,
+        _slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        match input {
+            JsonInput::Int(int) => Some(PureOutput::Float(*int as f64)),
+            JsonInput::Float(float) => Some(PureOutput::Float(*float)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
-pub struct StrictFloatValidator;
+pub struct StrictFloatValidator {
+    strict_protocols: bool,
+}
 
 impl StrictFloatValidator {
-    pub fn build() -> PyResult<CombinedValidator> {
-        Ok(Self.into())
+    pub fn build(schema: &PyDict, config: Option<&PyDict>) -> PyResult<CombinedValidator> {
+        Ok(Self {
+            strict_protocols: strict_protocols(schema, config)?,
+        }
+        .into())
     }
 }
 
@@ -76,22 +102,40 @@ impl Validator for StrictFloatValidator {
         _extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        Ok(input.strict_float()?.into_py(py))
+        Ok(strict_float(input, self.strict_protocols)?.into_py(py))
     }
 
     fn get_name(&self, _py: Python) -> String {
         "strict-float".to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        _extra: PureExtra,
+        _slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        match input {
+            JsonInput::Int(int) => Some(PureOutput::Float(*int as f64)),
+            JsonInput::Float(float) => Some(PureOutput::Float(*float)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ConstrainedFloatValidator {
     strict: bool,
+    strict_protocols: bool,
+    lax_coercions: LaxCoercions,
     multiple_of: Option<f64>,
     le: Option<f64>,
     lt: Option<f64>,
     ge: Option<f64>,
     gt: Option<f64>,
+    // values within epsilon of a bound (or of a multiple of `multiple_of`) are treated as equal to it, so
+    // representation error like `0.1 + 0.2 != 0.3` doesn't cause spurious failures
+    epsilon: f64,
 }
 
 impl Validator for ConstrainedFloatValidator {
@@ -99,12 +143,15 @@ impl Validator for ConstrainedFloatValidator {
         &'s self,
         py: Python<'data>,
         input: &'data dyn Input,
-        _extra: &Extra,
+        extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
         let float = match self.strict {
-            true => input.strict_float()?,
-            false => input.lax_float()?,
+            true => strict_float(input, self.strict_protocols)?,
+            false => {
+                self.lax_coercions.check(input, LaxType::Float)?;
+                extra.lax_with_report(py, input, "float", |i| i.strict_float(), |i| i.lax_float())?
+            }
         };
         self._validation_logic(py, input, float)
     }
@@ -116,78 +163,102 @@ impl Validator for ConstrainedFloatValidator {
         _extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        self._validation_logic(py, input, input.strict_float()?)
+        self._validation_logic(py, input, strict_float(input, self.strict_protocols)?)
     }
 
     fn get_name(&self, _py: Python) -> String {
         "constrained-float".to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        _extra: PureExtra,
+        _slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        let float = match input {
+            JsonInput::Int(int) => *int as f64,
+            JsonInput::Float(float) => *float,
+            _ => return None,
+        };
+        self.check_constraints(float).ok().map(|_| PureOutput::Float(float))
+    }
 }
 
 impl ConstrainedFloatValidator {
     pub fn build(schema: &PyDict, config: Option<&PyDict>) -> PyResult<CombinedValidator> {
+        let epsilon: f64 = schema.get_as("epsilon")?.unwrap_or(0.0);
+        if !(epsilon.is_finite() && epsilon >= 0.0) {
+            return py_error!("Invalid epsilon: {}, must be a non-negative number", epsilon);
+        }
         Ok(Self {
             strict: is_strict(schema, config)?,
+            strict_protocols: strict_protocols(schema, config)?,
+            lax_coercions: LaxCoercions::from_schema(schema, config)?,
             multiple_of: schema.get_as("multiple_of")?,
             le: schema.get_as("le")?,
             lt: schema.get_as("lt")?,
             ge: schema.get_as("ge")?,
             gt: schema.get_as("gt")?,
+            epsilon,
         }
         .into())
     }
 
-    fn _validation_logic<'s, 'data>(
-        &'s self,
-        py: Python<'data>,
-        input: &'data dyn Input,
-        float: f64,
-    ) -> ValResult<'data, PyObject> {
+    fn _validation_logic<'a>(&self, py: Python<'a>, input: &'a dyn Input, float: f64) -> ValResult<'a, PyObject> {
+        match self.check_constraints(float) {
+            Ok(()) => Ok(float.into_py(py)),
+            Err((kind, context)) => err_val_error!(
+                input_value = InputValue::InputRef(input),
+                kind = kind,
+                context = context
+            ),
+        }
+    }
+
+    /// Check `float` against the constraints without python, so items can be checked with the GIL released
+    pub fn check_constraints(&self, float: f64) -> Result<(), (ErrorKind, Context)> {
+        let epsilon = self.epsilon;
         if let Some(multiple_of) = self.multiple_of {
-            if float % multiple_of != 0.0 {
-                return err_val_error!(
-                    input_value = InputValue::InputRef(input),
-                    kind = ErrorKind::FloatMultiple,
-                    context = context!("multiple_of" => multiple_of)
-                );
+            let remainder = (float % multiple_of).abs();
+            // written so a NaN remainder, e.g. from an infinite input, fails
+            if !(remainder <= epsilon || multiple_of.abs() - remainder <= epsilon) {
+                return Err((ErrorKind::FloatMultiple, context!("multiple_of" => multiple_of)));
             }
         }
         if let Some(le) = self.le {
-            if float > le {
-                return err_val_error!(
-                    input_value = InputValue::InputRef(input),
-                    kind = ErrorKind::FloatLessThanEqual,
-                    context = context!("le" => le)
-                );
+            if float > le + epsilon {
+                return Err((ErrorKind::FloatLessThanEqual, context!("le" => le)));
             }
         }
         if let Some(lt) = self.lt {
-            if float >= lt {
-                return err_val_error!(
-                    input_value = InputValue::InputRef(input),
-                    kind = ErrorKind::FloatLessThan,
-                    context = context!("lt" => lt)
-                );
+            if float >= lt - epsilon {
+                return Err((ErrorKind::FloatLessThan, context!("lt" => lt)));
             }
         }
         if let Some(ge) = self.ge {
-            if float < ge {
-                return err_val_error!(
-                    input_value = InputValue::InputRef(input),
-                    kind = ErrorKind::FloatGreaterThanEqual,
-                    context = context!("ge" => ge)
-                );
+            if float < ge - epsilon {
+                return Err((ErrorKind::FloatGreaterThanEqual, context!("ge" => ge)));
             }
         }
         if let Some(gt) = self.gt {
-            if float <= gt {
-                return err_val_error!(
-                    input_value = InputValue::InputRef(input),
-                    kind = ErrorKind::FloatGreaterThan,
-                    context = context!("gt" => gt)
-                );
+            if float <= gt + epsilon {
+                return Err((ErrorKind::FloatGreaterThan, context!("gt" => gt)));
             }
         }
-        Ok(float.into_py(py))
+        Ok(())
+    }
+}
+
+/// Whether strict mode accepts objects implementing `__float__` or `__index__`, e.g. numpy scalars and `Decimal`s,
+/// otherwise only `float`s and `int`s are accepted
+fn strict_protocols(schema: &PyDict, config: Option<&PyDict>) -> PyResult<bool> {
+    Ok(schema_or_config(schema, config, "strict_protocols", "float_strict_protocols")?.unwrap_or(true))
+}
+
+fn strict_float(input: &dyn Input, strict_protocols: bool) -> ValResult<f64> {
+    match strict_protocols {
+        true => input.strict_float(),
+        false => input.exact_float(),
     }
 }
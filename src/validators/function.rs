@@ -1,12 +1,19 @@
+use std::rc::Rc;
+
 use pyo3::exceptions::{PyAssertionError, PyValueError};
+use pyo3::intern;
 use pyo3::prelude::*;
 use pyo3::types::{PyAny, PyDict};
 
 use crate::build_tools::{py_error, SchemaDict};
-use crate::errors::{as_validation_err, val_line_error, ErrorKind, InputValue, ValError, ValLineError, ValResult};
+use crate::errors::{as_validation_err, val_line_error, ErrorKind, InputValue, ValError, ValResult, ValidationError};
 use crate::input::Input;
 
-use super::{build_validator, BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
+use super::limits::Limits;
+use super::state::ValidationState;
+use super::{
+    build_validator, BuildContext, BuildValidator, CombinedValidator, ConfigOverrides, Extra, FieldLookup, Validator,
+};
 
 #[derive(Debug)]
 pub struct FunctionBuilder;
@@ -72,32 +79,19 @@ impl Validator for FunctionBeforeValidator {
         extra: &Extra,
         slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        let kwargs = kwargs!(py, "data" => extra.data, "config" => self.config.as_ref());
-        let value = self
-            .func
-            .call(py, (input.to_py(py),), kwargs)
-            .map_err(|e| convert_err(py, e, input))?;
+        let kwargs = kwargs!(py, "data" => extra.data, "config" => self.config.as_ref(), "context" => extra.context);
+        let value =
+            call_function(py, &self.func, input.to_py(py), kwargs, extra).map_err(|e| convert_err(py, e, input))?;
         // maybe there's some way to get the PyAny here and explicitly tell rust it should have lifespan 'a?
         let new_input: &PyAny = value.as_ref(py);
-        match self.validator.validate(py, new_input, extra, slots) {
-            Ok(v) => Ok(v),
-            Err(ValError::InternalErr(err)) => Err(ValError::InternalErr(err)),
-            Err(ValError::LineErrors(line_errors)) => {
-                // we have to be explicit about copying line errors here and converting the input value
-                Err(ValError::LineErrors(
-                    line_errors
-                        .into_iter()
-                        .map(|line_error| ValLineError {
-                            kind: line_error.kind,
-                            location: line_error.location,
-                            message: line_error.message,
-                            input_value: InputValue::PyObject(line_error.input_value.to_py(py)),
-                            context: line_error.context,
-                        })
-                        .collect(),
-                ))
-            }
-        }
+        self.validator
+            .validate(py, new_input, extra, slots)
+            .map_err(|err| err.into_owned(py))
+    }
+
+    /// functions are skipped when constructing, but the inner validator is used
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        self.validator.construct(py, input, slots)
     }
 
     fn get_name(&self, _py: Python) -> String {
@@ -122,9 +116,18 @@ impl Validator for FunctionAfterValidator {
         extra: &Extra,
         slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        let v = self.validator.validate(py, input, extra, slots)?;
-        let kwargs = kwargs!(py, "data" => extra.data, "config" => self.config.as_ref());
-        self.func.call(py, (v,), kwargs).map_err(|e| convert_err(py, e, input))
+        // the function is called with the output, so it must be built
+        let inner_extra = Extra {
+            check_only: false,
+            ..*extra
+        };
+        let v = self.validator.validate(py, input, &inner_extra, slots)?;
+        let kwargs = kwargs!(py, "data" => extra.data, "config" => self.config.as_ref(), "context" => extra.context);
+        call_function(py, &self.func, v, kwargs, extra).map_err(|e| convert_err(py, e, input))
+    }
+
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        self.validator.construct(py, input, slots)
     }
 
     fn get_name(&self, _py: Python) -> String {
@@ -156,10 +159,8 @@ impl Validator for FunctionPlainValidator {
         extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        let kwargs = kwargs!(py, "data" => extra.data, "config" => self.config.as_ref());
-        self.func
-            .call(py, (input.to_py(py),), kwargs)
-            .map_err(|e| convert_err(py, e, input))
+        let kwargs = kwargs!(py, "data" => extra.data, "config" => self.config.as_ref(), "context" => extra.context);
+        call_function(py, &self.func, input.to_py(py), kwargs, extra).map_err(|e| convert_err(py, e, input))
     }
 
     fn get_name(&self, _py: Python) -> String {
@@ -189,16 +190,28 @@ impl Validator for FunctionWrapValidator {
             slots: slots.to_vec(),
             data: extra.data.map(|d| d.into_py(py)),
             field: extra.field.map(|f| f.to_string()),
+            context: extra.context.map(|c| c.into_py(py)),
+            event_loop: extra.event_loop.map(|l| l.into_py(py)),
+            field_lookup: extra.field_lookup,
+            overrides: extra.overrides,
+            state: extra.state.cloned(),
+            warnings: extra.warnings.is_some(),
+            collect_errors: extra.collected_errors.is_some(),
+            coercions: extra.coercions.is_some(),
+            limits: extra.limits.cloned(),
         };
         let kwargs = kwargs!(
             py,
             "validator" => validator_kwarg,
             "data" => extra.data,
-            "config" => self.config.as_ref()
+            "config" => self.config.as_ref(),
+            "context" => extra.context
         );
-        self.func
-            .call(py, (input.to_py(py),), kwargs)
-            .map_err(|e| convert_err(py, e, input))
+        call_function(py, &self.func, input.to_py(py), kwargs, extra).map_err(|e| convert_err(py, e, input))
+    }
+
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        self.validator.construct(py, input, slots)
     }
 
     fn get_name(&self, _py: Python) -> String {
@@ -213,14 +226,42 @@ struct ValidatorCallable {
     slots: Vec<CombinedValidator>,
     data: Option<Py<PyDict>>,
     field: Option<String>,
+    context: Option<PyObject>,
+    event_loop: Option<PyObject>,
+    field_lookup: FieldLookup,
+    overrides: ConfigOverrides,
+    state: Option<Rc<ValidationState>>,
+    /// Whether warnings are collected, they're added to the collector in `state`
+    warnings: bool,
+    /// Whether errors are collected, see `warnings`
+    collect_errors: bool,
+    /// Whether coercions are reported, see `warnings`
+    coercions: bool,
+    limits: Option<Rc<Limits>>,
 }
 
+// SAFETY: the state and limits are reference counted with `Rc` and use `Cell`s, they're only accessed with the
+// GIL held, e.g. when a coroutine returned by a wrap function calls the validator on the event loop's thread, the
+// thread which created the callable waits for it with the GIL released
+unsafe impl Send for ValidatorCallable {}
+
 #[pymethods]
 impl ValidatorCallable {
     fn __call__(&self, py: Python, arg: &PyAny) -> PyResult<PyObject> {
+        let state = self.state.as_deref();
         let extra = Extra {
             data: self.data.as_ref().map(|data| data.as_ref(py)),
             field: self.field.as_deref(),
+            warnings: state.filter(|_| self.warnings).map(|state| &state.warnings),
+            context: self.context.as_ref().map(|c| c.as_ref(py)),
+            collected_errors: state.filter(|_| self.collect_errors).map(|state| &state.errors),
+            coercions: state.filter(|_| self.coercions).map(|state| &state.coercions),
+            event_loop: self.event_loop.as_ref().map(|l| l.as_ref(py)),
+            limits: self.limits.as_ref(),
+            field_lookup: self.field_lookup,
+            state: self.state.as_ref(),
+            overrides: self.overrides,
+            check_only: false,
         };
         self.validator
             .validate(py, arg, &extra, &self.slots)
@@ -235,6 +276,29 @@ impl ValidatorCallable {
     }
 }
 
+/// Call a validator function, when validating with `validate_python_async` the function may return a coroutine,
+/// it's run on the event loop and this thread waits for the result
+fn call_function(
+    py: Python,
+    func: &PyObject,
+    arg: PyObject,
+    kwargs: Option<&PyDict>,
+    extra: &Extra,
+) -> PyResult<PyObject> {
+    let result = func.call(py, (arg,), kwargs)?;
+    if let Some(event_loop) = extra.event_loop {
+        let asyncio = py.import("asyncio")?;
+        let is_coroutine = asyncio
+            .call_method1(intern!(py, "iscoroutine"), (&result,))?
+            .is_true()?;
+        if is_coroutine {
+            let future = asyncio.call_method1(intern!(py, "run_coroutine_threadsafe"), (result, event_loop))?;
+            return Ok(future.call_method0(intern!(py, "result"))?.into());
+        }
+    }
+    Ok(result)
+}
+
 fn get_function(schema: &PyDict) -> PyResult<PyObject> {
     match schema.get_item("function") {
         Some(obj) => {
@@ -248,7 +312,16 @@ fn get_function(schema: &PyDict) -> PyResult<PyObject> {
     }
 }
 
-fn convert_err<'a>(py: Python<'a>, err: PyErr, input: &'a dyn Input) -> ValError<'a> {
+pub(super) fn convert_err<'a>(py: Python<'a>, err: PyErr, input: &'a dyn Input) -> ValError<'a> {
+    // a `ValidationError`, e.g. from `validator` in wrap functions or raised by a function which checks several
+    // fields at once, keeps its errors, their locations are relative to this validator
+    if let Ok(validation_error) = err.value(py).extract::<PyRef<ValidationError>>() {
+        let line_errors = validation_error.val_line_errors(py);
+        if !line_errors.is_empty() {
+            return ValError::LineErrors(line_errors);
+        }
+    }
+
     // Only ValueError and AssertionError are considered as validation errors,
     // TypeError is now considered as a runtime error to catch errors in function signatures
     let kind = if err.is_instance_of::<PyValueError>(py) {
@@ -263,11 +336,18 @@ fn convert_err<'a>(py: Python<'a>, err: PyErr, input: &'a dyn Input) -> ValError
         Ok(s) => Some(s.to_string()),
         Err(err) => return ValError::InternalErr(err),
     };
+    // keep the traceback on the exception so it's not lost when it's used as the cause of a `ValidationError`
+    if let Some(traceback) = err.traceback(py) {
+        if let Err(err) = err.value(py).setattr(intern!(py, "__traceback__"), traceback) {
+            return ValError::InternalErr(err);
+        }
+    }
     #[allow(clippy::redundant_field_names)]
     let line_error = val_line_error!(
         input_value = InputValue::InputRef(input),
         kind = kind,
-        message = message
+        message = message,
+        cause = Some(err)
     );
     ValError::LineErrors(vec![line_error])
 }
@@ -0,0 +1,73 @@
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+
+use crate::build_tools::py_error;
+
+/// Called around every validation by every `SchemaValidator`, e.g. so APM tools can trace validation without
+/// wrapping each call site; `title` is the title of the validator's schema
+pub trait ValidationHooks: Send + Sync {
+    fn on_start(&self, py: Python, title: &str) -> PyResult<()>;
+
+    /// `error_count` is the number of errors raised, or collected with `collect_errors=True`
+    fn on_end(&self, py: Python, title: &str, duration: Duration, error_count: usize) -> PyResult<()>;
+}
+
+static HOOKS: RwLock<Option<Arc<dyn ValidationHooks>>> = RwLock::new(None);
+
+/// Replace the hooks called around validation, `None` removes them
+pub fn set_hooks(hooks: Option<Arc<dyn ValidationHooks>>) {
+    if let Ok(mut current) = HOOKS.write() {
+        *current = hooks;
+    }
+}
+
+/// The current hooks, cloned so hooks may be replaced while they're running
+pub(super) fn get_hooks() -> Option<Arc<dyn ValidationHooks>> {
+    HOOKS.read().ok().and_then(|hooks| hooks.clone())
+}
+
+/// Hooks set from python with `set_validation_hooks`
+struct PyValidationHooks {
+    on_start: Option<PyObject>,
+    on_end: Option<PyObject>,
+}
+
+impl ValidationHooks for PyValidationHooks {
+    fn on_start(&self, py: Python, title: &str) -> PyResult<()> {
+        if let Some(ref on_start) = self.on_start {
+            on_start.call1(py, (title,))?;
+        }
+        Ok(())
+    }
+
+    fn on_end(&self, py: Python, title: &str, duration: Duration, error_count: usize) -> PyResult<()> {
+        if let Some(ref on_end) = self.on_end {
+            on_end.call1(py, (title, duration.as_secs_f64(), error_count))?;
+        }
+        Ok(())
+    }
+}
+
+/// Set functions called around every validation: `on_start(title)` before and
+/// `on_end(title, duration, error_count)` after, `duration` is in seconds; exceptions raised by either are raised
+/// by the validation; calling without arguments removes the hooks
+#[pyfunction(on_start = "None", on_end = "None")]
+pub fn set_validation_hooks(on_start: Option<&PyAny>, on_end: Option<&PyAny>) -> PyResult<()> {
+    for hook in [on_start, on_end].into_iter().flatten() {
+        if !hook.is_callable() {
+            return py_error!(PyTypeError; "validation hooks must be callable");
+        }
+    }
+    let hooks: Option<Arc<dyn ValidationHooks>> = match (on_start, on_end) {
+        (None, None) => None,
+        _ => Some(Arc::new(PyValidationHooks {
+            on_start: on_start.map(|f| f.into()),
+            on_end: on_end.map(|f| f.into()),
+        })),
+    };
+    set_hooks(hooks);
+    Ok(())
+}
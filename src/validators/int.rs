@@ -1,14 +1,17 @@
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
-use crate::build_tools::{is_strict, SchemaDict};
-use crate::errors::{context, err_val_error, ErrorKind, InputValue, ValResult};
-use crate::input::Input;
+use crate::build_tools::{is_strict, schema_or_config, SchemaDict};
+use crate::errors::{context, err_val_error, Context, ErrorKind, InputValue, ValResult};
+use crate::input::{Input, JsonInput, LaxCoercions, LaxType};
 
+use super::pure::{PureExtra, PureOutput};
 use super::{BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
 
 #[derive(Debug, Clone)]
-pub struct IntValidator;
+pub struct IntValidator {
+    strict_protocols: bool,
+}
 
 impl BuildValidator for IntValidator {
     const EXPECTED_TYPE: &'static str = "int";
@@ -22,13 +25,17 @@ impl BuildValidator for IntValidator {
             || schema.get_item("le").is_some()
             || schema.get_item("lt").is_some()
             || schema.get_item("ge").is_some()
-            || schema.get_item("gt").is_some();
+            || schema.get_item("gt").is_some()
+            || LaxCoercions::from_schema(schema, config)? != LaxCoercions::default();
         if use_constrained {
             ConstrainedIntValidator::build(schema, config)
         } else if is_strict(schema, config)? {
-            StrictIntValidator::build()
+            StrictIntValidator::build(schema, config)
         } else {
-            Ok(Self.into())
+            Ok(Self {
+                strict_protocols: strict_protocols(schema, config)?,
+            }
+            .into())
         }
     }
 }
@@ -38,10 +45,11 @@ impl Validator for IntValidator {
         &'s self,
         py: Python<'data>,
         input: &'data dyn Input,
-        _extra: &Extra,
+        extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        Ok(input.lax_int()?.into_py(py))
+        let value = extra.lax_with_report(py, input, "int", |i| i.strict_int(), |i| i.lax_int())?;
+        Ok(value.into_py(py))
     }
 
     fn validate_strict<'s, 'data>(
@@ -51,20 +59,37 @@ impl Validator for IntValidator {
         _extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        Ok(input.strict_int()?.into_py(py))
+        Ok(strict_int(input, self.strict_protocols)?.into_py(py))
     }
 
     fn get_name(&self, _py: Python) -> String {
         Self::EXPECTED_TYPE.to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        _extra: PureExtra,
+        _slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        match input {
+            JsonInput::Int(int) => Some(PureOutput::Int(*int)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
-pub struct StrictIntValidator;
+pub struct StrictIntValidator {
+    strict_protocols: bool,
+}
 
 impl StrictIntValidator {
-    fn build() -> PyResult<CombinedValidator> {
-        Ok(Self.into())
+    fn build(schema: &PyDict, config: Option<&PyDict>) -> PyResult<CombinedValidator> {
+        Ok(Self {
+            strict_protocols: strict_protocols(schema, config)?,
+        }
+        .into())
     }
 }
 
@@ -76,22 +101,38 @@ impl Validator for StrictIntValidator {
         _extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        Ok(input.strict_int()?.into_py(py))
+        Ok(strict_int(input, self.strict_protocols)?.into_py(py))
     }
 
     fn get_name(&self, _py: Python) -> String {
         "strict-int".to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        _extra: PureExtra,
+        _slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        match input {
+            JsonInput::Int(int) => Some(PureOutput::Int(*int)),
+            _ => None,
+        }
+    }
 }
 
+/// Constraints are held as `i128` so bounds beyond the range of `i64` can be used, they're compared with the
+/// extracted `i64` directly without creating python ints
 #[derive(Debug, Clone)]
 pub struct ConstrainedIntValidator {
     strict: bool,
-    multiple_of: Option<i64>,
-    le: Option<i64>,
-    lt: Option<i64>,
-    ge: Option<i64>,
-    gt: Option<i64>,
+    strict_protocols: bool,
+    lax_coercions: LaxCoercions,
+    multiple_of: Option<i128>,
+    le: Option<i128>,
+    lt: Option<i128>,
+    ge: Option<i128>,
+    gt: Option<i128>,
 }
 
 impl Validator for ConstrainedIntValidator {
@@ -99,12 +140,15 @@ impl Validator for ConstrainedIntValidator {
         &'s self,
         py: Python<'data>,
         input: &'data dyn Input,
-        _extra: &Extra,
+        extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
         let int = match self.strict {
-            true => input.strict_int()?,
-            false => input.lax_int()?,
+            true => strict_int(input, self.strict_protocols)?,
+            false => {
+                self.lax_coercions.check(input, LaxType::Int)?;
+                extra.lax_with_report(py, input, "int", |i| i.strict_int(), |i| i.lax_int())?
+            }
         };
         self._validation_logic(py, input, int)
     }
@@ -116,18 +160,32 @@ impl Validator for ConstrainedIntValidator {
         _extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        self._validation_logic(py, input, input.strict_int()?)
+        self._validation_logic(py, input, strict_int(input, self.strict_protocols)?)
     }
 
     fn get_name(&self, _py: Python) -> String {
         "constrained-int".to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        _extra: PureExtra,
+        _slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        match input {
+            JsonInput::Int(int) => self.check_constraints(*int).ok().map(|_| PureOutput::Int(*int)),
+            _ => None,
+        }
+    }
 }
 
 impl ConstrainedIntValidator {
     fn build(schema: &PyDict, config: Option<&PyDict>) -> PyResult<CombinedValidator> {
         Ok(Self {
             strict: is_strict(schema, config)?,
+            strict_protocols: strict_protocols(schema, config)?,
+            lax_coercions: LaxCoercions::from_schema(schema, config)?,
             multiple_of: schema.get_as("multiple_of")?,
             le: schema.get_as("le")?,
             lt: schema.get_as("lt")?,
@@ -138,51 +196,57 @@ impl ConstrainedIntValidator {
     }
 
     fn _validation_logic<'a>(&self, py: Python<'a>, input: &'a dyn Input, int: i64) -> ValResult<'a, PyObject> {
+        match self.check_constraints(int) {
+            Ok(()) => Ok(int.into_py(py)),
+            Err((kind, context)) => err_val_error!(
+                input_value = InputValue::InputRef(input),
+                kind = kind,
+                context = context
+            ),
+        }
+    }
+
+    /// Check `int` against the constraints without python, so items can be checked with the GIL released
+    pub fn check_constraints(&self, int: i64) -> Result<(), (ErrorKind, Context)> {
+        let value = i128::from(int);
         if let Some(multiple_of) = self.multiple_of {
-            if int % multiple_of != 0 {
-                return err_val_error!(
-                    input_value = InputValue::InputRef(input),
-                    kind = ErrorKind::IntMultiple,
-                    context = context!("multiple_of" => multiple_of)
-                );
+            if value % multiple_of != 0 {
+                return Err((ErrorKind::IntMultiple, context!("multiple_of" => multiple_of)));
             }
         }
         if let Some(le) = self.le {
-            if int > le {
-                return err_val_error!(
-                    input_value = InputValue::InputRef(input),
-                    kind = ErrorKind::IntLessThanEqual,
-                    context = context!("le" => le)
-                );
+            if value > le {
+                return Err((ErrorKind::IntLessThanEqual, context!("le" => le)));
             }
         }
         if let Some(lt) = self.lt {
-            if int >= lt {
-                return err_val_error!(
-                    input_value = InputValue::InputRef(input),
-                    kind = ErrorKind::IntLessThan,
-                    context = context!("lt" => lt)
-                );
+            if value >= lt {
+                return Err((ErrorKind::IntLessThan, context!("lt" => lt)));
             }
         }
         if let Some(ge) = self.ge {
-            if int < ge {
-                return err_val_error!(
-                    input_value = InputValue::InputRef(input),
-                    kind = ErrorKind::IntGreaterThanEqual,
-                    context = context!("ge" => ge)
-                );
+            if value < ge {
+                return Err((ErrorKind::IntGreaterThanEqual, context!("ge" => ge)));
             }
         }
         if let Some(gt) = self.gt {
-            if int <= gt {
-                return err_val_error!(
-                    input_value = InputValue::InputRef(input),
-                    kind = ErrorKind::IntGreaterThan,
-                    context = context!("gt" => gt)
-                );
+            if value <= gt {
+                return Err((ErrorKind::IntGreaterThan, context!("gt" => gt)));
             }
         }
-        Ok(int.into_py(py))
+        Ok(())
+    }
+}
+
+/// Whether strict mode accepts objects implementing `__index__`, e.g. numpy integers, otherwise only `int`s are
+/// accepted
+fn strict_protocols(schema: &PyDict, config: Option<&PyDict>) -> PyResult<bool> {
+    Ok(schema_or_config(schema, config, "strict_protocols", "int_strict_protocols")?.unwrap_or(true))
+}
+
+fn strict_int(input: &dyn Input, strict_protocols: bool) -> ValResult<i64> {
+    match strict_protocols {
+        true => input.strict_int(),
+        false => input.exact_int(),
     }
 }
@@ -0,0 +1,141 @@
+use std::os::raw::c_int;
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple, PyType};
+use pyo3::{ffi, AsPyPointer};
+
+use crate::build_tools::{py_error, SchemaDict};
+use crate::errors::{as_internal, context, err_val_error, ErrorKind, InputValue, ValResult};
+use crate::input::{Input, InputSource};
+
+use super::{BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
+
+/// Checks the input with `isinstance(input, class_type)`, the input is returned unchanged, e.g. for fields with
+/// arbitrary types; JSON can't contain instances of python classes so JSON input is always an error
+#[derive(Debug, Clone)]
+pub struct IsInstanceValidator {
+    class: PyObject,
+    class_name: String,
+}
+
+impl BuildValidator for IsInstanceValidator {
+    const EXPECTED_TYPE: &'static str = "is-instance";
+
+    fn build(
+        schema: &PyDict,
+        _config: Option<&PyDict>,
+        _build_context: &mut BuildContext,
+    ) -> PyResult<CombinedValidator> {
+        let (class, class_name) = get_class(schema)?;
+        Ok(Self { class, class_name }.into())
+    }
+}
+
+impl Validator for IsInstanceValidator {
+    fn validate<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        _extra: &Extra,
+        _slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        if input.source() == InputSource::Python {
+            let obj = input.to_py(py);
+            // Safety: both pointers are valid python objects
+            let result = unsafe { ffi::PyObject_IsInstance(obj.as_ptr(), self.class.as_ptr()) };
+            if check_result(py, result).map_err(as_internal)? {
+                return Ok(obj);
+            }
+        }
+        err_val_error!(
+            input_value = InputValue::InputRef(input),
+            kind = ErrorKind::IsInstanceOf,
+            context = context!("class_name" => self.class_name.clone())
+        )
+    }
+
+    fn get_name(&self, _py: Python) -> String {
+        format!("{}[{}]", Self::EXPECTED_TYPE, self.class_name)
+    }
+}
+
+/// Checks the input is a class and `issubclass(input, class_type)`, the input is returned unchanged
+#[derive(Debug, Clone)]
+pub struct IsSubclassValidator {
+    class: PyObject,
+    class_name: String,
+}
+
+impl BuildValidator for IsSubclassValidator {
+    const EXPECTED_TYPE: &'static str = "is-subclass";
+
+    fn build(
+        schema: &PyDict,
+        _config: Option<&PyDict>,
+        _build_context: &mut BuildContext,
+    ) -> PyResult<CombinedValidator> {
+        let (class, class_name) = get_class(schema)?;
+        Ok(Self { class, class_name }.into())
+    }
+}
+
+impl Validator for IsSubclassValidator {
+    fn validate<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        _extra: &Extra,
+        _slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        if input.source() == InputSource::Python {
+            let obj = input.to_py(py);
+            // `issubclass` raises a `TypeError` if the input isn't a class
+            if obj.as_ref(py).cast_as::<PyType>().is_ok() {
+                // Safety: both pointers are valid python objects
+                let result = unsafe { ffi::PyObject_IsSubclass(obj.as_ptr(), self.class.as_ptr()) };
+                if check_result(py, result).map_err(as_internal)? {
+                    return Ok(obj);
+                }
+            }
+        }
+        err_val_error!(
+            input_value = InputValue::InputRef(input),
+            kind = ErrorKind::IsSubclassOf,
+            context = context!("class_name" => self.class_name.clone())
+        )
+    }
+
+    fn get_name(&self, _py: Python) -> String {
+        format!("{}[{}]", Self::EXPECTED_TYPE, self.class_name)
+    }
+}
+
+/// `class_type` from the schema, either a class or a tuple of classes, and its name used in errors,
+/// e.g. `Foo` or `(Foo, Bar)`
+fn get_class(schema: &PyDict) -> PyResult<(PyObject, String)> {
+    let class: &PyAny = schema.get_as_req("class_type")?;
+    let class_name = if let Ok(class) = class.cast_as::<PyType>() {
+        class.name()?.to_string()
+    } else if let Ok(classes) = class.cast_as::<PyTuple>() {
+        let names = classes
+            .iter()
+            .map(|class| match class.cast_as::<PyType>() {
+                Ok(class) => Ok(class.name()?.to_string()),
+                Err(_) => py_error!("'class_type' must be a class or a tuple of classes"),
+            })
+            .collect::<PyResult<Vec<String>>>()?;
+        format!("({})", names.join(", "))
+    } else {
+        return py_error!("'class_type' must be a class or a tuple of classes");
+    };
+    Ok((class.into(), class_name))
+}
+
+/// The result of `PyObject_IsInstance` or `PyObject_IsSubclass`, -1 means an exception was raised,
+/// e.g. by `__instancecheck__`
+fn check_result(py: Python, result: c_int) -> PyResult<bool> {
+    match result {
+        -1 => Err(PyErr::fetch(py)),
+        result => Ok(result == 1),
+    }
+}
@@ -0,0 +1,81 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::from_str as parse_json;
+
+use crate::errors::{as_validation_err, err_val_error, ErrorKind, InputValue, ValResult};
+use crate::input::{Input, JsonInput, ToPy};
+
+use super::{build_validator, BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
+
+/// Parses a JSON string, then validates the result against the optional inner schema
+#[derive(Debug, Clone)]
+pub struct JsonValidator {
+    validator: Option<Box<CombinedValidator>>,
+}
+
+impl BuildValidator for JsonValidator {
+    const EXPECTED_TYPE: &'static str = "json";
+
+    fn build(
+        schema: &PyDict,
+        config: Option<&PyDict>,
+        build_context: &mut BuildContext,
+    ) -> PyResult<CombinedValidator> {
+        Ok(Self {
+            validator: match schema.get_item("schema") {
+                Some(s) => Some(Box::new(build_validator(s, config, build_context)?.0)),
+                None => None,
+            },
+        }
+        .into())
+    }
+}
+
+impl Validator for JsonValidator {
+    fn validate<'data>(
+        &self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        let json_input = parse(input)?;
+        match self.validator {
+            // errors have to be converted since they can't borrow the parsed JSON
+            Some(ref validator) => validator
+                .validate(py, &json_input, extra, slots)
+                .map_err(|err| err.into_owned(py)),
+            None => Ok(json_input.to_py(py)),
+        }
+    }
+
+    /// the input is still parsed, invalid JSON raises a `ValidationError`
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        let output = parse(input)
+            .map_err(|err| as_validation_err(py, &self.get_name(py), err))?
+            .to_py(py);
+        match self.validator {
+            Some(ref validator) => validator.construct(py, output.as_ref(py), slots),
+            None => Ok(output),
+        }
+    }
+
+    fn get_name(&self, py: Python) -> String {
+        match self.validator {
+            Some(ref v) => format!("{}-{}", Self::EXPECTED_TYPE, v.get_name(py)),
+            None => Self::EXPECTED_TYPE.to_string(),
+        }
+    }
+}
+
+fn parse(input: &dyn Input) -> ValResult<JsonInput> {
+    let json_str = input.json_str()?;
+    match parse_json::<JsonInput>(json_str.as_str()?) {
+        Ok(json_input) => Ok(json_input),
+        Err(e) => err_val_error!(
+            input_value = InputValue::InputRef(input),
+            message = Some(e.to_string()),
+            kind = ErrorKind::InvalidJson
+        ),
+    }
+}
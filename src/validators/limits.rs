@@ -0,0 +1,146 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::build_tools::py_error;
+use crate::errors::{context, err_val_error, ErrorKind, InputValue, ValError, ValLineError, ValResult};
+use crate::input::{EitherString, Input};
+
+/// Limits on the size of the input and time spent validating it, set via the `limits` argument to
+/// `validate_python` and `validate_json` so untrusted input can't cause pathological CPU or memory usage
+#[derive(Debug, Default)]
+pub struct Limits {
+    /// Total number of items in all lists, sets, dicts and models, items validated more than once
+    /// (e.g. by multiple union choices) are counted each time
+    max_items_total: Option<usize>,
+    /// Maximum number of characters in any string validated as a string
+    max_string_length: Option<usize>,
+    /// Total number of bytes (UTF-8 encoded) in all strings validated as strings, counted like `max_items_total`;
+    /// once it's exceeded validation stops and the `ValidationError` has this as its only error
+    max_string_bytes_total: Option<usize>,
+    /// Maximum nesting of lists, sets, dicts and models
+    max_depth: Option<usize>,
+    /// In seconds, checked whenever a container is validated
+    timeout: Option<f64>,
+    deadline: Option<Instant>,
+    items: Cell<usize>,
+    string_bytes: Cell<usize>,
+    depth: Cell<usize>,
+}
+
+impl Limits {
+    pub fn new(limits: &PyDict) -> PyResult<Self> {
+        let mut new = Self::default();
+        for (key, value) in limits.iter() {
+            match key.extract::<&str>()? {
+                "max_items_total" => new.max_items_total = value.extract()?,
+                "max_string_length" => new.max_string_length = value.extract()?,
+                "max_string_bytes_total" => new.max_string_bytes_total = value.extract()?,
+                "max_depth" => new.max_depth = value.extract()?,
+                "timeout" => new.timeout = value.extract()?,
+                other => return py_error!(PyValueError; "Unknown limit: \"{}\"", other),
+            }
+        }
+        new.deadline = match new.timeout {
+            Some(timeout) if timeout.is_finite() && timeout >= 0.0 => {
+                Some(Instant::now() + Duration::from_secs_f64(timeout))
+            }
+            Some(timeout) => return py_error!(PyValueError; "Invalid timeout: {}", timeout),
+            None => None,
+        };
+        Ok(new)
+    }
+
+    /// Called by container validators before validating their items, the returned guard tracks nesting depth
+    pub fn enter_container<'a, 'data>(
+        &'a self,
+        input: &'data dyn Input,
+        items: usize,
+    ) -> ValResult<'data, DepthGuard<'a>> {
+        // once a total is exceeded the error is replaced anyway, so there's no point validating further
+        if let Some(err) = self.total_exceeded() {
+            return Err(err);
+        }
+        if let (Some(deadline), Some(timeout)) = (self.deadline, self.timeout) {
+            if Instant::now() > deadline {
+                return err_val_error!(
+                    input_value = InputValue::InputRef(input),
+                    kind = ErrorKind::LimitTimeout,
+                    context = context!("timeout" => timeout)
+                );
+            }
+        }
+        let depth = self.depth.get() + 1;
+        if let Some(max_depth) = self.max_depth {
+            if depth > max_depth {
+                return err_val_error!(
+                    input_value = InputValue::InputRef(input),
+                    kind = ErrorKind::LimitDepth,
+                    context = context!("max_depth" => max_depth)
+                );
+            }
+        }
+        let total = self.items.get() + items;
+        if let Some(max_items_total) = self.max_items_total {
+            if total > max_items_total {
+                return err_val_error!(
+                    input_value = InputValue::InputRef(input),
+                    kind = ErrorKind::LimitItemsTotal,
+                    context = context!("max_items_total" => max_items_total)
+                );
+            }
+        }
+        self.items.set(total);
+        self.depth.set(depth);
+        Ok(DepthGuard(&self.depth))
+    }
+
+    pub fn check_str<'data>(&self, input: &'data dyn Input, either_str: &EitherString<'data>) -> ValResult<'data, ()> {
+        if self.max_string_bytes_total.is_some() {
+            let total = self.string_bytes.get() + either_str.as_str()?.len();
+            self.string_bytes.set(total);
+            if let Some(err) = self.total_exceeded() {
+                return Err(err);
+            }
+        }
+        if let Some(max_string_length) = self.max_string_length {
+            let str = either_str.as_str()?;
+            // the byte length is an upper bound on the number of characters, so usually we needn't count them
+            if str.len() > max_string_length && str.chars().count() > max_string_length {
+                return err_val_error!(
+                    input_value = InputValue::InputRef(input),
+                    kind = ErrorKind::LimitStringLength,
+                    context = context!("max_string_length" => max_string_length)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// The error replacing all others if a limit on the whole input was exceeded, the location is empty since
+    /// the limit applies to the input as a whole
+    pub fn total_exceeded<'data>(&self) -> Option<ValError<'data>> {
+        match self.max_string_bytes_total {
+            Some(max_string_bytes_total) if self.string_bytes.get() > max_string_bytes_total => {
+                Some(ValError::LineErrors(vec![ValLineError {
+                    kind: ErrorKind::LimitStringBytesTotal,
+                    context: context!("max_string_bytes_total" => max_string_bytes_total),
+                    ..Default::default()
+                }]))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Decrements the nesting depth when the container validator finishes
+pub struct DepthGuard<'a>(&'a Cell<usize>);
+
+impl Drop for DepthGuard<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() - 1);
+    }
+}
@@ -1,10 +1,14 @@
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
+use pyo3::types::{PyDict, PyFrozenSet, PyList, PySet, PyTuple};
+use smallvec::smallvec;
 
-use crate::build_tools::{is_strict, SchemaDict};
+use crate::build_tools::{is_strict, schema_or_config, SchemaDict};
 use crate::errors::{context, err_val_error, ErrorKind, InputValue, LocItem, ValError, ValLineError};
-use crate::input::{GenericSequence, Input, SequenceLenIter};
+use crate::input::{GenericSequence, Input, JsonInput, NumericArray, SequenceLenIter};
 
+use super::array::{item_to_py, validate_array_items};
+use super::parallel::{check_items, validate_checked, Scalar, ScalarCheck, CHUNK_SIZE};
+use super::pure::{PureExtra, PureOutput};
 use super::{build_validator, BuildContext, BuildValidator, CombinedValidator, Extra, ValResult, Validator};
 
 #[derive(Debug, Clone)]
@@ -13,6 +17,8 @@ pub struct ListValidator {
     item_validator: Option<Box<CombinedValidator>>,
     min_items: Option<usize>,
     max_items: Option<usize>,
+    // check long JSON arrays and numeric arrays of ints or floats in parallel with the GIL released
+    parallel: bool,
 }
 
 impl BuildValidator for ListValidator {
@@ -31,6 +37,7 @@ impl BuildValidator for ListValidator {
             },
             min_items: schema.get_as("min_items")?,
             max_items: schema.get_as("max_items")?,
+            parallel: schema_or_config(schema, config, "parallel", "list_parallel")?.unwrap_or(false),
         }
         .into())
     }
@@ -44,9 +51,12 @@ impl Validator for ListValidator {
         extra: &Extra,
         slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        let list = match self.strict {
+        let list = match self.strict || extra.overrides.strict {
             true => input.strict_list()?,
-            false => input.lax_list()?,
+            false => match input.lax_numeric_array() {
+                Some(array) => return self._validate_array(py, input, array, extra, slots),
+                None => input.lax_list()?,
+            },
         };
         self._validation_logic(py, input, list, extra, slots)
     }
@@ -61,24 +71,48 @@ impl Validator for ListValidator {
         self._validation_logic(py, input, input.strict_list()?, extra, slots)
     }
 
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        match construct_items(py, input, |_| self.item_validator.as_deref(), slots)? {
+            Some(output) => Ok(PyList::new(py, &output).into_py(py)),
+            None => Ok(input.into_py(py)),
+        }
+    }
+
     fn get_name(&self, py: Python) -> String {
         match &self.item_validator {
             Some(v) => format!("{}-{}", Self::EXPECTED_TYPE, v.get_name(py)),
             None => Self::EXPECTED_TYPE.to_string(),
         }
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        extra: PureExtra,
+        slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        let items = match input {
+            JsonInput::Array(items) => items,
+            _ => return None,
+        };
+        let too_short = matches!(self.min_items, Some(min_length) if items.len() < min_length);
+        let too_long = matches!(self.max_items, Some(max_length) if items.len() > max_length);
+        if too_short || too_long {
+            return None;
+        }
+        match self.item_validator {
+            Some(ref validator) => items
+                .iter()
+                .map(|item| validator.validate_pure(item, extra, slots))
+                .collect::<Option<_>>()
+                .map(PureOutput::List),
+            None => Some(PureOutput::Json(input)),
+        }
+    }
 }
 
 impl ListValidator {
-    fn _validation_logic<'s, 'data>(
-        &'s self,
-        py: Python<'data>,
-        input: &'data dyn Input,
-        list: GenericSequence<'data>,
-        extra: &Extra,
-        slots: &'data [CombinedValidator],
-    ) -> ValResult<'data, PyObject> {
-        let length = list.generic_len();
+    fn check_length<'data>(&self, input: &'data dyn Input, length: usize) -> ValResult<'data, ()> {
         if let Some(min_length) = self.min_items {
             if length < min_length {
                 return err_val_error!(
@@ -97,25 +131,86 @@ impl ListValidator {
                 );
             }
         }
+        Ok(())
+    }
+
+    fn _validate_array<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        array: NumericArray,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        self.check_length(input, array.len())?;
+        let _depth = extra.enter_container(input, array.len())?;
+        let output = match self.parallel_check(array.len()) {
+            Some((validator, check)) => {
+                let checked = match array {
+                    NumericArray::Int(ref ints) => check_items(py, ints, |int| Some(Scalar::Int(*int)), check),
+                    NumericArray::Float(ref floats) => {
+                        check_items(py, floats, |float| Some(Scalar::Float(*float)), check)
+                    }
+                };
+                let item_input = |index| -> &'data dyn Input { item_to_py(py, &array, index).into_ref(py) };
+                validate_checked(py, checked, item_input, validator, extra, slots)?
+            }
+            None => validate_array_items(py, &array, |_| self.item_validator.as_deref(), extra, slots)?,
+        };
+        Ok(PyList::new(py, &output).into_py(py))
+    }
+
+    /// The item validator and how to check items without python if items should be checked in parallel
+    fn parallel_check(&self, length: usize) -> Option<(&CombinedValidator, ScalarCheck)> {
+        if !self.parallel || length <= CHUNK_SIZE {
+            return None;
+        }
+        let validator = self.item_validator.as_deref()?;
+        ScalarCheck::new(validator).map(|check| (validator, check))
+    }
+
+    fn _validation_logic<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        list: GenericSequence<'data>,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        let length = list.generic_len();
+        self.check_length(input, length)?;
 
+        let _depth = extra.enter_container(input, length)?;
+        if let (GenericSequence::JsonArray(items), Some((validator, check))) = (&list, self.parallel_check(length)) {
+            let checked = check_items(py, items, Scalar::from_json, check);
+            let items: &'data [JsonInput] = items;
+            let item_input = |index: usize| &items[index] as &'data dyn Input;
+            let output = validate_checked(py, checked, item_input, validator, extra, slots)?;
+            return Ok(output.into_py(py));
+        }
         match self.item_validator {
             Some(ref validator) => {
                 let mut output: Vec<PyObject> = Vec::with_capacity(length);
                 let mut errors: Vec<ValLineError> = Vec::new();
                 for (index, item) in list.generic_iter() {
-                    match validator.validate(py, item, extra, slots) {
+                    let checkpoint = extra.checkpoint();
+                    let result = validator.validate(py, item, extra, slots);
+                    extra.prefix_location(checkpoint, &index);
+                    match result {
                         Ok(item) => output.push(item),
                         Err(ValError::LineErrors(line_errors)) => {
-                            let loc = vec![LocItem::I(index)];
+                            let loc = smallvec![LocItem::I(index)];
                             errors.extend(line_errors.into_iter().map(|err| err.with_prefix_location(&loc)));
                         }
                         Err(err) => return Err(err),
                     }
                 }
-                if errors.is_empty() {
-                    Ok(output.into_py(py))
-                } else {
+                if !errors.is_empty() {
                     Err(ValError::LineErrors(errors))
+                } else if extra.check_only {
+                    Ok(py.None())
+                } else {
+                    Ok(output.into_py(py))
                 }
             }
             None => {
@@ -125,3 +220,33 @@ impl ListValidator {
         }
     }
 }
+
+/// Construct each item of a list, tuple, set or frozenset with the validator `get_validator` returns for its index,
+/// items without a validator are used unchanged; `None` for other inputs, which are used unchanged by `construct`
+pub(super) fn construct_items<'a>(
+    py: Python,
+    input: &PyAny,
+    get_validator: impl Fn(usize) -> Option<&'a CombinedValidator>,
+    slots: &[CombinedValidator],
+) -> PyResult<Option<Vec<PyObject>>> {
+    let items: Vec<&PyAny> = if let Ok(list) = input.cast_as::<PyList>() {
+        list.iter().collect()
+    } else if let Ok(tuple) = input.cast_as::<PyTuple>() {
+        tuple.iter().collect()
+    } else if let Ok(set) = input.cast_as::<PySet>() {
+        set.iter().collect()
+    } else if let Ok(frozenset) = input.cast_as::<PyFrozenSet>() {
+        frozenset.iter().collect()
+    } else {
+        return Ok(None);
+    };
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| match get_validator(index) {
+            Some(validator) => validator.construct(py, item, slots),
+            None => Ok(item.into_py(py)),
+        })
+        .collect::<PyResult<Vec<PyObject>>>()
+        .map(Some)
+}
@@ -4,7 +4,7 @@ use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
 
 use crate::build_tools::{py_error, SchemaDict};
-use crate::errors::{as_internal, context, err_val_error, ErrorKind, InputValue, ValResult};
+use crate::errors::{as_internal, context, err_val_error, ContextValue, ErrorKind, InputValue, ValResult};
 use crate::input::Input;
 
 use super::{BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
@@ -46,13 +46,17 @@ impl BuildValidator for LiteralBuilder {
 #[derive(Debug, Clone)]
 pub struct LiteralSingleStringValidator {
     expected: String,
-    repr: String,
+    // error context is always a list of the expected values, rendered in messages as they'd be written in python
+    expected_context: ContextValue,
 }
 
 impl LiteralSingleStringValidator {
     fn new(expected: String) -> Self {
-        let repr = format!("'{}'", expected);
-        Self { expected, repr }
+        let expected_context = ContextValue::L(vec![expected.as_str().into()], format!("'{}'", expected));
+        Self {
+            expected,
+            expected_context,
+        }
     }
 }
 
@@ -64,14 +68,14 @@ impl Validator for LiteralSingleStringValidator {
         _extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        let str = input.strict_str()?;
-        if str == self.expected {
+        let either_str = input.strict_str()?;
+        if either_str.as_str()? == self.expected {
             Ok(input.to_py(py))
         } else {
             err_val_error!(
                 input_value = InputValue::InputRef(input),
                 kind = ErrorKind::LiteralSingleError,
-                context = context!("expected" => self.repr.clone()),
+                context = context!("expected" => self.expected_context.clone()),
             )
         }
     }
@@ -84,11 +88,16 @@ impl Validator for LiteralSingleStringValidator {
 #[derive(Debug, Clone)]
 pub struct LiteralSingleIntValidator {
     expected: i64,
+    expected_context: ContextValue,
 }
 
 impl LiteralSingleIntValidator {
     fn new(expected: i64) -> Self {
-        Self { expected }
+        let expected_context = ContextValue::L(vec![expected.into()], expected.to_string());
+        Self {
+            expected,
+            expected_context,
+        }
     }
 }
 
@@ -107,7 +116,7 @@ impl Validator for LiteralSingleIntValidator {
             err_val_error!(
                 input_value = InputValue::InputRef(input),
                 kind = ErrorKind::LiteralSingleError,
-                context = context!("expected" => self.expected)
+                context = context!("expected" => self.expected_context.clone())
             )
         }
     }
@@ -120,16 +129,18 @@ impl Validator for LiteralSingleIntValidator {
 #[derive(Debug, Clone)]
 pub struct LiteralMultipleStringsValidator {
     expected: HashSet<String>,
-    repr: String,
+    expected_context: ContextValue,
 }
 
 impl LiteralMultipleStringsValidator {
     fn new(expected_list: &PyList) -> Option<Self> {
         let mut expected: HashSet<String> = HashSet::new();
+        let mut context_items = Vec::new();
         let mut repr_args = Vec::new();
         for item in expected_list.iter() {
-            if let Ok(str) = item.extract() {
+            if let Ok(str) = item.extract::<String>() {
                 repr_args.push(format!("'{}'", str));
+                context_items.push(str.as_str().into());
                 expected.insert(str);
             } else {
                 return None;
@@ -138,7 +149,7 @@ impl LiteralMultipleStringsValidator {
 
         Some(Self {
             expected,
-            repr: repr_args.join(", "),
+            expected_context: ContextValue::L(context_items, repr_args.join(", ")),
         })
     }
 }
@@ -151,14 +162,14 @@ impl Validator for LiteralMultipleStringsValidator {
         _extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        let str = input.strict_str()?;
-        if self.expected.contains(&str) {
+        let either_str = input.strict_str()?;
+        if self.expected.contains(either_str.as_str()?) {
             Ok(input.to_py(py))
         } else {
             err_val_error!(
                 input_value = InputValue::InputRef(input),
                 kind = ErrorKind::LiteralMultipleError,
-                context = context!("expected" => self.repr.clone()),
+                context = context!("expected" => self.expected_context.clone()),
             )
         }
     }
@@ -171,16 +182,18 @@ impl Validator for LiteralMultipleStringsValidator {
 #[derive(Debug, Clone)]
 pub struct LiteralMultipleIntsValidator {
     expected: HashSet<i64>,
-    repr: String,
+    expected_context: ContextValue,
 }
 
 impl LiteralMultipleIntsValidator {
     fn new(expected_list: &PyList) -> Option<Self> {
         let mut expected: HashSet<i64> = HashSet::new();
+        let mut context_items = Vec::new();
         let mut repr_args = Vec::new();
         for item in expected_list.iter() {
-            if let Ok(str) = item.extract() {
+            if let Ok(str) = item.extract::<i64>() {
                 expected.insert(str);
+                context_items.push(str.into());
                 repr_args.push(str.to_string());
             } else {
                 return None;
@@ -189,7 +202,7 @@ impl LiteralMultipleIntsValidator {
 
         Some(Self {
             expected,
-            repr: repr_args.join(", "),
+            expected_context: ContextValue::L(context_items, repr_args.join(", ")),
         })
     }
 }
@@ -209,7 +222,7 @@ impl Validator for LiteralMultipleIntsValidator {
             err_val_error!(
                 input_value = InputValue::InputRef(input),
                 kind = ErrorKind::LiteralMultipleError,
-                context = context!("expected" => self.repr.clone())
+                context = context!("expected" => self.expected_context.clone())
             )
         }
     }
@@ -224,7 +237,7 @@ pub struct LiteralGeneralValidator {
     expected_int: HashSet<i64>,
     expected_str: HashSet<String>,
     expected_py: Py<PyList>,
-    repr: String,
+    expected_context: ContextValue,
 }
 
 impl LiteralGeneralValidator {
@@ -233,8 +246,11 @@ impl LiteralGeneralValidator {
         let mut expected_str = HashSet::new();
         let py = expected.py();
         let expected_py = PyList::empty(py);
+        let mut context_items: Vec<ContextValue> = Vec::new();
         let mut repr_args: Vec<String> = Vec::new();
         for item in expected.iter() {
+            // values other than ints, floats and lists are strings in error context
+            context_items.push(item.extract()?);
             repr_args.push(item.repr()?.extract()?);
             if let Ok(int) = item.extract::<i64>() {
                 expected_int.insert(int);
@@ -248,7 +264,7 @@ impl LiteralGeneralValidator {
             expected_int,
             expected_str,
             expected_py: expected_py.into_py(py),
-            repr: repr_args.join(", "),
+            expected_context: ContextValue::L(context_items, repr_args.join(", ")),
         })
     }
 }
@@ -269,8 +285,8 @@ impl Validator for LiteralGeneralValidator {
             }
         }
         if !self.expected_str.is_empty() {
-            if let Ok(str) = input.strict_str() {
-                if self.expected_str.contains(&str) {
+            if let Ok(either_str) = input.strict_str() {
+                if self.expected_str.contains(either_str.as_str()?) {
                     return Ok(input.to_py(py));
                 }
             }
@@ -284,9 +300,9 @@ impl Validator for LiteralGeneralValidator {
         }
 
         err_val_error!(
-            input_value = InputValue::PyObject(py_value),
+            input_value = InputValue::Owned(py_value),
             kind = ErrorKind::LiteralMultipleError,
-            context = context!("expected" => self.repr.clone())
+            context = context!("expected" => self.expected_context.clone())
         )
     }
 
@@ -294,3 +310,49 @@ impl Validator for LiteralGeneralValidator {
         "literal-general".to_string()
     }
 }
+
+/// The str and int literals of all the choices in a union of literals, so the union can be checked with one lookup
+/// rather than by trying each choice in turn
+#[derive(Debug, Clone, Default)]
+pub struct LiteralLookup {
+    expected_str: HashSet<String>,
+    expected_int: HashSet<i64>,
+}
+
+impl LiteralLookup {
+    /// `None` unless every choice is a str or int literal
+    pub fn from_choices(choices: &[CombinedValidator]) -> Option<Self> {
+        let mut lookup = Self::default();
+        for choice in choices {
+            match choice {
+                CombinedValidator::LiteralSingleString(v) => {
+                    lookup.expected_str.insert(v.expected.clone());
+                }
+                CombinedValidator::LiteralSingleInt(v) => {
+                    lookup.expected_int.insert(v.expected);
+                }
+                CombinedValidator::LiteralMultipleStrings(v) => lookup.expected_str.extend(v.expected.iter().cloned()),
+                CombinedValidator::LiteralMultipleInts(v) => lookup.expected_int.extend(v.expected.iter()),
+                _ => return None,
+            }
+        }
+        Some(lookup)
+    }
+
+    /// Whether any of the choices would accept the input, this uses the same checks as the choices themselves
+    pub fn contains<'data>(&self, input: &'data dyn Input) -> ValResult<'data, bool> {
+        if !self.expected_str.is_empty() {
+            if let Ok(either_str) = input.strict_str() {
+                if self.expected_str.contains(either_str.as_str()?) {
+                    return Ok(true);
+                }
+            }
+        }
+        if !self.expected_int.is_empty() {
+            if let Ok(int) = input.strict_int() {
+                return Ok(self.expected_int.contains(&int));
+            }
+        }
+        Ok(false)
+    }
+}
@@ -0,0 +1,96 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::build_tools::is_strict;
+use crate::errors::{err_val_error, ErrorKind, InputValue, ValResult};
+use crate::input::Input;
+
+use super::{BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
+
+/// Validates EUI-48 and EUI-64 MAC addresses separated by colons (`00:1a:2b:3c:4d:5e`), hyphens
+/// (`00-1A-2B-3C-4D-5E`) or dots (`001a.2b3c.4d5e`), the output is always lower case and colon separated
+#[derive(Debug, Clone)]
+pub struct MacAddressValidator {
+    strict: bool,
+}
+
+impl BuildValidator for MacAddressValidator {
+    const EXPECTED_TYPE: &'static str = "mac-address";
+
+    fn build(
+        schema: &PyDict,
+        config: Option<&PyDict>,
+        _build_context: &mut BuildContext,
+    ) -> PyResult<CombinedValidator> {
+        Ok(Self {
+            strict: is_strict(schema, config)?,
+        }
+        .into())
+    }
+}
+
+impl Validator for MacAddressValidator {
+    fn validate<'data>(
+        &self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        _slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        let either_str = match self.strict {
+            true => input.strict_str()?,
+            false => extra.lax_with_report(py, input, "str", |i| i.strict_str(), |i| i.lax_str())?,
+        };
+        match normalize_mac_address(either_str.as_str()?) {
+            Some(mac_address) => Ok(mac_address.into_py(py)),
+            None => err_val_error!(input_value = InputValue::InputRef(input), kind = ErrorKind::MacAddress),
+        }
+    }
+
+    fn validate_strict<'data>(
+        &self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        _extra: &Extra,
+        _slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        match normalize_mac_address(input.strict_str()?.as_str()?) {
+            Some(mac_address) => Ok(mac_address.into_py(py)),
+            None => err_val_error!(input_value = InputValue::InputRef(input), kind = ErrorKind::MacAddress),
+        }
+    }
+
+    fn get_name(&self, _py: Python) -> String {
+        Self::EXPECTED_TYPE.to_string()
+    }
+}
+
+/// Parse a MAC address in any of the supported forms, returning the lower case colon separated form
+fn normalize_mac_address(mac_address: &str) -> Option<String> {
+    let (separator, group_length) = if mac_address.contains(':') {
+        (':', 2)
+    } else if mac_address.contains('-') {
+        ('-', 2)
+    } else {
+        ('.', 4)
+    };
+
+    let mut hex_digits = String::with_capacity(16);
+    for group in mac_address.split(separator) {
+        if group.len() != group_length || !group.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        hex_digits.push_str(group);
+    }
+    // 12 digits for EUI-48, 16 for EUI-64
+    if hex_digits.len() != 12 && hex_digits.len() != 16 {
+        return None;
+    }
+
+    let hex_digits = hex_digits.to_ascii_lowercase();
+    let pairs: Vec<&str> = (0..hex_digits.len())
+        .step_by(2)
+        .map(|i| &hex_digits[i..i + 2])
+        .collect();
+    Some(pairs.join(":"))
+}
@@ -1,99 +1,553 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Instant;
 
 use enum_dispatch::enum_dispatch;
-use pyo3::exceptions::PyRecursionError;
+use pyo3::conversion::AsPyPointer;
+use pyo3::exceptions::{PyRecursionError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::{PyAny, PyDict};
+use pyo3::types::{PyAny, PyByteArray, PyBytes, PyCFunction, PyDict, PyList, PyString, PyTuple, PyType};
+use pyo3::{ffi, intern};
 use serde_json::from_str as parse_json;
+use smallvec::smallvec;
 
-use crate::build_tools::{py_error, SchemaDict};
+use crate::build_tools::{is_strict, merge_config, py_error, SchemaDict, SchemaKeyError};
 use crate::errors::{
-    as_validation_err, val_line_error, ErrorKind, InputValue, ValError, ValResult,
+    as_internal, as_validation_err, val_line_error, Coercion, CoercionsCollector, ErrorKind, ErrorsCollector,
+    InputValue, Location, ValError, ValResult, ValWarning, WarningCategory, WarningsCollector,
+};
+use crate::input::{
+    parse_json_array_items, parse_json_array_items_from_reader, parse_json_bytes, EitherString, Input, JsonInput,
+    JsonObject, ToLocItem, ToPy,
 };
-use crate::input::{Input, JsonInput};
 use crate::SchemaError;
 
+pub use custom::register_schema_type;
+use env::EnvSource;
+#[cfg(feature = "extensions")]
+pub use extension::{register_validator, DynValidator, ExtensionValidator};
+pub use hooks::set_validation_hooks;
+#[cfg(feature = "extensions")]
+pub use hooks::{set_hooks, ValidationHooks};
+use limits::{DepthGuard, Limits};
+use model::ExtraBehavior;
+use pure::{PureExtra, PureOutput};
+use shared::SharedSchema;
+use state::{RecursionGuard, StatePool, ValidationState};
+
 mod any;
+mod array;
 mod bool;
+mod complex;
+mod conditional;
+mod counted;
+mod custom;
+mod definitions;
 mod dict;
+mod env;
+mod extension;
 mod float;
 mod function;
+mod hooks;
 mod int;
+mod is_instance;
+mod json;
+mod limits;
 mod list;
 mod literal;
+mod mac_address;
 mod model;
 mod model_class;
 mod none;
 mod optional;
+mod parallel;
+mod pure;
 mod recursive;
 mod set;
+mod shared;
+mod state;
 mod string;
+mod timezone;
+mod tuple;
 mod union;
+mod url;
 
 #[pyclass(module = "pydantic_core._pydantic_core")]
 #[derive(Debug, Clone)]
 pub struct SchemaValidator {
-    validator: CombinedValidator,
-    slots: Vec<CombinedValidator>,
+    /// Shared with copies, see `copy`
+    validator: Arc<CombinedValidator>,
+    /// Shared between validators built together by `from_definitions`
+    slots: Arc<Vec<CombinedValidator>>,
     schema: PyObject,
+    /// The schema as it was built, see `core_schema`
+    core_schema: Py<PyDict>,
+    /// Used in `ValidationError`s, see `schema_title`
+    title: String,
+    states: Arc<StatePool>,
+    /// Whether each validator counts how many times it ran, see `debug_repr`
+    counters: bool,
+    /// Set by `copy`
+    overrides: ConfigOverrides,
 }
 
+// validators are never mutated once built, state used during validation (warnings, errors etc.) lives in `Extra`,
+// so one `SchemaValidator` can be used by many threads at once - this makes sure it stays that way
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SchemaValidator>();
+};
+
 #[pymethods]
 impl SchemaValidator {
     #[new]
-    pub fn py_new(py: Python, schema: &PyAny) -> PyResult<Self> {
+    #[args(counters = "false")]
+    pub fn py_new(py: Python, schema: &PyAny, counters: bool) -> PyResult<Self> {
         let mut build_context = BuildContext::new();
+        build_context.counters = counters;
         let validator = match build_validator(schema, None, &mut build_context) {
             Ok((v, _)) => v,
             Err(err) => {
+                let err = build_context.schema_error(py, err);
                 return Err(match err.is_instance_of::<SchemaError>(py) {
                     true => err,
                     false => SchemaError::new_err(format!("Schema build error:\n  {}", err)),
                 });
             }
         };
+        let title = schema_title(py, schema, &validator).map_err(|err| build_context.schema_error(py, err))?;
+        let core_schema = build_context.take_core_schema(schema)?;
         let slots = build_context.into_slots()?;
         Ok(Self {
-            validator,
-            slots,
+            validator: Arc::new(validator),
+            slots: Arc::new(slots),
             schema: schema.into_py(py),
+            core_schema,
+            title,
+            states: Arc::default(),
+            counters,
+            overrides: ConfigOverrides::default(),
         })
     }
 
-    fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
-        let args = (self.schema.as_ref(py),);
-        let cls = Py::new(py, self.to_owned())?.getattr(py, "__class__")?;
-        Ok((cls, args).into_py(py))
+    /// Build a validator for each of a set of named schemas which can reference each other via `recursive-ref`,
+    /// each definition is compiled once and shared by all the validators returned
+    #[staticmethod]
+    pub fn from_definitions(py: Python, definitions: &PyDict) -> PyResult<Py<PyDict>> {
+        let mut build_context = BuildContext::new();
+        build_context.add_definitions(definitions, None)?;
+        let mut validators: Vec<(&PyAny, CombinedValidator, String)> = Vec::with_capacity(definitions.len());
+        for name in definitions.keys() {
+            let id = build_context.find_id(name.extract()?)?;
+            let validator = build_context.get_slot(id)?;
+            let title = schema_title(py, definitions.get_item(name).unwrap_or(name), &validator)
+                .map_err(|err| build_context.schema_error(py, err))?;
+            validators.push((name, validator, title));
+        }
+        let core_definitions = build_context.with_core_schemas(definitions)?;
+        let slots = Arc::new(build_context.into_slots()?);
+
+        let output = PyDict::new(py);
+        for (name, validator, title) in validators {
+            // when pickled, the validator is rebuilt from all the definitions with a reference to this one
+            let schema = PyDict::new(py);
+            schema.set_item("type", "definitions")?;
+            schema.set_item("title", &title)?;
+            schema.set_item("definitions", definitions)?;
+            let ref_schema = PyDict::new(py);
+            ref_schema.set_item("type", "recursive-ref")?;
+            ref_schema.set_item("name", name)?;
+            schema.set_item("schema", ref_schema)?;
+            let core_schema = schema.copy()?;
+            core_schema.set_item("definitions", &core_definitions)?;
+
+            let schema_validator = Self {
+                validator: Arc::new(validator),
+                slots: slots.clone(),
+                schema: schema.into_py(py),
+                core_schema: core_schema.into(),
+                title,
+                states: Arc::default(),
+                counters: false,
+                overrides: ConfigOverrides::default(),
+            };
+            output.set_item(name, Py::new(py, schema_validator)?)?;
+        }
+        Ok(output.into())
+    }
+
+    fn __reduce__(&self, py: Python) -> PyObject {
+        // the validator is rebuilt from the original schema when unpickled, then `__setstate__` restores anything
+        // changed by `copy`
+        let args = (self.schema.as_ref(py), self.counters);
+        let extra_behavior = self.overrides.extra_behavior.map(|b| b.name());
+        let state = (&self.title, self.overrides.strict, extra_behavior);
+        (py.get_type::<Self>(), args, state).into_py(py)
     }
 
-    fn validate_python(&self, py: Python, input: &PyAny) -> PyResult<PyObject> {
-        let extra = Extra {
-            data: None,
-            field: None,
+    fn __setstate__(&mut self, state: (String, bool, Option<&str>)) -> PyResult<()> {
+        let (title, strict, extra_behavior) = state;
+        self.title = title;
+        self.overrides = ConfigOverrides {
+            strict,
+            extra_behavior: extra_behavior.map(ExtraBehavior::from_name).transpose()?,
         };
-        let r = self.validator.validate(py, input, &extra, &self.slots);
-        r.map_err(|e| as_validation_err(py, &self.validator.get_name(py), e))
+        Ok(())
     }
 
-    fn validate_json(&self, py: Python, input: String) -> PyResult<PyObject> {
-        match parse_json::<JsonInput>(&input) {
-            Ok(input) => {
-                let extra = Extra {
-                    data: None,
-                    field: None,
-                };
-                let r = self.validator.validate(py, &input, &extra, &self.slots);
-                r.map_err(|e| as_validation_err(py, &self.validator.get_name(py), e))
-            }
-            Err(e) => {
+    /// A copy of this validator which shares its built validators, with the title used in errors or some config
+    /// overridden, e.g. so a strict and a lax variant of a model don't require two builds, see `ConfigOverrides`
+    #[args(strict = "None", title = "None", extra_behavior = "None")]
+    fn copy(&self, strict: Option<bool>, title: Option<String>, extra_behavior: Option<&str>) -> PyResult<Self> {
+        let mut copy = self.clone();
+        if let Some(strict) = strict {
+            copy.overrides.strict = strict;
+        }
+        if let Some(title) = title {
+            copy.title = title;
+        }
+        if let Some(extra_behavior) = extra_behavior {
+            copy.overrides.extra_behavior = Some(ExtraBehavior::from_name(extra_behavior)?);
+        }
+        Ok(copy)
+    }
+
+    /// The schema as it was actually built, e.g. for tooling which generates JSON Schema, see the `core_schema`
+    /// function; building a validator from it gives an equivalent validator
+    fn core_schema(&self, py: Python) -> Py<PyDict> {
+        self.core_schema.clone_ref(py)
+    }
+
+    #[args(
+        warnings = "None",
+        context = "None",
+        collect_errors = "false",
+        report_coercions = "false",
+        limits = "None",
+        by_alias = "true",
+        by_name = "false"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    fn validate_python(
+        &self,
+        py: Python,
+        input: &PyAny,
+        warnings: Option<&str>,
+        context: Option<&PyAny>,
+        collect_errors: bool,
+        report_coercions: bool,
+        limits: Option<&PyDict>,
+        by_alias: bool,
+        by_name: bool,
+    ) -> PyResult<PyObject> {
+        let options = RunOptions {
+            warnings,
+            context,
+            collect_errors,
+            report_coercions,
+            event_loop: None,
+            limits,
+            by_alias,
+            by_name,
+        };
+        self.run_validation(py, options, |extra| {
+            self.validator.validate(py, input, extra, &self.slots)
+        })
+    }
+
+    /// If `return_input` is true, a tuple of `(value, parsed)` is returned where `parsed` is the JSON as parsed
+    /// before validation, e.g. so callers can show the part of the input which is invalid.
+    /// The GIL is released while the JSON is parsed, and while it's validated unless a validator needs python, e.g.
+    /// functions, or warnings, errors or coercions are collected or limits are set
+    #[args(
+        warnings = "None",
+        context = "None",
+        collect_errors = "false",
+        report_coercions = "false",
+        limits = "None",
+        by_alias = "true",
+        by_name = "false",
+        return_input = "false"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    fn validate_json(
+        &self,
+        py: Python,
+        input: &PyAny,
+        warnings: Option<&str>,
+        context: Option<&PyAny>,
+        collect_errors: bool,
+        report_coercions: bool,
+        limits: Option<&PyDict>,
+        by_alias: bool,
+        by_name: bool,
+        return_input: bool,
+    ) -> PyResult<PyObject> {
+        let options = RunOptions {
+            warnings,
+            context,
+            collect_errors,
+            report_coercions,
+            event_loop: None,
+            limits,
+            by_alias,
+            by_name,
+        };
+        let parsed = parse_json_input(py, input)?;
+        let value = self.run_validation(py, options, |extra| match parsed {
+            Ok(ref json_input) => self.validate_json_input(py, json_input, extra),
+            Err(ref e) => {
                 let line_err = val_line_error!(
-                    input_value = InputValue::InputRef(&input),
+                    input_value = InputValue::InputRef(input),
                     message = Some(e.to_string()),
                     kind = ErrorKind::InvalidJson
                 );
-                let err = ValError::LineErrors(vec![line_err]);
-                Err(as_validation_err(py, &self.validator.get_name(py), err))
+                Err(ValError::LineErrors(vec![line_err]))
+            }
+        })?;
+        match (return_input, parsed) {
+            (false, _) => Ok(value),
+            (true, Ok(json_input)) => Ok((value, json_input.to_py(py)).into_py(py)),
+            // only reachable with `collect_errors`
+            (true, Err(_)) => Ok((value, py.None()).into_py(py)),
+        }
+    }
+
+    /// Validate each item of a JSON array with this schema as the array is parsed, so neither the parsed array nor
+    /// the output is held in memory in full, e.g. to ingest huge JSON files. `input` may be str, bytes, bytearray,
+    /// memoryview or a binary file-like object, which is read in chunks as it's parsed so the document isn't held in
+    /// memory either. Items are passed to `callback` in order: `callback(item, None)` for each valid item and
+    /// `callback(None, error)` with a `ValidationError` located by index for each invalid item; the number of
+    /// invalid items is returned.
+    /// Invalid JSON raises a `ValidationError` once the items before it have been passed to `callback`
+    #[args(context = "None")]
+    fn validate_json_items(
+        &self,
+        py: Python,
+        input: &PyAny,
+        callback: &PyAny,
+        context: Option<&PyAny>,
+    ) -> PyResult<usize> {
+        self.with_state(|state| {
+            let extra = Extra {
+                context,
+                state: Some(state),
+                overrides: self.overrides,
+                ..Default::default()
+            };
+            let mut index = 0;
+            let mut invalid = 0;
+            let mut on_item = |item: JsonInput| {
+                let args = match self.validator.validate(py, &item, &extra, &self.slots) {
+                    Ok(value) => (value, py.None()),
+                    Err(ValError::LineErrors(line_errors)) => {
+                        invalid += 1;
+                        let loc: Location = smallvec![index.to_loc()];
+                        let line_errors = line_errors.into_iter().map(|e| e.with_prefix_location(&loc)).collect();
+                        let err = as_validation_err(py, &self.title, ValError::LineErrors(line_errors));
+                        (py.None(), err.into_py(py))
+                    }
+                    Err(ValError::InternalErr(err)) => return Err(err),
+                };
+                index += 1;
+                callback.call1(args).map(drop)
+            };
+            let parsed = match input.hasattr(intern!(py, "read"))? {
+                true => {
+                    let mut reader = PyFileReader {
+                        file: input,
+                        error: None,
+                    };
+                    let parsed = parse_json_array_items_from_reader(&mut reader, &mut on_item)?;
+                    // an exception from `read` is raised as it is rather than as invalid JSON
+                    if let Some(err) = reader.error {
+                        return Err(err);
+                    }
+                    parsed
+                }
+                false => parse_json_array_items(&json_input_bytes(py, input)?, &mut on_item)?,
+            };
+            match parsed {
+                Ok(()) => Ok(invalid),
+                Err(e) => {
+                    let line_err = val_line_error!(
+                        input_value = InputValue::InputRef(input),
+                        message = Some(e),
+                        kind = ErrorKind::InvalidJson
+                    );
+                    Err(as_validation_err(py, &self.title, ValError::LineErrors(vec![line_err])))
+                }
             }
+        })
+    }
+
+    /// Validate data where every value is a string, e.g. environment variables, query parameters and form data;
+    /// the input may be a `str` or a (nested) `dict` or `list` of strings, strings are coerced like JSON strings,
+    /// so validators which are strict still reject them for any type but `str`
+    #[args(
+        warnings = "None",
+        context = "None",
+        collect_errors = "false",
+        report_coercions = "false",
+        limits = "None",
+        by_alias = "true",
+        by_name = "false"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    fn validate_strings(
+        &self,
+        py: Python,
+        input: &PyAny,
+        warnings: Option<&str>,
+        context: Option<&PyAny>,
+        collect_errors: bool,
+        report_coercions: bool,
+        limits: Option<&PyDict>,
+        by_alias: bool,
+        by_name: bool,
+    ) -> PyResult<PyObject> {
+        let options = RunOptions {
+            warnings,
+            context,
+            collect_errors,
+            report_coercions,
+            event_loop: None,
+            limits,
+            by_alias,
+            by_name,
+        };
+        let strings_input = strings_as_json(input)?;
+        self.run_validation(py, options, |extra| {
+            self.validator.validate(py, &strings_input, extra, &self.slots)
+        })
+    }
+
+    /// Validate a model from environment variables, e.g. `os.environ`: the mapping of variable names to values is
+    /// converted to input for the model (see `EnvSource`) which is validated like `validate_strings`
+    #[args(
+        env_prefix = "None",
+        env_nested_delimiter = "None",
+        case_sensitive = "false",
+        secrets_dir = "None",
+        warnings = "None",
+        context = "None",
+        collect_errors = "false",
+        report_coercions = "false",
+        limits = "None"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    fn validate_env(
+        &self,
+        py: Python,
+        input: &PyAny,
+        env_prefix: Option<&str>,
+        env_nested_delimiter: Option<&str>,
+        case_sensitive: bool,
+        secrets_dir: Option<PathBuf>,
+        warnings: Option<&str>,
+        context: Option<&PyAny>,
+        collect_errors: bool,
+        report_coercions: bool,
+        limits: Option<&PyDict>,
+    ) -> PyResult<PyObject> {
+        let options = RunOptions {
+            warnings,
+            context,
+            collect_errors,
+            report_coercions,
+            event_loop: None,
+            limits,
+            by_alias: true,
+            by_name: false,
+        };
+        let env_source = EnvSource::new(
+            env_prefix.unwrap_or_default(),
+            env_nested_delimiter,
+            case_sensitive,
+            secrets_dir,
+        )?;
+        let env_input = env_source.model_input(&self.validator, &self.slots, input)?;
+        self.run_validation(py, options, |extra| {
+            self.validator.validate(py, &env_input, extra, &self.slots)
+        })
+    }
+
+    /// Like `validate_python` but validator functions may be coroutine functions, validation runs in the event
+    /// loop's default executor and coroutines are awaited on the running event loop, the thread waits for them
+    #[args(
+        warnings = "None",
+        context = "None",
+        collect_errors = "false",
+        report_coercions = "false",
+        limits = "None",
+        by_alias = "true",
+        by_name = "false"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    fn validate_python_async(
+        &self,
+        py: Python,
+        input: PyObject,
+        warnings: Option<String>,
+        context: Option<PyObject>,
+        collect_errors: bool,
+        report_coercions: bool,
+        limits: Option<Py<PyDict>>,
+        by_alias: bool,
+        by_name: bool,
+    ) -> PyResult<PyObject> {
+        let event_loop = py.import("asyncio")?.call_method0("get_running_loop")?;
+        let loop_obj: PyObject = event_loop.into();
+        let validator = self.clone();
+        let run = move |args: &PyTuple, _kwargs: Option<&PyDict>| {
+            let py = args.py();
+            let options = RunOptions {
+                warnings: warnings.as_deref(),
+                context: context.as_ref().map(|c| c.as_ref(py)),
+                collect_errors,
+                report_coercions,
+                event_loop: Some(loop_obj.as_ref(py)),
+                limits: limits.as_ref().map(|l| l.as_ref(py)),
+                by_alias,
+                by_name,
+            };
+            validator.run_validation(py, options, |extra| {
+                validator
+                    .validator
+                    .validate(py, input.as_ref(py), extra, &validator.slots)
+            })
+        };
+        let run = PyCFunction::new_closure(run, py)?;
+        Ok(event_loop.call_method1("run_in_executor", (py.None(), run))?.into())
+    }
+
+    fn construct(&self, py: Python, input: &PyAny) -> PyResult<PyObject> {
+        self.validator.construct(py, input, &self.slots)
+    }
+
+    /// Whether `input` is valid, without building the output of models, model classes and containers; the
+    /// fields of models are still built since validators of later fields may read them via `data`, and so are
+    /// the values passed to `after` and `wrap` functions
+    fn isinstance_python(&self, py: Python, input: &PyAny) -> PyResult<bool> {
+        self.with_state(|state| {
+            let extra = self.check_only_extra(state);
+            is_valid(self.validator.validate(py, input, &extra, &self.slots))
+        })
+    }
+
+    /// Like `isinstance_python` for a JSON string
+    fn isinstance_json(&self, py: Python, input: &PyAny) -> PyResult<bool> {
+        match parse_json_input(py, input)? {
+            Ok(input) => self.with_state(|state| {
+                let extra = self.check_only_extra(state);
+                is_valid(self.validator.validate(py, &input, &extra, &self.slots))
+            }),
+            Err(_) => Ok(false),
         }
     }
 
@@ -104,12 +558,24 @@ impl SchemaValidator {
         input: &PyAny,
         data: &PyDict,
     ) -> PyResult<PyObject> {
-        let extra = Extra {
-            data: Some(data),
-            field: Some(field.as_str()),
-        };
-        let r = self.validator.validate(py, input, &extra, &self.slots);
-        r.map_err(|e| as_validation_err(py, &self.validator.get_name(py), e))
+        self.with_state(|state| {
+            let extra = Extra {
+                data: Some(data),
+                field: Some(field.as_str()),
+                warnings: None,
+                context: None,
+                collected_errors: None,
+                coercions: None,
+                event_loop: None,
+                limits: None,
+                field_lookup: FieldLookup::Alias,
+                state: Some(state),
+                overrides: self.overrides,
+                check_only: false,
+            };
+            let r = self.validator.validate(py, input, &extra, &self.slots);
+            r.map_err(|e| as_validation_err(py, &self.title, e))
+        })
     }
 
     fn __repr__(&self, py: Python) -> String {
@@ -119,6 +585,285 @@ impl SchemaValidator {
             self.validator
         )
     }
+
+    /// Like `__repr__` but also shows the validators referenced by `recursive-ref`s, by slot id, and if built with
+    /// `counters=True`, how many times each validator has run
+    fn debug_repr(&self, py: Python) -> String {
+        let mut output = self.__repr__(py);
+        for (id, slot) in self.slots.iter().enumerate() {
+            output.push_str(&format!("\nslot {}: {:#?}", id, slot));
+        }
+        output
+    }
+}
+
+impl SchemaValidator {
+    /// Validate parsed JSON with the GIL released if no validator needs python, see `Validator::validate_pure`,
+    /// otherwise (or if the input is invalid) it's validated as usual with the GIL held
+    fn validate_json_input<'data>(
+        &'data self,
+        py: Python<'data>,
+        input: &'data JsonInput,
+        extra: &Extra,
+    ) -> ValResult<'data, PyObject> {
+        if let Some(pure_extra) = PureExtra::from_extra(extra) {
+            let (validator, slots) = (&self.validator, self.slots.as_slice());
+            if let Some(output) = py.allow_threads(|| validator.validate_pure(input, pure_extra, slots)) {
+                return output.into_python(py).map_err(as_internal);
+            }
+        }
+        self.validator.validate(py, input, extra, &self.slots)
+    }
+
+    fn extra_with_state<'a>(&self, state: &'a Rc<ValidationState>) -> Extra<'a> {
+        Extra {
+            overrides: self.overrides,
+            ..Extra::with_state(state)
+        }
+    }
+
+    fn check_only_extra<'a>(&self, state: &'a Rc<ValidationState>) -> Extra<'a> {
+        Extra {
+            check_only: true,
+            ..self.extra_with_state(state)
+        }
+    }
+
+    /// Call `f` with a `ValidationState` from the pool, it's returned to the pool afterwards unless python still
+    /// holds a reference to it via the `validator` argument of a wrap function
+    fn with_state<T>(&self, f: impl FnOnce(&Rc<ValidationState>) -> T) -> T {
+        let state = Rc::new(self.states.take());
+        let output = f(&state);
+        if let Ok(state) = Rc::try_unwrap(state) {
+            self.states.give_back(state);
+        }
+        output
+    }
+
+    /// Run validation with the given options, see `RunOptions`, each output requested in addition to the value
+    /// wraps the result in a tuple, e.g. `((value, errors), warnings)`
+    fn run_validation<'data, F>(&self, py: Python, options: RunOptions, validate: F) -> PyResult<PyObject>
+    where
+        F: FnOnce(&Extra) -> ValResult<'data, PyObject>,
+    {
+        self.with_state(|state| self.run_with_state(py, options, state, validate))
+    }
+
+    fn run_with_state<'data, F>(
+        &self,
+        py: Python,
+        options: RunOptions,
+        state: &Rc<ValidationState>,
+        validate: F,
+    ) -> PyResult<PyObject>
+    where
+        F: FnOnce(&Extra) -> ValResult<'data, PyObject>,
+    {
+        let (warnings, errors, coercions) = (&state.warnings, &state.errors, &state.coercions);
+        if let Some(mode) = options.warnings {
+            if mode != "collect" && mode != "warn" {
+                return py_error!(PyValueError; "Invalid warnings mode: \"{}\"", mode);
+            }
+        }
+        let limits = match options.limits {
+            Some(limits) => Some(Rc::new(Limits::new(limits)?)),
+            None => None,
+        };
+        let field_lookup = match (options.by_alias, options.by_name) {
+            (true, false) => FieldLookup::Alias,
+            (false, true) => FieldLookup::Name,
+            (true, true) => FieldLookup::AliasThenName,
+            (false, false) => return py_error!(PyValueError; "At least one of by_alias and by_name must be true"),
+        };
+        let extra = Extra {
+            warnings: options.warnings.map(|_| warnings),
+            context: options.context,
+            collected_errors: match options.collect_errors {
+                true => Some(errors),
+                false => None,
+            },
+            coercions: match options.report_coercions {
+                true => Some(coercions),
+                false => None,
+            },
+            event_loop: options.event_loop,
+            limits: limits.as_ref(),
+            field_lookup,
+            state: Some(state),
+            overrides: self.overrides,
+            ..Default::default()
+        };
+        let hooks = hooks::get_hooks();
+        if let Some(ref hooks) = hooks {
+            hooks.on_start(py, &self.title)?;
+        }
+        let start = Instant::now();
+        let mut result = validate(&extra);
+        let total_exceeded = limits.as_ref().and_then(|limits| limits.total_exceeded());
+        let collect_errors = options.collect_errors && total_exceeded.is_none();
+        if let Some(err) = total_exceeded {
+            // the errors found before the limit was exceeded, and any collected errors, are discarded
+            result = Err(err);
+        }
+        if let Some(ref hooks) = hooks {
+            let error_count = match result {
+                Err(ValError::LineErrors(ref line_errors)) => errors.checkpoint() + line_errors.len(),
+                _ => errors.checkpoint(),
+            };
+            hooks.on_end(py, &self.title, start.elapsed(), error_count)?;
+        }
+        let mut value = match (result, collect_errors) {
+            (Ok(value), false) => value,
+            (Ok(value), true) => (value, errors.as_list(py)?).into_py(py),
+            (Err(ValError::LineErrors(line_errors)), true) => {
+                errors.extend(py, line_errors);
+                (py.None(), errors.as_list(py)?).into_py(py)
+            }
+            (Err(err), _) => return Err(as_validation_err(py, &self.title, err)),
+        };
+        if options.report_coercions {
+            value = (value, coercions.as_list(py)?).into_py(py);
+        }
+        match options.warnings {
+            None => Ok(value),
+            Some("collect") => Ok((value, warnings.as_list(py)?).into_py(py)),
+            // "warn", the mode is checked before validating
+            Some(_) => {
+                warnings.emit(py)?;
+                Ok(value)
+            }
+        }
+    }
+}
+
+/// The `title` of the schema if it's set, otherwise the name of the validator
+fn schema_title(py: Python, schema: &PyAny, validator: &CombinedValidator) -> PyResult<String> {
+    let title: Option<String> = match schema.cast_as::<PyDict>() {
+        Ok(schema_dict) => schema_dict.get_as("title")?,
+        Err(_) => None,
+    };
+    Ok(title.unwrap_or_else(|| validator.get_name(py)))
+}
+
+/// Options for `validate_python` and `validate_json`
+struct RunOptions<'a> {
+    /// How warnings are reported:
+    /// * `None` - warnings are not collected
+    /// * `"collect"` - a tuple of `(value, warnings)` is returned
+    /// * `"warn"` - warnings are emitted as python `UserWarning`s
+    warnings: Option<&'a str>,
+    /// Passed to validator functions
+    context: Option<&'a PyAny>,
+    /// If true, models drop invalid fields instead of failing and a tuple of `(value, errors)` is returned
+    collect_errors: bool,
+    /// If true, a tuple of `(value, coercions)` is returned listing all lax mode coercions
+    report_coercions: bool,
+    /// Set by `validate_python_async`, see `Extra.event_loop`
+    event_loop: Option<&'a PyAny>,
+    /// See `Limits`
+    limits: Option<&'a PyDict>,
+    /// Whether model fields are looked up by alias, by name or both, see `FieldLookup`
+    by_alias: bool,
+    by_name: bool,
+}
+
+/// Parse the input to `validate_json` or `isinstance_json`, which may be `str` or bytes-like, with the GIL released
+/// since parsing is pure rust so other threads can run meanwhile
+fn parse_json_input(py: Python, input: &PyAny) -> PyResult<Result<JsonInput, String>> {
+    if let Ok(py_str) = input.cast_as::<PyString>() {
+        let json_str = py_str.to_str()?;
+        return Ok(py.allow_threads(|| parse_json::<JsonInput>(json_str).map_err(|e| e.to_string())));
+    }
+    let bytes = json_input_bytes(py, input)?;
+    Ok(py.allow_threads(|| parse_json_bytes(&bytes)))
+}
+
+/// The bytes of JSON input which may be `str` or bytes-like
+fn json_input_bytes<'a>(py: Python, input: &'a PyAny) -> PyResult<Cow<'a, [u8]>> {
+    let bytes: Cow<[u8]> = if let Ok(py_str) = input.cast_as::<PyString>() {
+        Cow::Borrowed(py_str.to_str()?.as_bytes())
+    } else if let Ok(py_bytes) = input.cast_as::<PyBytes>() {
+        Cow::Borrowed(py_bytes.as_bytes())
+    } else if let Ok(py_bytearray) = input.cast_as::<PyByteArray>() {
+        // copied since another thread could modify the bytearray while the GIL is released
+        Cow::Owned(py_bytearray.to_vec())
+    } else if unsafe { ffi::PyMemoryView_Check(input.as_ptr()) } == 1 {
+        let py_bytes: &PyBytes = input.call_method0(intern!(py, "tobytes"))?.cast_as()?;
+        Cow::Borrowed(py_bytes.as_bytes())
+    } else {
+        let type_name = input.get_type().name()?;
+        return py_error!(PyTypeError; "JSON input should be str, bytes, bytearray or memoryview, got {}", type_name);
+    };
+    Ok(bytes)
+}
+
+/// Reads a binary file-like object with its `read` method, an exception raised by `read` is kept in `error`
+struct PyFileReader<'py> {
+    file: &'py PyAny,
+    error: Option<PyErr>,
+}
+
+impl Read for PyFileReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let py = self.file.py();
+        let chunk = self
+            .file
+            .call_method1(intern!(py, "read"), (buf.len(),))
+            .and_then(|chunk| match chunk.cast_as::<PyBytes>() {
+                Ok(chunk) if chunk.as_bytes().len() <= buf.len() => Ok(chunk.as_bytes()),
+                Ok(_) => py_error!(PyValueError; "read() returned more bytes than requested"),
+                Err(_) => {
+                    let type_name = chunk.get_type().name()?;
+                    py_error!(PyTypeError; "JSON files should be opened in binary mode, read() returned {}", type_name)
+                }
+            });
+        match chunk {
+            Ok(chunk) => {
+                buf[..chunk.len()].copy_from_slice(chunk);
+                Ok(chunk.len())
+            }
+            Err(err) => {
+                self.error = Some(err);
+                // the io error is never seen, `error` is raised instead
+                Err(io::ErrorKind::Other.into())
+            }
+        }
+    }
+}
+
+/// Convert the input to `validate_strings` to JSON input with string leaves, so it's validated as if it was JSON
+fn strings_as_json(input: &PyAny) -> PyResult<JsonInput> {
+    if let Ok(py_str) = input.cast_as::<PyString>() {
+        Ok(JsonInput::String(py_str.to_str()?.to_string()))
+    } else if let Ok(dict) = input.cast_as::<PyDict>() {
+        let mut object = JsonObject::with_capacity(dict.len());
+        for (key, value) in dict.iter() {
+            let key: &PyString = match key.cast_as() {
+                Ok(key) => key,
+                Err(_) => {
+                    let type_name = key.get_type().name()?;
+                    return py_error!(PyTypeError; "validate_strings dict keys should be str, got {}", type_name);
+                }
+            };
+            object.insert(key.to_str()?.to_string(), strings_as_json(value)?);
+        }
+        Ok(JsonInput::Object(object))
+    } else if let Ok(list) = input.cast_as::<PyList>() {
+        let array = list.iter().map(strings_as_json).collect::<PyResult<_>>()?;
+        Ok(JsonInput::Array(array))
+    } else {
+        let type_name = input.get_type().name()?;
+        py_error!(PyTypeError; "validate_strings input should be str, or a dict or list of str, got {}", type_name)
+    }
+}
+
+/// Convert the result of validation to a bool without creating a `ValidationError`, internal errors are still raised
+fn is_valid(result: ValResult<PyObject>) -> PyResult<bool> {
+    match result {
+        Ok(_) => Ok(true),
+        Err(ValError::LineErrors(_)) => Ok(false),
+        Err(ValError::InternalErr(err)) => Err(err),
+    }
 }
 
 pub trait BuildValidator: Sized {
@@ -141,6 +886,7 @@ macro_rules! validator_match {
                 <$validator>::EXPECTED_TYPE => {
                     $build_context.incr_check_depth()?;
                     let val = <$validator>::build($dict, $config, $build_context).map_err(|err| {
+                        let err = $build_context.schema_error($dict.py(), err);
                         crate::SchemaError::new_err(format!("Error building \"{}\" validator:\n  {}", $type, err))
                     })?;
                     $build_context.decr_depth();
@@ -148,7 +894,25 @@ macro_rules! validator_match {
                 },
             )+
             _ => {
-                return py_error!(r#"Unknown schema type: "{}""#, $type)
+                $build_context.incr_check_depth()?;
+                // types registered by other crates, then from python
+                let custom = self::extension::build_extension_type($type, $dict, $config, $build_context)
+                    .and_then(|extension| match extension {
+                        Some(val) => Ok(Some(val)),
+                        None => self::custom::build_custom_type($dict.py(), $type, $dict, $config, $build_context),
+                    })
+                    .map_err(|err| {
+                        let err = $build_context.schema_error($dict.py(), err);
+                        crate::SchemaError::new_err(format!("Error building \"{}\" validator:\n  {}", $type, err))
+                    })?;
+                $build_context.decr_depth();
+                match custom {
+                    Some(val) => Ok((val, $dict)),
+                    None => {
+                        let expected = [$(format!("\"{}\"", <$validator>::EXPECTED_TYPE),)+];
+                        py_error!(r#"Unknown schema type: "{}", expected one of: {}"#, $type, expected.join(", "))
+                    }
+                }
             },
         }
     };
@@ -158,17 +922,70 @@ pub fn build_validator<'a>(
     schema: &'a PyAny,
     config: Option<&'a PyDict>,
     build_context: &mut BuildContext,
+) -> PyResult<(CombinedValidator, &'a PyDict)> {
+    // sub-schemas built by this schema's validator add their core schemas to this frame
+    build_context.core_schemas.push(HashMap::new());
+    build_context.schema_stack.push(schema.into_py(schema.py()));
+    let output = build_validator_inner(schema, config, build_context)
+        .map_err(|err| build_context.schema_error(schema.py(), err));
+    build_context.schema_stack.pop();
+    let children = build_context.core_schemas.pop().unwrap_or_default();
+    let shared_first = build_context.shared_first.take();
+    let shared_recorded = std::mem::take(&mut build_context.shared_recorded);
+    let delegated_to = build_context.delegated_to.take();
+    let (validator, dict) = output?;
+
+    let py = schema.py();
+    let shared_core_schema = shared_first.and_then(|first| build_context.shared_core_schemas.get(&first));
+    let delegated_core_schema = delegated_to
+        .and_then(|address| children.get(&address))
+        .map(|(_, core_schema)| core_schema);
+    let core_schema = match shared_core_schema.or(delegated_core_schema) {
+        Some(core_schema) => core_schema.clone_ref(py),
+        None => core_schema(dict, config, &children)?,
+    };
+    if shared_recorded {
+        let address = dict.as_ptr() as usize;
+        build_context
+            .shared_core_schemas
+            .insert(address, core_schema.clone_ref(py));
+    }
+    if let Some(frame) = build_context.core_schemas.last_mut() {
+        frame.insert(schema.as_ptr() as usize, (schema.into_py(py), core_schema));
+    }
+    Ok((validator, dict))
+}
+
+fn build_validator_inner<'a>(
+    schema: &'a PyAny,
+    config: Option<&'a PyDict>,
+    build_context: &mut BuildContext,
 ) -> PyResult<(CombinedValidator, &'a PyDict)> {
     let dict: &PyDict = match schema.cast_as() {
         Ok(s) => s,
-        Err(_) => {
+        Err(_) if schema.cast_as::<PyString>().is_ok() => {
             let dict = PyDict::new(schema.py());
             dict.set_item("type", schema)?;
             dict
         }
+        Err(_) if schema.cast_as::<PyType>().is_ok() => return build_class_schema(schema, config, build_context),
+        Err(_) => {
+            let type_name = schema.get_type().name()?;
+            return py_error!("Schema must be a dict or a string, got {}", type_name);
+        }
     };
     let type_: &str = dict.get_as_req("type")?;
-    validator_match!(
+    let shared_key = match build_context.counters {
+        // counts are per schema
+        true => None,
+        false => self::shared::schema_key(dict, config)?,
+    };
+    if let Some(ref key) = shared_key {
+        if let Some(validator) = build_context.shared_validator(key, dict)? {
+            return Ok((validator, dict));
+        }
+    }
+    let (validator, dict) = validator_match!(
         type_,
         dict,
         config,
@@ -189,10 +1006,14 @@ pub fn build_validator<'a>(
         self::bool::BoolValidator,
         // floats
         self::float::FloatValidator,
+        // complex numbers
+        self::complex::ComplexValidator,
         // list/arrays
         self::list::ListValidator,
         // sets - unique lists
         self::set::SetValidator,
+        // tuples, fixed leading items and/or variable length
+        self::tuple::TupleValidator,
         // dicts/objects (recursive)
         self::dict::DictValidator,
         // None/null
@@ -206,18 +1027,374 @@ pub fn build_validator<'a>(
         self::literal::LiteralBuilder,
         // any
         self::any::AnyValidator,
-    )
+        // json strings
+        self::json::JsonValidator,
+        // if/then/else
+        self::conditional::ConditionalValidator,
+        // named definitions, builds the inner validator
+        self::definitions::DefinitionsBuilder,
+        // IANA timezone names
+        self::timezone::TimezoneNameValidator,
+        // MAC addresses
+        self::mac_address::MacAddressValidator,
+        // URLs with multiple hosts
+        self::url::MultiHostUrlValidator,
+        // isinstance/issubclass checks
+        self::is_instance::IsInstanceValidator,
+        self::is_instance::IsSubclassValidator,
+    )?;
+    if let Some(key) = shared_key {
+        build_context.record_shared(key, dict, &validator);
+    }
+    match build_context.counters {
+        true => Ok((self::counted::CountedValidator::wrap(validator), dict)),
+        false => Ok((validator, dict)),
+    }
+}
+
+/// Classes can be used in place of a schema by defining a `__get_pydantic_core_schema__` class method,
+/// it's called without arguments and the schema it returns is built in place of the class
+fn build_class_schema<'a>(
+    class: &'a PyAny,
+    config: Option<&'a PyDict>,
+    build_context: &mut BuildContext,
+) -> PyResult<(CombinedValidator, &'a PyDict)> {
+    let py = class.py();
+    let get_schema = match class.getattr(intern!(py, "__get_pydantic_core_schema__")) {
+        Ok(get_schema) => get_schema,
+        Err(_) => {
+            let class_name = class.getattr(intern!(py, "__name__"))?;
+            return py_error!(
+                "Class {} must define __get_pydantic_core_schema__ to be used as a schema",
+                class_name
+            );
+        }
+    };
+    let core_schema = get_schema.call0()?;
+    // the returned schema may reference the class itself, this stops that recursing forever
+    build_context.incr_check_depth()?;
+    let output = build_validator(core_schema, config, build_context);
+    build_context.decr_depth();
+    build_context.delegate_core_schema(core_schema);
+    output
+}
+
+/// The core schemas of the sub-schemas built while building a schema, by the address of the sub-schema; sub-schemas
+/// are kept so their addresses can't be reused by other objects while the schema is built
+type CoreSchemas = HashMap<usize, (PyObject, Py<PyDict>)>;
+
+/// A schema as it was built: schemas given as strings or classes are replaced by the dict built, as are all its
+/// sub-schemas, a `model`'s `config` is merged with the config it inherits, and `strict` is set from the config
+/// where the schema doesn't set it; `recursive-ref`s are kept since the schemas they reference may be recursive
+fn core_schema(schema: &PyDict, config: Option<&PyDict>, children: &CoreSchemas) -> PyResult<Py<PyDict>> {
+    let py = schema.py();
+    let core_schema: &PyDict = with_core_schemas(schema, children, 0)?.into_ref(py).cast_as()?;
+    let mut config = config;
+    if schema.get_as::<&str>("type")? == Some(self::model::ModelValidator::EXPECTED_TYPE) {
+        config = merge_config(config, schema.get_as("config")?)?;
+        if let Some(config) = config {
+            core_schema.set_item("config", config)?;
+        }
+    }
+    if !core_schema.contains("strict")? && is_strict(schema, config)? {
+        core_schema.set_item("strict", true)?;
+    }
+    Ok(core_schema.into())
+}
+
+/// Values in schemas, e.g. defaults, are only copied to this depth
+const MAX_CORE_SCHEMA_DEPTH: usize = 100;
+
+/// Keys of schemas whose values are a sub-schema, or a list or dict of them; sub-schemas given as strings or classes
+/// are only replaced by their core schemas at these keys since the same objects may be other values, e.g. the
+/// interned string 'int' could also be a `title`, whereas dict sub-schemas are found anywhere
+const SUB_SCHEMA_KEYS: [&str; 13] = [
+    "schema",
+    "items",
+    "prefix_items",
+    "keys",
+    "values",
+    "fields",
+    "extra_validator",
+    "choices",
+    "model",
+    "definitions",
+    "if",
+    "then",
+    "else",
+];
+
+/// A copy of `value` with each sub-schema which was built replaced by its core schema
+fn with_core_schemas(value: &PyAny, core_schemas: &CoreSchemas, depth: usize) -> PyResult<PyObject> {
+    let py = value.py();
+    if depth > MAX_CORE_SCHEMA_DEPTH {
+        Ok(value.into_py(py))
+    } else if let Ok(dict) = value.cast_as::<PyDict>() {
+        if let Some((_, core_schema)) = core_schemas.get(&(dict.as_ptr() as usize)) {
+            return Ok(core_schema.clone_ref(py).into_py(py));
+        }
+        let output = PyDict::new(py);
+        for (key, item) in dict.iter() {
+            let item = match key.extract::<&str>() {
+                Ok(key) if SUB_SCHEMA_KEYS.contains(&key) => sub_schemas(item, core_schemas, depth + 1)?,
+                _ => with_core_schemas(item, core_schemas, depth + 1)?,
+            };
+            output.set_item(key, item)?;
+        }
+        Ok(output.into_py(py))
+    } else if let Ok(list) = value.cast_as::<PyList>() {
+        let items = list
+            .iter()
+            .map(|item| with_core_schemas(item, core_schemas, depth + 1))
+            .collect::<PyResult<Vec<PyObject>>>()?;
+        Ok(PyList::new(py, items).into_py(py))
+    } else {
+        Ok(value.into_py(py))
+    }
+}
+
+/// Like `with_core_schemas` for the value of one of `SUB_SCHEMA_KEYS`
+fn sub_schemas(value: &PyAny, core_schemas: &CoreSchemas, depth: usize) -> PyResult<PyObject> {
+    let py = value.py();
+    let sub_schema = |item: &PyAny| match core_schemas.get(&(item.as_ptr() as usize)) {
+        Some((_, core_schema)) => Ok(core_schema.clone_ref(py).into_py(py)),
+        None => with_core_schemas(item, core_schemas, depth + 1),
+    };
+    if core_schemas.contains_key(&(value.as_ptr() as usize)) {
+        sub_schema(value)
+    } else if let Ok(dict) = value.cast_as::<PyDict>() {
+        let output = PyDict::new(py);
+        for (key, item) in dict.iter() {
+            output.set_item(key, sub_schema(item)?)?;
+        }
+        Ok(output.into_py(py))
+    } else if let Ok(list) = value.cast_as::<PyList>() {
+        let items = list.iter().map(sub_schema).collect::<PyResult<Vec<PyObject>>>()?;
+        Ok(PyList::new(py, items).into_py(py))
+    } else {
+        Ok(value.into_py(py))
+    }
+}
+
+/// The keys and indexes leading from `value` to `target`, found by identity like `with_core_schemas`
+fn find_in_schema(value: &PyAny, target: &PyAny, depth: usize) -> Option<Vec<String>> {
+    if value.is(target) {
+        Some(vec![])
+    } else if depth > MAX_CORE_SCHEMA_DEPTH {
+        None
+    } else if let Ok(dict) = value.cast_as::<PyDict>() {
+        dict.iter().find_map(|(key, item)| {
+            let mut path = find_in_schema(item, target, depth + 1)?;
+            path.insert(0, key.str().ok()?.to_string_lossy().into_owned());
+            Some(path)
+        })
+    } else if let Ok(list) = value.cast_as::<PyList>() {
+        list.iter().enumerate().find_map(|(index, item)| {
+            let mut path = find_in_schema(item, target, depth + 1)?;
+            path.insert(0, index.to_string());
+            Some(path)
+        })
+    } else {
+        None
+    }
 }
 
 /// More (mostly immutable) data to pass between validators, should probably be class `Context`,
 /// but that would confuse it with context as per samuelcolvin/pydantic#1549
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Extra<'a> {
     /// This is used as the `data` kwargs to validator functions, it's also represents the current model
-    /// data when validating assignment
+    /// data when validating assignment, within a model it contains the fields validated so far
     pub data: Option<&'a PyDict>,
     /// The field being assigned to when validating assignment
     pub field: Option<&'a str>,
+    /// Collects non-fatal warnings, `None` unless warnings were requested
+    pub warnings: Option<&'a WarningsCollector>,
+    /// The `context` passed to `validate_python` or `validate_json`, this is passed to validator functions
+    pub context: Option<&'a PyAny>,
+    /// Errors from invalid model fields, set when validating with `collect_errors=True`
+    pub collected_errors: Option<&'a ErrorsCollector>,
+    /// Records lax mode coercions, set when validating with `report_coercions=True`
+    pub coercions: Option<&'a CoercionsCollector>,
+    /// The running event loop when validating with `validate_python_async`, coroutines returned by validator
+    /// functions are awaited on it
+    pub event_loop: Option<&'a PyAny>,
+    /// Limits on input size and validation time, set via the `limits` argument
+    pub limits: Option<&'a Rc<Limits>>,
+    /// Which keys model field values are found by, set via the `by_alias` and `by_name` arguments
+    pub field_lookup: FieldLookup,
+    /// State for this call to validate, it's shared with the validators wrap functions call from python
+    pub state: Option<&'a Rc<ValidationState>>,
+    /// Config overridden by `SchemaValidator.copy`
+    pub overrides: ConfigOverrides,
+    /// Set by `isinstance_python` and `isinstance_json` where the output is dropped: models, model classes and
+    /// containers return `None` rather than building it, validators which use the output of their inner validator
+    /// (e.g. `after` functions) must unset it
+    pub check_only: bool,
+}
+
+/// Config applied during validation rather than when validators are built, set by `SchemaValidator.copy`:
+/// * `strict` - validate as though every validator were strict, `strict=False` can't make validators built strict lax
+/// * `extra_behavior` - used by every model in place of its own `extra_behavior`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfigOverrides {
+    pub strict: bool,
+    pub extra_behavior: Option<ExtraBehavior>,
+}
+
+/// Lets the same validator accept external data keyed by field aliases and internal data keyed by field names,
+/// fields without an alias are always found by name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FieldLookup {
+    #[default]
+    Alias,
+    Name,
+    /// the alias if present in the input, otherwise the name
+    AliasThenName,
+}
+
+/// The number of warnings and collected errors at a point during validation, used to find those added by child
+/// validators
+#[derive(Debug, Clone, Copy)]
+pub struct Checkpoint {
+    warnings: usize,
+    errors: usize,
+    coercions: usize,
+}
+
+impl<'a> Extra<'a> {
+    pub fn with_state(state: &'a Rc<ValidationState>) -> Self {
+        Self {
+            state: Some(state),
+            ..Default::default()
+        }
+    }
+
+    /// Check for cycles and excessive depth before following a recursive reference, see
+    /// `ValidationState.enter_recursion`
+    pub fn enter_recursion<'d>(
+        &self,
+        py: Python,
+        input: &'d dyn Input,
+        validator_id: usize,
+    ) -> ValResult<'d, Option<RecursionGuard<'a>>> {
+        match self.state {
+            Some(state) => Ok(Some(state.enter_recursion(py, input, validator_id)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Check limits before validating the items of a container, the depth is restored when the guard is dropped
+    pub fn enter_container<'d>(&self, input: &'d dyn Input, items: usize) -> ValResult<'d, Option<DepthGuard<'a>>> {
+        match self.limits {
+            Some(limits) => Ok(Some(limits.enter_container(input, items)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Check the `max_string_length` and `max_string_bytes_total` limits, if set
+    pub fn check_str<'d>(&self, input: &'d dyn Input, either_str: &EitherString<'d>) -> ValResult<'d, ()> {
+        match self.limits {
+            Some(limits) => limits.check_str(input, either_str),
+            None => Ok(()),
+        }
+    }
+
+    /// Record a warning, this is a no-op unless warnings are being collected
+    pub fn warn(&self, location: Location, message: &str) {
+        self.warn_as(WarningCategory::User, location, message)
+    }
+
+    /// Record a warning which is emitted as a `DeprecationWarning`, e.g. for input to deprecated fields
+    pub fn warn_deprecated(&self, location: Location, message: &str) {
+        self.warn_as(WarningCategory::Deprecation, location, message)
+    }
+
+    fn warn_as(&self, category: WarningCategory, location: Location, message: &str) {
+        if let Some(warnings) = self.warnings {
+            warnings.push(ValWarning {
+                location,
+                message: message.to_string(),
+                category,
+            });
+        }
+    }
+
+    /// Validate `input` using `lax`, when coercions are being reported and `strict` would have failed, the
+    /// coercion is recorded
+    pub fn lax_with_report<'d, T, S, L>(
+        &self,
+        py: Python,
+        input: &'d dyn Input,
+        to_type: &'static str,
+        strict: S,
+        lax: L,
+    ) -> ValResult<'d, T>
+    where
+        S: FnOnce(&'d dyn Input) -> ValResult<'d, T>,
+        L: FnOnce(&'d dyn Input) -> ValResult<'d, T>,
+    {
+        if self.overrides.strict {
+            return strict(input);
+        }
+        match self.coercions {
+            Some(coercions) => match strict(input) {
+                Ok(value) => Ok(value),
+                Err(_) => {
+                    let value = lax(input)?;
+                    let input_obj = input.to_py(py);
+                    coercions.push(Coercion {
+                        location: Location::new(),
+                        from_type: input_obj.as_ref(py).get_type().name().map_err(as_internal)?.to_string(),
+                        to_type,
+                    });
+                    Ok(value)
+                }
+            },
+            None => lax(input),
+        }
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            warnings: self.warnings.map(|w| w.checkpoint()).unwrap_or(0),
+            errors: self.collected_errors.map(|e| e.checkpoint()).unwrap_or(0),
+            coercions: self.coercions.map(|c| c.checkpoint()).unwrap_or(0),
+        }
+    }
+
+    /// Prefix the location of warnings and collected errors added since `checkpoint`, analogous to
+    /// `with_prefix_location` on errors
+    pub fn prefix_location<L: ToLocItem + ?Sized>(&self, checkpoint: Checkpoint, loc_item: &L) {
+        if let Some(warnings) = self.warnings {
+            if warnings.checkpoint() > checkpoint.warnings {
+                warnings.prefix_location(checkpoint.warnings, loc_item.to_loc());
+            }
+        }
+        if let Some(errors) = self.collected_errors {
+            if errors.checkpoint() > checkpoint.errors {
+                errors.prefix_location(checkpoint.errors, loc_item.to_loc());
+            }
+        }
+        if let Some(coercions) = self.coercions {
+            if coercions.checkpoint() > checkpoint.coercions {
+                coercions.prefix_location(checkpoint.coercions, loc_item.to_loc());
+            }
+        }
+    }
+
+    /// Drop warnings and collected errors added since `checkpoint`, e.g. those from a union choice which didn't match
+    pub fn discard(&self, checkpoint: Checkpoint) {
+        if let Some(warnings) = self.warnings {
+            warnings.truncate(checkpoint.warnings);
+        }
+        if let Some(errors) = self.collected_errors {
+            errors.truncate(checkpoint.errors);
+        }
+        if let Some(coercions) = self.coercions {
+            coercions.truncate(checkpoint.coercions);
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -242,14 +1419,19 @@ pub enum CombinedValidator {
     // booleans
     Bool(self::bool::BoolValidator),
     StrictBool(self::bool::StrictBoolValidator),
+    ConstrainedBool(self::bool::ConstrainedBoolValidator),
     // floats
     Float(self::float::FloatValidator),
     StrictFloat(self::float::StrictFloatValidator),
     ConstrainedFloat(self::float::ConstrainedFloatValidator),
+    // complex numbers
+    Complex(self::complex::ComplexValidator),
     // lists
     List(self::list::ListValidator),
     // sets - unique lists
     Set(self::set::SetValidator),
+    // tuples
+    Tuple(self::tuple::TupleValidator),
     // dicts/objects (recursive)
     Dict(self::dict::DictValidator),
     // None/null
@@ -270,6 +1452,25 @@ pub enum CombinedValidator {
     LiteralGeneral(self::literal::LiteralGeneralValidator),
     // any
     Any(self::any::AnyValidator),
+    // json strings
+    Json(self::json::JsonValidator),
+    // if/then/else
+    Conditional(self::conditional::ConditionalValidator),
+    // validators defined in other crates
+    Extension(self::extension::ExtensionValidator),
+    // IANA timezone names
+    TimezoneName(self::timezone::TimezoneNameValidator),
+    // MAC addresses
+    MacAddress(self::mac_address::MacAddressValidator),
+    // URLs with multiple hosts
+    MultiHostUrl(self::url::MultiHostUrlValidator),
+    // isinstance/issubclass checks
+    IsInstance(self::is_instance::IsInstanceValidator),
+    IsSubclass(self::is_instance::IsSubclassValidator),
+    // wraps validators when counting how often they run
+    Counted(self::counted::CountedValidator),
+    // model schemas identical to an earlier one
+    Shared(self::shared::SharedValidator),
 }
 
 /// This trait must be implemented by all validators, it allows various validators to be accessed consistently,
@@ -297,14 +1498,49 @@ pub trait Validator: Send + Sync + Clone + Debug {
         self.validate(py, input, extra, slots)
     }
 
+    /// Build the output from trusted input without any validation or coercion, see `SchemaValidator.construct`,
+    /// most validators simply return the input unchanged
+    fn construct(&self, py: Python, input: &PyAny, _slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        Ok(input.into_py(py))
+    }
+
     /// `get_name` generally returns `Self::EXPECTED_TYPE` or some other clear identifier of the validator
     /// this is used in the error location in unions, and in the top level message in `ValidationError`
     fn get_name(&self, py: Python) -> String;
+
+    /// Validate JSON without python so it can run with the GIL released, see `SchemaValidator.validate_json`;
+    /// `None` if the validator needs python or the input is invalid, it's then validated again as usual with the GIL
+    /// held, which builds the errors
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        _input: &'data JsonInput,
+        _extra: PureExtra,
+        _slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        None
+    }
 }
 
+#[derive(Default)]
 pub struct BuildContext {
     named_slots: Vec<(Option<String>, Option<CombinedValidator>)>,
     depth: usize,
+    /// Wrap each validator in a `CountedValidator`
+    counters: bool,
+    /// The schemas built so far by `schema_key`, see `shared_validator`
+    shared_schemas: HashMap<String, SharedSchema>,
+    /// A frame for each schema being built with the core schemas of the sub-schemas built so far, see `core_schema`
+    core_schemas: Vec<CoreSchemas>,
+    /// The core schemas of the schemas in `shared_schemas` by address, for identical schemas which reuse their build
+    shared_core_schemas: HashMap<usize, Py<PyDict>>,
+    /// Set by `shared_validator` when it reuses a build, the address of the first identical schema
+    shared_first: Option<usize>,
+    /// Set once a schema is added to `shared_schemas`, so its core schema is kept
+    shared_recorded: bool,
+    /// Set by `delegate_core_schema`
+    delegated_to: Option<usize>,
+    /// The schemas being built, outermost first, used to find the path of keys in `schema_error`
+    schema_stack: Vec<PyObject>,
 }
 
 const MAX_DEPTH: usize = 100;
@@ -315,6 +1551,15 @@ impl BuildContext {
         BuildContext {
             named_slots,
             depth: 0,
+            counters: false,
+            shared_schemas: HashMap::new(),
+            // the frame for top level schemas
+            core_schemas: vec![HashMap::new()],
+            shared_core_schemas: HashMap::new(),
+            shared_first: None,
+            shared_recorded: false,
+            delegated_to: None,
+            schema_stack: Vec::new(),
         }
     }
 
@@ -331,6 +1576,136 @@ impl BuildContext {
         Ok(id)
     }
 
+    /// Add a named slot for each definition, all slots are reserved before any is built so definitions can
+    /// reference each other (or themselves) in any order via `recursive-ref`; unlike recursive containers, the
+    /// names remain available after the definitions are built
+    pub fn add_definitions(&mut self, definitions: &PyDict, config: Option<&PyDict>) -> PyResult<()> {
+        let start_id = self.named_slots.len();
+        for name in definitions.keys() {
+            self.named_slots.push((Some(name.extract()?), None));
+        }
+        for (offset, (name, schema)) in definitions.iter().enumerate() {
+            let validator = build_validator(schema, config, self)
+                .map_err(|err| SchemaError::new_err(format!("Definition \"{}\":\n  {}", name, err)))?
+                .0;
+            self.named_slots[start_id + offset].1 = Some(validator);
+        }
+        Ok(())
+    }
+
+    /// Identical schemas (with the same config) are built once, e.g. the same model or type used by many fields:
+    /// later schemas reuse the validator built for the first. `model` validators are moved to a slot which every
+    /// identical schema after the first uses via `SharedValidator`, other validators are small so they're copied,
+    /// that way validators which match on the validators they contain, e.g. list's parallel checks, are unaffected;
+    /// `None` if no identical schema has been built
+    fn shared_validator(&mut self, key: &str, schema: &PyDict) -> PyResult<Option<CombinedValidator>> {
+        let shared = match self.shared_schemas.get_mut(key) {
+            Some(shared) => shared,
+            None => return Ok(None),
+        };
+        self.shared_first = Some(shared.schema.as_ptr() as usize);
+        if !matches!(shared.validator, CombinedValidator::Model(_)) {
+            return Ok(Some(shared.validator.clone()));
+        }
+        let validator_id = match shared.validator_id {
+            Some(validator_id) => validator_id,
+            None => {
+                let validator_id = self.named_slots.len();
+                self.named_slots.push((None, Some(shared.validator.clone())));
+                shared.validator_id = Some(validator_id);
+                validator_id
+            }
+        };
+        let name = shared.validator.get_name(schema.py());
+        Ok(Some(self::shared::SharedValidator::for_slot(validator_id, name)))
+    }
+
+    /// Keep the validator built for `schema` so identical schemas built later reuse it, see `shared_validator`
+    fn record_shared(&mut self, key: String, schema: &PyDict, validator: &CombinedValidator) {
+        let shared = SharedSchema {
+            schema: schema.into(),
+            validator: validator.clone(),
+            validator_id: None,
+        };
+        self.shared_schemas.insert(key, shared);
+        self.shared_recorded = true;
+    }
+
+    /// For schemas which are built by building another schema, e.g. classes and types registered with
+    /// `register_schema_type`, the core schema is that of the other schema, call this once it's built
+    pub fn delegate_core_schema(&mut self, schema: &PyAny) {
+        self.delegated_to = Some(schema.as_ptr() as usize);
+    }
+
+    /// The core schema of a schema built at the top level, see `SchemaValidator.core_schema`
+    fn take_core_schema(&mut self, schema: &PyAny) -> PyResult<Py<PyDict>> {
+        let top_level = self.core_schemas.first_mut();
+        match top_level.and_then(|frame| frame.remove(&(schema.as_ptr() as usize))) {
+            Some((_, core_schema)) => Ok(core_schema),
+            None => py_error!("Schema build error: missing core schema"),
+        }
+    }
+
+    /// A copy of definitions with the schemas built at the top level replaced by their core schemas
+    fn with_core_schemas(&self, definitions: &PyDict) -> PyResult<PyObject> {
+        match self.core_schemas.first() {
+            Some(top_level) => sub_schemas(definitions, top_level, 0),
+            None => Ok(definitions.into_py(definitions.py())),
+        }
+    }
+
+    /// Convert a `SchemaKeyError` to a `SchemaError` with the path of the key in the schema, e.g.
+    /// `fields.age.ge`, other errors are returned unchanged; the path is relative to the innermost schema which
+    /// isn't part of its parent, e.g. one returned by `__get_pydantic_core_schema__`
+    fn schema_error(&self, py: Python, err: PyErr) -> PyErr {
+        if !err.is_instance_of::<SchemaKeyError>(py) {
+            return err;
+        }
+        let args = err
+            .value(py)
+            .getattr(intern!(py, "args"))
+            .and_then(|args| args.extract());
+        let (dict, key, cause): (&PyAny, String, Option<String>) = match args {
+            Ok(args) => args,
+            Err(err) => return err,
+        };
+        let mut path = self.schema_path(dict).unwrap_or_default();
+        path.push(key);
+        let path = path.join(".");
+        match cause {
+            Some(cause) => SchemaError::new_err(format!(r#"Invalid "{}": {}"#, path, cause)),
+            None => SchemaError::new_err(format!(r#""{}" is required"#, path)),
+        }
+    }
+
+    /// The keys and indexes leading to `target` from the outermost schema being built
+    fn schema_path(&self, target: &PyAny) -> Option<Vec<String>> {
+        let py = target.py();
+        let mut paths: Vec<Vec<String>> = Vec::with_capacity(self.schema_stack.len());
+        for (index, schema) in self.schema_stack.iter().enumerate() {
+            let path = match index {
+                0 => vec![],
+                _ => {
+                    let parent = self.schema_stack[index - 1].as_ref(py);
+                    match find_in_schema(parent, schema.as_ref(py), 0) {
+                        Some(mut sub_path) => {
+                            let mut path = paths[index - 1].clone();
+                            path.append(&mut sub_path);
+                            path
+                        }
+                        None => vec![],
+                    }
+                }
+            };
+            paths.push(path);
+        }
+        let mut schemas = self.schema_stack.iter().zip(paths).rev();
+        schemas.find_map(|(schema, mut path)| {
+            path.append(&mut find_in_schema(schema.as_ref(py), target, 0)?);
+            Some(path)
+        })
+    }
+
     pub fn incr_check_depth(&mut self) -> PyResult<()> {
         self.depth += 1;
         if self.depth > MAX_DEPTH {
@@ -344,6 +1719,14 @@ impl BuildContext {
         self.depth -= 1;
     }
 
+    /// Get the validator built for a slot, used to avoid an extra level of indirection for top level definitions
+    pub fn get_slot(&self, id: usize) -> PyResult<CombinedValidator> {
+        match self.named_slots.get(id) {
+            Some((_, Some(validator))) => Ok(validator.clone()),
+            _ => py_error!("Schema build error: missing named slot"),
+        }
+    }
+
     pub fn find_id(&self, name: &str) -> PyResult<usize> {
         let is_match = |(n, _): &(Option<String>, Option<CombinedValidator>)| match n {
             Some(n) => n == name,
@@ -1,28 +1,85 @@
+use indexmap::IndexSet;
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PySet};
+use pyo3::types::{PyDict, PySet, PyType};
+use smallvec::smallvec;
 
-use crate::build_tools::{py_error, SchemaDict};
+use crate::build_tools::{merge_config, py_error, schema_or_config, SchemaDict};
 use crate::errors::{
     as_internal, err_val_error, val_line_error, ErrorKind, InputValue, ValError, ValLineError, ValResult,
 };
-use crate::input::{Input, MappingLenIter, ToLocItem};
+use crate::input::{GenericMapping, Input, JsonInput, JsonObject, LookupKey, MappingLenIter, ToLocItem};
 
-use super::{build_validator, BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
+use super::model_class::create_class;
+use super::pure::{PureExtra, PureOutput};
+use super::{build_validator, BuildContext, BuildValidator, CombinedValidator, Extra, FieldLookup, Validator};
 
 #[derive(Debug, Clone)]
 struct ModelField {
     name: String,
-    // alias: Option<String>,
-    default: Option<PyObject>,
+    alias: Option<String>,
+    name_key: LookupKey,
+    // the alias if set, otherwise the name
+    lookup_key: LookupKey,
+    // identifies the field in error locations, see `ErrorLocBy`
+    loc: String,
+    // names of environment variables to read the field from with `validate_env`, instead of the lookup key
+    env_names: Option<Vec<String>>,
+    default: Option<FieldDefault>,
+    on_error: OnError,
+    // frozen fields can't be changed by `validate_assignment`
+    frozen: bool,
+    // excluded fields are validated, and visible to later fields via `data`, but omitted from the output,
+    // e.g. write-only fields like password confirmations
+    exclude: bool,
+    // if set, input for the field adds this deprecation warning
+    deprecated: Option<String>,
     validator: CombinedValidator,
 }
 
+impl ModelField {
+    fn get_value<'a>(&self, dict: &GenericMapping<'a>, lookup: FieldLookup) -> Option<&'a dyn Input> {
+        match lookup {
+            FieldLookup::Alias => dict.generic_get(&self.lookup_key),
+            FieldLookup::Name => dict.generic_get(&self.name_key),
+            FieldLookup::AliasThenName => match self.alias {
+                Some(_) => dict
+                    .generic_get(&self.lookup_key)
+                    .or_else(|| dict.generic_get(&self.name_key)),
+                None => dict.generic_get(&self.name_key),
+            },
+        }
+    }
+
+    /// Like `get_value` for JSON objects, see `Validator::validate_pure`
+    fn json_value<'a>(&self, dict: &'a JsonObject, lookup: FieldLookup) -> Option<&'a JsonInput> {
+        match lookup {
+            FieldLookup::Alias => dict.get(self.lookup_key.as_str()),
+            FieldLookup::Name => dict.get(self.name_key.as_str()),
+            FieldLookup::AliasThenName => match self.alias {
+                Some(_) => dict
+                    .get(self.lookup_key.as_str())
+                    .or_else(|| dict.get(self.name_key.as_str())),
+                None => dict.get(self.name_key.as_str()),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ModelValidator {
     name: String,
+    title: String,
     fields: Vec<ModelField>,
+    // lookup keys and names of all fields, used to find extra keys in the input
+    lookup_keys: IndexSet<String>,
+    names: IndexSet<String>,
     extra_behavior: ExtraBehavior,
     extra_validator: Option<Box<CombinedValidator>>,
+    // output always has fields in the order they're defined, this also makes the order of extra keys deterministic
+    sort_extra: bool,
+    // fields may be found by name as well as by alias, even when validating `by_alias` alone
+    populate_by_name: bool,
+    output: ModelOutput,
 }
 
 impl BuildValidator for ModelValidator {
@@ -30,11 +87,11 @@ impl BuildValidator for ModelValidator {
 
     fn build(
         schema: &PyDict,
-        _config: Option<&PyDict>,
+        parent_config: Option<&PyDict>,
         build_context: &mut BuildContext,
     ) -> PyResult<CombinedValidator> {
-        // models ignore the parent config and always use the config from this model
-        let config: Option<&PyDict> = schema.get_as("config")?;
+        // the config of enclosing models provides defaults for this model's config
+        let config = merge_config(parent_config, schema.get_as("config")?)?;
 
         let extra_behavior = ExtraBehavior::from_config(config)?;
         let extra_validator = match extra_behavior {
@@ -44,21 +101,34 @@ impl BuildValidator for ModelValidator {
             },
             _ => None,
         };
+        let sort_extra = schema_or_config(schema, config, "sort_extra", "sort_extra")?.unwrap_or(false);
+        let populate_by_name =
+            schema_or_config(schema, config, "populate_by_name", "populate_by_name")?.unwrap_or(false);
+
+        let output = ModelOutput::from_schema(schema)?;
 
         let name: String = schema.get_as("name")?.unwrap_or_else(|| "Model".to_string());
+        let title: String = schema.get_as("title")?.unwrap_or_else(|| name.clone());
         let fields_dict: &PyDict = match schema.get_as("fields")? {
             Some(fields) => fields,
             None => {
                 // allow an empty model, is this is a good idea?
                 return Ok(Self {
                     name,
+                    title,
                     fields: vec![],
+                    lookup_keys: IndexSet::new(),
+                    names: IndexSet::new(),
                     extra_behavior,
                     extra_validator,
+                    sort_extra,
+                    populate_by_name,
+                    output,
                 }
                 .into());
             }
         };
+        let error_loc_by = ErrorLocBy::from_schema(schema, config)?;
         let mut fields: Vec<ModelField> = Vec::with_capacity(fields_dict.len());
 
         for (key, value) in fields_dict.iter() {
@@ -67,18 +137,72 @@ impl BuildValidator for ModelValidator {
                 Err(err) => return py_error!("Key \"{}\":\n  {}", key, err),
             };
 
+            let default = FieldDefault::from_field(key, field_dict)?;
+            let on_error = OnError::from_field(field_dict)?;
+            if matches!(on_error, OnError::Default) && default.is_none() {
+                return py_error!("Key \"{}\":\n  'on_error = default' requires a `default` value", key);
+            }
+
+            let name = key.to_string();
+            // int aliases find fields keyed by ints, or by numeric strings, see `LookupKey`
+            let (alias, int_alias) = match field_dict.get_item("alias") {
+                Some(alias) => match alias.extract::<String>() {
+                    Ok(alias) => (Some(alias), None),
+                    Err(_) => {
+                        let int_alias: i64 = alias.extract()?;
+                        (Some(int_alias.to_string()), Some(int_alias))
+                    }
+                },
+                None => (None, None),
+            };
+            let frozen: bool = field_dict.get_as("frozen")?.unwrap_or(false);
+            let exclude: bool = field_dict.get_as("exclude")?.unwrap_or(false);
+            let deprecated = match field_dict.get_item("deprecated") {
+                Some(deprecated) => match deprecated.extract::<String>() {
+                    Ok(message) => Some(message),
+                    Err(_) => deprecated.extract::<bool>()?.then(|| "Field is deprecated".to_string()),
+                },
+                None => None,
+            };
+            let env_names = match field_dict.get_item("env") {
+                Some(env) => match env.extract::<String>() {
+                    Ok(env_name) => Some(vec![env_name]),
+                    Err(_) => Some(env.extract::<Vec<String>>()?),
+                },
+                None => None,
+            };
+            let py = schema.py();
             fields.push(ModelField {
-                name: key.to_string(),
-                // alias: field_dict.get_as("alias"),
+                name_key: LookupKey::new(py, &name),
+                lookup_key: match int_alias {
+                    Some(int_alias) => LookupKey::new_int(py, int_alias),
+                    None => LookupKey::new(py, alias.as_ref().unwrap_or(&name)),
+                },
+                loc: error_loc_by.field_loc(&name, alias.as_deref()),
+                env_names,
+                name,
+                alias,
                 validator,
-                default: field_dict.get_as("default")?,
+                default,
+                on_error,
+                frozen,
+                exclude,
+                deprecated,
             });
         }
+        let lookup_keys = fields.iter().map(|f| f.lookup_key.as_str().to_string()).collect();
+        let names = fields.iter().map(|f| f.name.clone()).collect();
         Ok(Self {
             name,
+            title,
             fields,
+            lookup_keys,
+            names,
             extra_behavior,
             extra_validator,
+            sort_extra,
+            populate_by_name,
+            output,
         }
         .into())
     }
@@ -97,102 +221,337 @@ impl Validator for ModelValidator {
             return self.validate_assignment(py, field, input, extra, slots);
         }
 
+        // `data` is only set within another model, in which case errors record this model's title
+        let nested = extra.data.is_some();
+        let check_only = extra.check_only;
+        let field_lookup = match (self.populate_by_name, extra.field_lookup) {
+            (true, FieldLookup::Alias) => FieldLookup::AliasThenName,
+            (_, lookup) => lookup,
+        };
+
         // TODO we shouldn't always use try_instance=true here
         let dict = input.lax_dict(true)?;
+        let _depth = extra.enter_container(input, dict.generic_len())?;
         let output_dict = PyDict::new(py);
         let mut errors: Vec<ValLineError> = Vec::new();
         let fields_set = PySet::empty(py).map_err(as_internal)?;
 
+        // fields are validated in the order they're defined in the schema, so `data` (and thus the `data` kwarg of
+        // function validators) holds the values of all preceding fields which were valid or had a default
         let extra = Extra {
             data: Some(output_dict),
             field: None,
+            warnings: extra.warnings,
+            context: extra.context,
+            collected_errors: extra.collected_errors,
+            coercions: extra.coercions,
+            event_loop: extra.event_loop,
+            limits: extra.limits,
+            field_lookup: extra.field_lookup,
+            state: extra.state,
+            overrides: extra.overrides,
+            // field values are built since validators of later fields may read them via `data`
+            check_only: false,
         };
 
+        // number of keys in the input which matched a field, if this is the length of the input there are no extra keys
+        let mut used_keys = 0;
         for field in &self.fields {
-            if let Some(value) = dict.generic_get(&field.name) {
-                match field.validator.validate(py, value, &extra, slots) {
-                    Ok(value) => output_dict.set_item(&field.name, value).map_err(as_internal)?,
-                    Err(ValError::LineErrors(line_errors)) => {
-                        let loc = vec![field.name.to_loc()];
-                        for err in line_errors {
-                            errors.push(err.with_prefix_location(&loc));
+            if let Some(value) = field.get_value(&dict, field_lookup) {
+                used_keys += 1;
+                if let Some(ref message) = field.deprecated {
+                    extra.warn_deprecated(smallvec![field.loc.to_loc()], message);
+                }
+                let checkpoint = extra.checkpoint();
+                let result = field.validator.validate(py, value, &extra, slots);
+                extra.prefix_location(checkpoint, &field.loc);
+                match result {
+                    Ok(value) => {
+                        output_dict
+                            .set_item(field.name_key.py_key(), value)
+                            .map_err(as_internal)?;
+                        if !field.exclude {
+                            fields_set.add(field.name_key.py_key()).map_err(as_internal)?;
                         }
                     }
+                    Err(ValError::LineErrors(line_errors)) => match field.on_error {
+                        OnError::Raise => {
+                            let loc = smallvec![field.loc.to_loc()];
+                            for err in line_errors {
+                                errors.push(err.with_prefix_location(&loc));
+                            }
+                        }
+                        OnError::Default => {
+                            // the field's errors are dropped, and so are any errors collected within it
+                            extra.discard(checkpoint);
+                            if let Some(ref default) = field.default {
+                                let default = default.get(py, output_dict).map_err(as_internal)?;
+                                output_dict
+                                    .set_item(field.name_key.py_key(), default)
+                                    .map_err(as_internal)?;
+                            }
+                        }
+                        OnError::Omit => extra.discard(checkpoint),
+                    },
                     Err(err) => return Err(err),
                 }
-                fields_set.add(field.name.clone()).map_err(as_internal)?;
             } else if let Some(ref default) = field.default {
+                let default = default.get(py, output_dict).map_err(as_internal)?;
                 output_dict
-                    .set_item(&field.name, default.clone())
+                    .set_item(field.name_key.py_key(), default)
                     .map_err(as_internal)?;
             } else {
                 errors.push(val_line_error!(
                     input_value = InputValue::InputRef(input),
                     kind = ErrorKind::Missing,
-                    location = vec![field.name.to_loc()]
+                    location = smallvec![field.loc.to_loc()]
                 ));
             }
         }
 
-        let (check_extra, forbid) = match self.extra_behavior {
-            ExtraBehavior::Ignore => (false, false),
-            ExtraBehavior::Allow => (true, false),
-            ExtraBehavior::Forbid => (true, true),
+        // `SchemaValidator.copy` may override the behavior of all models
+        let extra_behavior = extra.overrides.extra_behavior.unwrap_or(self.extra_behavior);
+        let check_extra = match extra_behavior {
+            // with ignore, extra fields are only checked in order to warn about them
+            ExtraBehavior::Ignore => extra.warnings.is_some(),
+            ExtraBehavior::Allow | ExtraBehavior::Forbid => true,
         };
-        if check_extra {
+        let mut sorted_extra: Vec<(String, PyObject, PyObject)> = Vec::new();
+        if check_extra && used_keys < dict.generic_len() {
             for (raw_key, value) in dict.generic_iter() {
-                let key: String = match raw_key.lax_str() {
-                    Ok(k) => k,
-                    Err(ValError::LineErrors(line_errors)) => {
-                        let loc = vec![raw_key.to_loc()];
-                        for err in line_errors {
-                            errors.push(err.with_prefix_location(&loc));
-                        }
-                        continue;
-                    }
+                // keys which can't be read as strings, e.g. tuples, can't be field keys
+                let key_str = match raw_key.lax_str() {
+                    Ok(k) => Some(k),
+                    Err(ValError::LineErrors(_)) => None,
                     Err(err) => return Err(err),
                 };
-                if fields_set.contains(&key).map_err(as_internal)? {
-                    continue;
+                if let Some(ref k) = key_str {
+                    if self.is_field_key(k.as_str()?, field_lookup) {
+                        continue;
+                    }
                 }
-                fields_set.add(key.clone()).map_err(as_internal)?;
-                let loc = vec![key.to_loc()];
-
-                if forbid {
-                    errors.push(val_line_error!(
+                // extra keys which aren't strings, e.g. ints or tuples, are kept as they are in locations and output
+                let (key_loc, output_key) = match key_str {
+                    Some(k) if raw_key.strict_int().is_err() => {
+                        let key_loc = k.as_str()?.to_loc();
+                        (key_loc, k.into_py(py))
+                    }
+                    _ => (raw_key.to_loc(), raw_key.to_py(py)),
+                };
+                match extra_behavior {
+                    ExtraBehavior::Ignore => extra.warn(smallvec![key_loc], "Extra field ignored"),
+                    ExtraBehavior::Forbid => errors.push(val_line_error!(
                         input_value = InputValue::InputRef(input),
                         kind = ErrorKind::ExtraForbidden,
-                        location = loc
-                    ));
-                } else if let Some(ref validator) = self.extra_validator {
-                    match validator.validate(py, value, &extra, slots) {
-                        Ok(value) => output_dict.set_item(&key, value).map_err(as_internal)?,
-                        Err(ValError::LineErrors(line_errors)) => {
-                            for err in line_errors {
-                                errors.push(err.with_prefix_location(&loc));
+                        location = smallvec![key_loc]
+                    )),
+                    ExtraBehavior::Allow => {
+                        fields_set.add(&output_key).map_err(as_internal)?;
+                        let value = match self.extra_validator {
+                            Some(ref validator) => {
+                                let checkpoint = extra.checkpoint();
+                                let result = validator.validate(py, value, &extra, slots);
+                                extra.prefix_location(checkpoint, &key_loc);
+                                match result {
+                                    Ok(value) => value,
+                                    Err(ValError::LineErrors(line_errors)) => {
+                                        let loc = smallvec![key_loc];
+                                        for err in line_errors {
+                                            errors.push(err.with_prefix_location(&loc));
+                                        }
+                                        continue;
+                                    }
+                                    Err(err) => return Err(err),
+                                }
                             }
+                            None => value.to_py(py),
+                        };
+                        if self.sort_extra {
+                            sorted_extra.push((key_loc.to_string(), output_key, value));
+                        } else {
+                            output_dict.set_item(output_key, value).map_err(as_internal)?;
                         }
-                        Err(err) => return Err(err),
                     }
-                } else {
-                    output_dict.set_item(&key, value.to_py(py)).map_err(as_internal)?;
                 }
             }
         }
+        sorted_extra.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+        for (_, key, value) in sorted_extra {
+            output_dict.set_item(key, value).map_err(as_internal)?;
+        }
+        for field in self.fields.iter().filter(|f| f.exclude) {
+            if output_dict.contains(field.name_key.py_key()).map_err(as_internal)? {
+                output_dict.del_item(field.name_key.py_key()).map_err(as_internal)?;
+            }
+        }
+
+        if nested {
+            // errors from models nested within this one already have their title
+            for error in errors.iter_mut().filter(|e| e.title.is_none()) {
+                error.title = Some(self.title.clone());
+            }
+        }
 
-        if errors.is_empty() {
-            Ok((output_dict, fields_set).to_object(py))
+        if errors.is_empty() && check_only {
+            Ok(py.None())
+        } else if errors.is_empty() {
+            self.output.create(py, output_dict, fields_set).map_err(as_internal)
+        } else if let Some(collected_errors) = extra.collected_errors {
+            // invalid fields are omitted from the output
+            collected_errors.extend(py, errors);
+            self.output.create(py, output_dict, fields_set).map_err(as_internal)
         } else {
             Err(ValError::LineErrors(errors))
         }
     }
 
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        let dict: &PyDict = input.cast_as()?;
+        let output_dict = PyDict::new(py);
+        let fields_set = PySet::empty(py)?;
+        let mut used_keys: Vec<&str> = Vec::with_capacity(self.fields.len() * 2);
+
+        for field in &self.fields {
+            used_keys.push(field.lookup_key.as_str());
+            used_keys.push(&field.name);
+            if field.exclude {
+                continue;
+            }
+            // like `BaseModel.construct()`, values may be keyed by either the alias or the field name
+            let value = field.lookup_key.py_get(dict).or_else(|| dict.get_item(&field.name));
+            if let Some(value) = value {
+                output_dict.set_item(&field.name, field.validator.construct(py, value, slots)?)?;
+                fields_set.add(&field.name)?;
+            } else if let Some(ref default) = field.default {
+                output_dict.set_item(&field.name, default.get(py, output_dict)?)?;
+            }
+        }
+
+        if let ExtraBehavior::Allow = self.extra_behavior {
+            for (key, value) in dict.iter() {
+                let used = match key.extract::<&str>() {
+                    Ok(key) => used_keys.contains(&key),
+                    // int keys are used by fields with the same int alias
+                    Err(_) => match key.extract::<i64>() {
+                        Ok(int) => used_keys.contains(&int.to_string().as_str()),
+                        Err(_) => false,
+                    },
+                };
+                if used {
+                    continue;
+                }
+                output_dict.set_item(key, value)?;
+                fields_set.add(key)?;
+            }
+        }
+        self.output.create(py, output_dict, fields_set)
+    }
+
     fn get_name(&self, _py: Python) -> String {
         self.name.clone()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        extra: PureExtra,
+        slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        let dict = match input {
+            JsonInput::Object(dict) => dict,
+            _ => return None,
+        };
+        let field_lookup = match (self.populate_by_name, extra.field_lookup) {
+            (true, FieldLookup::Alias) => FieldLookup::AliasThenName,
+            (_, lookup) => lookup,
+        };
+        let mut fields = Vec::with_capacity(self.fields.len());
+        for field in &self.fields {
+            match field.json_value(dict, field_lookup) {
+                Some(value) => fields.push(Some(field.validator.validate_pure(value, extra, slots)?)),
+                // defaults are set by `create_pure` since they may be python objects or need calling
+                None if field.default.is_some() => fields.push(None),
+                None => return None,
+            }
+        }
+
+        let mut extra_items = Vec::new();
+        let extra_behavior = extra.overrides.extra_behavior.unwrap_or(self.extra_behavior);
+        if !matches!(extra_behavior, ExtraBehavior::Ignore) {
+            for (key, value) in dict {
+                if self.is_field_key(key, field_lookup) {
+                    continue;
+                }
+                if let ExtraBehavior::Forbid = extra_behavior {
+                    return None;
+                }
+                let value = match self.extra_validator {
+                    Some(ref validator) => validator.validate_pure(value, extra, slots)?,
+                    None => PureOutput::Json(value),
+                };
+                extra_items.push((key.as_str(), value));
+            }
+        }
+        if self.sort_extra {
+            extra_items.sort_by_key(|(key, _)| *key);
+        }
+        Some(PureOutput::Model(self, fields, extra_items))
+    }
 }
 
 impl ModelValidator {
+    /// The lookup key, `env` names and validator of each field, used by `validate_env`
+    pub fn env_fields(&self) -> impl Iterator<Item = (&str, Option<&[String]>, &CombinedValidator)> {
+        self.fields
+            .iter()
+            .map(|field| (field.lookup_key.as_str(), field.env_names.as_deref(), &field.validator))
+    }
+
+    /// Build the output from the result of `validate_pure` with the GIL held, like `validate` does
+    pub(super) fn create_pure(
+        &self,
+        py: Python,
+        fields: Vec<Option<PureOutput>>,
+        extra_items: Vec<(&str, PureOutput)>,
+    ) -> PyResult<PyObject> {
+        let output_dict = PyDict::new(py);
+        let fields_set = PySet::empty(py)?;
+        for (field, value) in self.fields.iter().zip(fields) {
+            match value {
+                Some(value) => {
+                    output_dict.set_item(field.name_key.py_key(), value.into_python(py)?)?;
+                    if !field.exclude {
+                        fields_set.add(field.name_key.py_key())?;
+                    }
+                }
+                // as in `validate`, a default factory taking data sees the fields before this one
+                None => {
+                    if let Some(ref default) = field.default {
+                        output_dict.set_item(field.name_key.py_key(), default.get(py, output_dict)?)?;
+                    }
+                }
+            }
+        }
+        for (key, value) in extra_items {
+            fields_set.add(key)?;
+            output_dict.set_item(key, value.into_python(py)?)?;
+        }
+        for field in self.fields.iter().filter(|f| f.exclude) {
+            if output_dict.contains(field.name_key.py_key())? {
+                output_dict.del_item(field.name_key.py_key())?;
+            }
+        }
+        self.output.create(py, output_dict, fields_set)
+    }
+
+    fn is_field_key(&self, key: &str, lookup: FieldLookup) -> bool {
+        match lookup {
+            FieldLookup::Alias => self.lookup_keys.contains(key),
+            FieldLookup::Name => self.names.contains(key),
+            FieldLookup::AliasThenName => self.lookup_keys.contains(key) || self.names.contains(key),
+        }
+    }
+
     fn validate_assignment<'s, 'data>(
         &'s self,
         py: Python<'data>,
@@ -219,27 +578,46 @@ impl ModelValidator {
         let prepare_result = |result: ValResult<'data, PyObject>| match result {
             Ok(output) => prepare_tuple(output),
             Err(ValError::LineErrors(line_errors)) => {
-                let loc = vec![field.to_loc()];
+                let loc = smallvec![field.to_loc()];
                 let errors = line_errors.into_iter().map(|e| e.with_prefix_location(&loc)).collect();
                 Err(ValError::LineErrors(errors))
             }
             Err(err) => Err(err),
         };
 
+        // like when validating the whole model, field validators see the other fields' values, but not the
+        // value being replaced; `field` is cleared so nested models validate normally
+        let other_data = data.copy().map_err(as_internal)?;
+        if other_data.contains(field).map_err(as_internal)? {
+            other_data.del_item(field).map_err(as_internal)?;
+        }
+        let field_extra = Extra {
+            data: Some(other_data),
+            field: None,
+            ..*extra
+        };
+
         if let Some(field) = self.fields.iter().find(|f| f.name == field) {
-            prepare_result(field.validator.validate(py, input, extra, slots))
+            if field.frozen {
+                return err_val_error!(
+                    input_value = InputValue::InputRef(input),
+                    location = smallvec![field.name.to_loc()],
+                    kind = ErrorKind::FrozenField
+                );
+            }
+            prepare_result(field.validator.validate(py, input, &field_extra, slots))
         } else {
-            match self.extra_behavior {
+            match extra.overrides.extra_behavior.unwrap_or(self.extra_behavior) {
                 // with allow we either want to set the value
                 ExtraBehavior::Allow => match self.extra_validator {
-                    Some(ref validator) => prepare_result(validator.validate(py, input, extra, slots)),
+                    Some(ref validator) => prepare_result(validator.validate(py, input, &field_extra, slots)),
                     None => prepare_tuple(input.to_py(py)),
                 },
                 // otherwise we raise an error:
                 // - with forbid this is obvious
                 // - with ignore the model should never be overloaded, so an error is the clearest option
                 _ => {
-                    let loc = vec![field.to_loc()];
+                    let loc = smallvec![field.to_loc()];
                     err_val_error!(
                         input_value = InputValue::InputRef(input),
                         location = loc,
@@ -251,8 +629,8 @@ impl ModelValidator {
     }
 }
 
-#[derive(Debug, Clone)]
-enum ExtraBehavior {
+#[derive(Debug, Clone, Copy)]
+pub enum ExtraBehavior {
     Allow,
     Ignore,
     Forbid,
@@ -261,19 +639,175 @@ enum ExtraBehavior {
 impl ExtraBehavior {
     pub fn from_config(config: Option<&PyDict>) -> PyResult<Self> {
         match config {
-            Some(dict) => {
-                let b: Option<String> = dict.get_as("extra")?;
-                match b {
-                    Some(s) => match s.as_str() {
-                        "allow" => Ok(ExtraBehavior::Allow),
-                        "ignore" => Ok(ExtraBehavior::Ignore),
-                        "forbid" => Ok(ExtraBehavior::Forbid),
-                        _ => py_error!(r#"Invalid extra_behavior: "{}""#, s),
-                    },
-                    None => Ok(ExtraBehavior::Ignore),
-                }
-            }
+            Some(dict) => match dict.get_as::<&str>("extra")? {
+                Some(s) => Self::from_name(s),
+                None => Ok(ExtraBehavior::Ignore),
+            },
             None => Ok(ExtraBehavior::Ignore),
         }
     }
+
+    pub fn from_name(name: &str) -> PyResult<Self> {
+        match name {
+            "allow" => Ok(ExtraBehavior::Allow),
+            "ignore" => Ok(ExtraBehavior::Ignore),
+            "forbid" => Ok(ExtraBehavior::Forbid),
+            _ => py_error!(r#"Invalid extra_behavior: "{}""#, name),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ExtraBehavior::Allow => "allow",
+            ExtraBehavior::Ignore => "ignore",
+            ExtraBehavior::Forbid => "forbid",
+        }
+    }
+}
+
+/// What the model validator returns, set by `output`, `output_class` or `output_factory` on the schema:
+/// * `tuple` - the output dict and the set of fields provided, the default and what `model-class` requires
+/// * `dict` - the output dict alone
+/// * `output_class` - an instance of the class, created without calling `__init__` as `model-class` does
+/// * `output_factory` - the result of calling the factory with the output dict as keyword arguments
+///
+/// `validate_assignment` always returns the tuple.
+#[derive(Debug, Clone)]
+pub enum ModelOutput {
+    Tuple,
+    Dict,
+    Class(Py<PyType>),
+    Factory(PyObject),
+}
+
+impl ModelOutput {
+    pub fn from_schema(schema: &PyDict) -> PyResult<Self> {
+        let output: Option<String> = schema.get_as("output")?;
+        let class: Option<&PyType> = schema.get_as("output_class")?;
+        let factory: Option<&PyAny> = schema.get_as("output_factory")?;
+        match (output.as_deref(), class, factory) {
+            (None | Some("tuple"), None, None) => Ok(Self::Tuple),
+            (Some("dict"), None, None) => Ok(Self::Dict),
+            (Some(s), None, None) => py_error!("Invalid model output: '{}'", s),
+            (None, Some(class), None) => Ok(Self::Class(class.into())),
+            (None, None, Some(factory)) => match factory.is_callable() {
+                true => Ok(Self::Factory(factory.into())),
+                false => py_error!("'output_factory' must be callable"),
+            },
+            _ => py_error!("Only one of 'output', 'output_class' and 'output_factory' may be set"),
+        }
+    }
+
+    pub fn is_tuple(&self) -> bool {
+        matches!(self, Self::Tuple)
+    }
+
+    fn create(&self, py: Python, output_dict: &PyDict, fields_set: &PySet) -> PyResult<PyObject> {
+        match self {
+            Self::Tuple => Ok((output_dict, fields_set).to_object(py)),
+            Self::Dict => Ok(output_dict.to_object(py)),
+            Self::Class(class) => create_class(class.as_ref(py), output_dict, fields_set),
+            Self::Factory(factory) => factory.call(py, (), Some(output_dict)),
+        }
+    }
+}
+
+/// How fields are identified in error locations, set by `error_loc_by` on the schema or in config:
+/// * `name` - the field name, the default
+/// * `alias` - the alias, where the field has one, so locations match the keys in the input
+/// * `both` - the alias followed by the name in brackets, e.g. `fooBar (foo_bar)`, where the field has an alias
+#[derive(Debug, Clone, Copy)]
+enum ErrorLocBy {
+    Name,
+    Alias,
+    Both,
+}
+
+impl ErrorLocBy {
+    fn from_schema(schema: &PyDict, config: Option<&PyDict>) -> PyResult<Self> {
+        let loc_by: Option<String> = schema_or_config(schema, config, "error_loc_by", "error_loc_by")?;
+        match loc_by.as_deref() {
+            None | Some("name") => Ok(Self::Name),
+            Some("alias") => Ok(Self::Alias),
+            Some("both") => Ok(Self::Both),
+            Some(s) => py_error!(r#"Invalid error_loc_by: "{}""#, s),
+        }
+    }
+
+    fn field_loc(self, name: &str, alias: Option<&str>) -> String {
+        match (self, alias) {
+            (Self::Alias, Some(alias)) => alias.to_string(),
+            (Self::Both, Some(alias)) => format!("{} ({})", alias, name),
+            _ => name.to_string(),
+        }
+    }
+}
+
+/// The value used for a field missing from the input
+#[derive(Debug, Clone)]
+enum FieldDefault {
+    Value(PyObject),
+    /// `default_factory` is called for each missing value, with the data validated so far (as for function
+    /// validators, only the fields before this one) if `default_factory_takes_data` is set
+    Factory {
+        factory: PyObject,
+        takes_data: bool,
+    },
+}
+
+impl FieldDefault {
+    fn from_field(key: &PyAny, field: &PyDict) -> PyResult<Option<Self>> {
+        let default: Option<PyObject> = field.get_as("default")?;
+        let factory: Option<&PyAny> = field.get_as("default_factory")?;
+        let takes_data: bool = field.get_as("default_factory_takes_data")?.unwrap_or(false);
+        match (default, factory) {
+            (Some(_), Some(_)) => py_error!("Key \"{}\":\n  'default' and 'default_factory' cannot both be set", key),
+            (Some(default), None) => Ok(Some(Self::Value(default))),
+            (None, Some(factory)) if !factory.is_callable() => {
+                py_error!("Key \"{}\":\n  'default_factory' must be callable", key)
+            }
+            (None, Some(factory)) => Ok(Some(Self::Factory {
+                factory: factory.into(),
+                takes_data,
+            })),
+            (None, None) => Ok(None),
+        }
+    }
+
+    fn get(&self, py: Python, data: &PyDict) -> PyResult<PyObject> {
+        match self {
+            Self::Value(default) => Ok(default.clone_ref(py)),
+            Self::Factory {
+                factory,
+                takes_data: false,
+            } => factory.call0(py),
+            Self::Factory {
+                factory,
+                takes_data: true,
+            } => factory.call1(py, (data,)),
+        }
+    }
+}
+
+/// What to do when a field's value is invalid
+#[derive(Debug, Clone)]
+enum OnError {
+    /// the error is included in the `ValidationError`, this is the default
+    Raise,
+    /// the field's default is used instead
+    Default,
+    /// the field is omitted from the output
+    Omit,
+}
+
+impl OnError {
+    fn from_field(field_dict: &PyDict) -> PyResult<Self> {
+        let on_error: Option<&str> = field_dict.get_as("on_error")?;
+        match on_error {
+            Some("raise") | None => Ok(Self::Raise),
+            Some("default") => Ok(Self::Default),
+            Some("omit") => Ok(Self::Omit),
+            Some(s) => py_error!(r#"Invalid on_error: "{}""#, s),
+        }
+    }
 }
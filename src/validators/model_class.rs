@@ -9,13 +9,18 @@ use pyo3::{ffi, intern};
 
 use crate::build_tools::{py_error, SchemaDict};
 use crate::errors::{as_internal, context, err_val_error, ErrorKind, InputValue, ValError, ValResult};
-use crate::input::Input;
+use crate::input::{Input, JsonInput, StrictCoercion, StrictCoercions};
 
+use super::model::ModelOutput;
+use super::pure::{PureExtra, PureOutput};
 use super::{build_validator, BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
 
 #[derive(Debug, Clone)]
 pub struct ModelClassValidator {
     strict: bool,
+    strict_coercions: StrictCoercions,
+    // if true, instances of subclasses of `class` are validated like other input instead of being accepted as is
+    exact_type: bool,
     validator: Box<CombinedValidator>,
     class: Py<PyType>,
 }
@@ -36,11 +41,16 @@ impl BuildValidator for ModelClassValidator {
         if &model_type != "model" {
             return py_error!("model-class expected a 'model' schema, got '{}'", model_type);
         }
+        if !ModelOutput::from_schema(model_schema)?.is_tuple() {
+            return py_error!("model-class requires the 'model' schema's default 'tuple' output");
+        }
 
         Ok(Self {
             // we don't use is_strict here since we don't wan validation to be strict in this case if
             // `config.strict` is set, only if this specific field is strict
             strict: schema.get_as("strict")?.unwrap_or(false),
+            strict_coercions: StrictCoercions::from_schema(schema, config)?,
+            exact_type: schema.get_as("exact_type")?.unwrap_or(false),
             validator: Box::new(validator),
             class: class.into(),
         }
@@ -48,6 +58,19 @@ impl BuildValidator for ModelClassValidator {
     }
 }
 
+impl ModelClassValidator {
+    pub fn model_validator(&self) -> &CombinedValidator {
+        &self.validator
+    }
+
+    fn instance_check<'data>(&self, py: Python<'data>, input: &'data dyn Input) -> ValResult<'data, bool> {
+        match self.exact_type {
+            true => input.exact_model_check(self.class.as_ref(py)),
+            false => input.strict_model_check(self.class.as_ref(py)),
+        }
+    }
+}
+
 impl Validator for ModelClassValidator {
     fn validate<'s, 'data>(
         &'s self,
@@ -56,10 +79,11 @@ impl Validator for ModelClassValidator {
         extra: &Extra,
         slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        let class = self.class.as_ref(py);
-        if input.strict_model_check(class)? {
+        if self.instance_check(py, input)? {
             Ok(input.to_py(py))
-        } else if self.strict {
+        } else if (self.strict || extra.overrides.strict)
+            && !input.allows_strict_coercion(&self.strict_coercions, StrictCoercion::ObjectToModel)
+        {
             err_val_error!(
                 input_value = InputValue::InputRef(input),
                 kind = ErrorKind::ModelType,
@@ -67,7 +91,7 @@ impl Validator for ModelClassValidator {
             )
         } else {
             let output = self.validator.validate(py, input, extra, slots)?;
-            self.create_class(py, output).map_err(as_internal)
+            self.output(py, output, extra)
         }
     }
 
@@ -75,17 +99,30 @@ impl Validator for ModelClassValidator {
         &'s self,
         py: Python<'data>,
         input: &'data dyn Input,
-        _extra: &Extra,
-        _slots: &'data [CombinedValidator],
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        if input.strict_model_check(self.class.as_ref(py))? {
+        if self.instance_check(py, input)? {
             Ok(input.to_py(py))
+        } else if input.allows_strict_coercion(&self.strict_coercions, StrictCoercion::ObjectToModel) {
+            // JSON can't contain instances, so by default in strict mode JSON objects are validated as the model
+            let output = self.validator.validate_strict(py, input, extra, slots)?;
+            self.output(py, output, extra)
         } else {
             // errors from `validate_strict` are never used used, so we can keep this simple
             Err(ValError::LineErrors(vec![]))
         }
     }
 
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        if input.is_instance(self.class.as_ref(py))? {
+            Ok(input.into_py(py))
+        } else {
+            let output = self.validator.construct(py, input, slots)?;
+            self.create_class(py, output)
+        }
+    }
+
     fn get_name(&self, py: Python) -> String {
         // Get the class's `__name__`, not using `class.name()` since it uses `__qualname__`
         // which is not what we want here
@@ -96,36 +133,67 @@ impl Validator for ModelClassValidator {
         };
         name_result.unwrap_or("ModelClass").to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        extra: PureExtra,
+        slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        // JSON objects are validated as the model, except in strict mode where that depends on `strict_coercions`
+        if self.strict || extra.overrides.strict {
+            return None;
+        }
+        let output = self.validator.validate_pure(input, extra, slots)?;
+        Some(PureOutput::ModelClass(self, Box::new(output)))
+    }
 }
 
 impl ModelClassValidator {
-    fn create_class(&self, py: Python, output: PyObject) -> PyResult<PyObject> {
+    /// The address of the class, used by unions of model classes to find the choice for an instance
+    pub fn class_id(&self) -> usize {
+        self.class.as_ptr() as usize
+    }
+
+    fn output<'data>(&self, py: Python, output: PyObject, extra: &Extra) -> ValResult<'data, PyObject> {
+        match extra.check_only {
+            true => Ok(py.None()),
+            false => self.create_class(py, output).map_err(as_internal),
+        }
+    }
+
+    pub(super) fn create_class(&self, py: Python, output: PyObject) -> PyResult<PyObject> {
         let (model_dict, fields_set): (&PyAny, &PyAny) = output.extract(py)?;
+        create_class(self.class.as_ref(py), model_dict, fields_set)
+    }
+}
 
-        // based on the following but with the second argument of new_func set to an empty tuple as required
-        // https://github.com/PyO3/pyo3/blob/d2caa056e9aacc46374139ef491d112cb8af1a25/src/pyclass_init.rs#L35-L77
-        let args = PyTuple::empty(py);
-        let raw_type = self.class.as_ref(py).as_type_ptr();
-        let instance = unsafe {
-            // Safety: raw_type is known to be a non-null type object pointer
-            match (*raw_type).tp_new {
-                // Safety: the result of new_func is guaranteed to be either an owned pointer or null on error returns.
-                Some(new_func) => PyObject::from_owned_ptr_or_err(
-                    py,
-                    // Safety: the non-null pointers are known to be valid, and it's allowed to call tp_new with a
-                    // null kwargs dict.
-                    new_func(raw_type, args.as_ptr(), null_mut()),
-                )?,
-                None => return Err(PyTypeError::new_err("base type without tp_new")),
-            }
-        };
+/// Create an instance of `class` without calling `__init__`, setting `__dict__` and `__fields_set__` directly
+pub fn create_class(class: &PyType, model_dict: &PyAny, fields_set: &PyAny) -> PyResult<PyObject> {
+    let py = class.py();
+    // based on the following but with the second argument of new_func set to an empty tuple as required
+    // https://github.com/PyO3/pyo3/blob/d2caa056e9aacc46374139ef491d112cb8af1a25/src/pyclass_init.rs#L35-L77
+    let args = PyTuple::empty(py);
+    let raw_type = class.as_type_ptr();
+    let instance = unsafe {
+        // Safety: raw_type is known to be a non-null type object pointer
+        match (*raw_type).tp_new {
+            // Safety: the result of new_func is guaranteed to be either an owned pointer or null on error returns.
+            Some(new_func) => PyObject::from_owned_ptr_or_err(
+                py,
+                // Safety: the non-null pointers are known to be valid, and it's allowed to call tp_new with a
+                // null kwargs dict.
+                new_func(raw_type, args.as_ptr(), null_mut()),
+            )?,
+            None => return Err(PyTypeError::new_err("base type without tp_new")),
+        }
+    };
 
-        let instance_ref = instance.as_ref(py);
-        force_setattr(py, instance_ref, intern!(py, "__dict__"), model_dict)?;
-        force_setattr(py, instance_ref, intern!(py, "__fields_set__"), fields_set)?;
+    let instance_ref = instance.as_ref(py);
+    force_setattr(py, instance_ref, intern!(py, "__dict__"), model_dict)?;
+    force_setattr(py, instance_ref, intern!(py, "__fields_set__"), fields_set)?;
 
-        Ok(instance)
-    }
+    Ok(instance)
 }
 
 pub fn force_setattr<N, V>(py: Python<'_>, obj: &PyAny, attr_name: N, value: V) -> PyResult<()>
@@ -2,8 +2,9 @@ use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
 use crate::errors::{err_val_error, ErrorKind, InputValue, ValResult};
-use crate::input::Input;
+use crate::input::{Input, JsonInput};
 
+use super::pure::{PureExtra, PureOutput};
 use super::{BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
 
 #[derive(Debug, Clone)]
@@ -41,4 +42,16 @@ impl Validator for NoneValidator {
     fn get_name(&self, _py: Python) -> String {
         Self::EXPECTED_TYPE.to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        _extra: PureExtra,
+        _slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        match input {
+            JsonInput::Null => Some(PureOutput::None),
+            _ => None,
+        }
+    }
 }
@@ -2,8 +2,9 @@ use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
 use crate::build_tools::SchemaDict;
-use crate::input::Input;
+use crate::input::{Input, JsonInput};
 
+use super::pure::{PureExtra, PureOutput};
 use super::{build_validator, BuildContext, BuildValidator, CombinedValidator, Extra, ValResult, Validator};
 
 #[derive(Debug, Clone)]
@@ -27,6 +28,12 @@ impl BuildValidator for OptionalValidator {
     }
 }
 
+impl OptionalValidator {
+    pub fn inner(&self) -> &CombinedValidator {
+        &self.validator
+    }
+}
+
 impl Validator for OptionalValidator {
     fn validate<'s, 'data>(
         &'s self,
@@ -54,7 +61,26 @@ impl Validator for OptionalValidator {
         }
     }
 
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        match input.is_none() {
+            true => Ok(py.None()),
+            false => self.validator.construct(py, input, slots),
+        }
+    }
+
     fn get_name(&self, _py: Python) -> String {
         Self::EXPECTED_TYPE.to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        extra: PureExtra,
+        slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        match input {
+            JsonInput::Null => Some(PureOutput::None),
+            _ => self.validator.validate_pure(input, extra, slots),
+        }
+    }
 }
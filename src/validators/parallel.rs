@@ -0,0 +1,131 @@
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use smallvec::smallvec;
+
+use crate::errors::{LocItem, ValError, ValLineError, ValResult};
+use crate::input::{Input, JsonInput};
+
+use super::float::ConstrainedFloatValidator;
+use super::int::ConstrainedIntValidator;
+use super::{CombinedValidator, Extra, Validator};
+
+/// Each thread checks at least this many items, so shorter lists are always validated sequentially
+pub const CHUNK_SIZE: usize = 4096;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Scalar {
+    Int(i64),
+    Float(f64),
+}
+
+impl Scalar {
+    pub fn from_json(item: &JsonInput) -> Option<Self> {
+        match item {
+            JsonInput::Int(int) => Some(Self::Int(*int)),
+            JsonInput::Float(float) => Some(Self::Float(*float)),
+            _ => None,
+        }
+    }
+}
+
+impl IntoPy<PyObject> for Scalar {
+    fn into_py(self, py: Python) -> PyObject {
+        match self {
+            Self::Int(int) => int.into_py(py),
+            Self::Float(float) => float.into_py(py),
+        }
+    }
+}
+
+/// Item validators which can check items without python, so items can be checked with the GIL released
+#[derive(Debug, Clone, Copy)]
+pub enum ScalarCheck<'a> {
+    Int(Option<&'a ConstrainedIntValidator>),
+    Float(Option<&'a ConstrainedFloatValidator>),
+}
+
+impl<'a> ScalarCheck<'a> {
+    pub fn new(validator: &'a CombinedValidator) -> Option<Self> {
+        match validator {
+            CombinedValidator::Int(_) | CombinedValidator::StrictInt(_) => Some(Self::Int(None)),
+            CombinedValidator::ConstrainedInt(validator) => Some(Self::Int(Some(validator))),
+            CombinedValidator::Float(_) | CombinedValidator::StrictFloat(_) => Some(Self::Float(None)),
+            CombinedValidator::ConstrainedFloat(validator) => Some(Self::Float(Some(validator))),
+            _ => None,
+        }
+    }
+
+    /// The validated item, `None` if the item isn't an int or float as appropriate or it fails the constraints;
+    /// those items are validated again as usual with the GIL held, which builds the errors
+    fn check(self, item: Scalar) -> Option<Scalar> {
+        match (self, item) {
+            (Self::Int(constrained), Scalar::Int(int)) => match constrained {
+                Some(validator) => validator.check_constraints(int).ok().map(|_| item),
+                None => Some(item),
+            },
+            (Self::Int(_), Scalar::Float(_)) => None,
+            (Self::Float(constrained), Scalar::Int(int)) => Self::check_float(constrained, int as f64),
+            (Self::Float(constrained), Scalar::Float(float)) => Self::check_float(constrained, float),
+        }
+    }
+
+    fn check_float(constrained: Option<&ConstrainedFloatValidator>, float: f64) -> Option<Scalar> {
+        match constrained {
+            Some(validator) => validator.check_constraints(float).ok().map(|_| Scalar::Float(float)),
+            None => Some(Scalar::Float(float)),
+        }
+    }
+}
+
+/// Check `items` in chunks of at least `CHUNK_SIZE` items on rayon's thread pool with the GIL released,
+/// the result is in the same order as `items`
+pub fn check_items<T: Sync>(
+    py: Python,
+    items: &[T],
+    as_scalar: fn(&T) -> Option<Scalar>,
+    check: ScalarCheck,
+) -> Vec<Option<Scalar>> {
+    py.allow_threads(|| {
+        items
+            .par_iter()
+            .with_min_len(CHUNK_SIZE)
+            .map(|item| as_scalar(item).and_then(|item| check.check(item)))
+            .collect()
+    })
+}
+
+/// Convert the items checked by `check_items` to python, items which failed the check are validated again by
+/// `validator` as usual, `item_input` gives the input for each index
+pub fn validate_checked<'s, 'data>(
+    py: Python<'data>,
+    checked: Vec<Option<Scalar>>,
+    item_input: impl Fn(usize) -> &'data dyn Input,
+    validator: &'s CombinedValidator,
+    extra: &Extra,
+    slots: &'data [CombinedValidator],
+) -> ValResult<'data, Vec<PyObject>> {
+    let mut output: Vec<PyObject> = Vec::with_capacity(checked.len());
+    let mut errors: Vec<ValLineError> = Vec::new();
+    for (index, item) in checked.into_iter().enumerate() {
+        if let Some(item) = item {
+            output.push(item.into_py(py));
+            continue;
+        }
+        let checkpoint = extra.checkpoint();
+        let result = validator.validate(py, item_input(index), extra, slots);
+        extra.prefix_location(checkpoint, &index);
+        match result {
+            Ok(item) => output.push(item),
+            Err(ValError::LineErrors(line_errors)) => {
+                let loc = smallvec![LocItem::I(index)];
+                errors.extend(line_errors.into_iter().map(|err| err.with_prefix_location(&loc)));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    if errors.is_empty() {
+        Ok(output)
+    } else {
+        Err(ValError::LineErrors(errors))
+    }
+}
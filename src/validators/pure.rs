@@ -0,0 +1,103 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::input::{JsonInput, ToPy};
+
+use super::model::ModelValidator;
+use super::model_class::ModelClassValidator;
+use super::state::MAX_RECURSION_DEPTH;
+use super::{ConfigOverrides, Extra, FieldLookup};
+
+/// What `Validator::validate_pure` needs from `Extra`, it holds no python objects so JSON can be validated with the
+/// GIL released
+#[derive(Debug, Clone, Copy)]
+pub struct PureExtra {
+    pub overrides: ConfigOverrides,
+    pub field_lookup: FieldLookup,
+    // number of recursive references followed, as counted by `ValidationState::enter_recursion`
+    recursion_depth: usize,
+}
+
+impl PureExtra {
+    /// `None` if validating with `extra` needs python, i.e. warnings, errors or coercions are collected, limits are
+    /// set, or the output isn't built
+    pub fn from_extra(extra: &Extra) -> Option<Self> {
+        let needs_python = extra.warnings.is_some()
+            || extra.collected_errors.is_some()
+            || extra.coercions.is_some()
+            || extra.limits.is_some()
+            || extra.data.is_some()
+            || extra.field.is_some()
+            || extra.check_only;
+        match needs_python {
+            true => None,
+            false => Some(Self {
+                overrides: extra.overrides,
+                field_lookup: extra.field_lookup,
+                recursion_depth: 0,
+            }),
+        }
+    }
+
+    /// Follow a recursive reference, `None` once the maximum depth is reached so validating again with the GIL
+    /// held raises the usual recursion error
+    pub fn enter_recursion(self) -> Option<Self> {
+        match self.recursion_depth < MAX_RECURSION_DEPTH {
+            true => Some(Self {
+                recursion_depth: self.recursion_depth + 1,
+                ..self
+            }),
+            false => None,
+        }
+    }
+}
+
+/// The output of `Validator::validate_pure`, converted to python by `into_python` once the GIL is held again
+#[derive(Debug)]
+pub enum PureOutput<'s, 'data> {
+    None,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(&'data str),
+    /// the input converted to python as it is, e.g. by `any`
+    Json(&'data JsonInput),
+    List(Vec<PureOutput<'s, 'data>>),
+    Dict(Vec<(&'data str, PureOutput<'s, 'data>)>),
+    /// the value of each field, `None` for missing fields which use their default, and the extra items
+    Model(
+        &'s ModelValidator,
+        Vec<Option<PureOutput<'s, 'data>>>,
+        Vec<(&'data str, PureOutput<'s, 'data>)>,
+    ),
+    ModelClass(&'s ModelClassValidator, Box<PureOutput<'s, 'data>>),
+}
+
+impl PureOutput<'_, '_> {
+    pub fn into_python(self, py: Python) -> PyResult<PyObject> {
+        match self {
+            Self::None => Ok(py.None()),
+            Self::Bool(b) => Ok(b.into_py(py)),
+            Self::Int(int) => Ok(int.into_py(py)),
+            Self::Float(float) => Ok(float.into_py(py)),
+            Self::Str(s) => Ok(s.into_py(py)),
+            Self::Json(input) => Ok(input.to_py(py)),
+            Self::List(items) => {
+                let items = items
+                    .into_iter()
+                    .map(|item| item.into_python(py))
+                    .collect::<PyResult<Vec<PyObject>>>()?;
+                Ok(PyList::new(py, items).into_py(py))
+            }
+            Self::Dict(items) => {
+                let dict = PyDict::new(py);
+                for (key, value) in items {
+                    dict.set_item(key, value.into_python(py)?)?;
+                }
+                Ok(dict.into_py(py))
+            }
+            Self::Model(validator, fields, extra) => validator.create_pure(py, fields, extra),
+            Self::ModelClass(validator, output) => validator.create_class(py, output.into_python(py)?),
+        }
+    }
+}
@@ -4,8 +4,9 @@ use pyo3::types::PyDict;
 
 use crate::build_tools::{py_error, SchemaDict};
 use crate::errors::{as_internal, ValResult};
-use crate::input::Input;
+use crate::input::{Input, JsonInput};
 
+use super::pure::{PureExtra, PureOutput};
 use super::{BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
 
 #[derive(Debug, Clone)]
@@ -37,12 +38,31 @@ impl Validator for RecursiveValidator {
         slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
         let validator = get_validator(slots, self.validator_id)?;
+        let _recursion = extra.enter_recursion(py, input, self.validator_id)?;
         validator.validate(py, input, extra, slots)
     }
 
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        match slots.get(self.validator_id) {
+            Some(validator) => validator.construct(py, input, slots),
+            None => py_error!(PyRuntimeError; "Unable to find validator {}", self.validator_id),
+        }
+    }
+
     fn get_name(&self, _py: Python) -> String {
         Self::EXPECTED_TYPE.to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        extra: PureExtra,
+        slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        slots
+            .get(self.validator_id)?
+            .validate_pure(input, extra.enter_recursion()?, slots)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -73,12 +93,31 @@ impl Validator for RecursiveRefValidator {
         slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
         let validator = get_validator(slots, self.validator_id)?;
+        let _recursion = extra.enter_recursion(py, input, self.validator_id)?;
         validator.validate(py, input, extra, slots)
     }
 
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        match slots.get(self.validator_id) {
+            Some(validator) => validator.construct(py, input, slots),
+            None => py_error!(PyRuntimeError; "Unable to find validator {}", self.validator_id),
+        }
+    }
+
     fn get_name(&self, _py: Python) -> String {
         Self::EXPECTED_TYPE.to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        extra: PureExtra,
+        slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        slots
+            .get(self.validator_id)?
+            .validate_pure(input, extra.enter_recursion()?, slots)
+    }
 }
 
 fn get_validator(slots: &[CombinedValidator], id: usize) -> ValResult<&CombinedValidator> {
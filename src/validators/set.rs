@@ -1,18 +1,29 @@
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PySet};
+use pyo3::types::{PyBool, PyDict, PyFloat, PyFrozenSet, PyInt, PyList, PySet, PyString, PyTuple};
+use smallvec::smallvec;
 
-use crate::build_tools::{is_strict, SchemaDict};
-use crate::errors::{as_internal, context, err_val_error, ErrorKind, InputValue, LocItem, ValError, ValLineError};
-use crate::input::{GenericSequence, Input, SequenceLenIter};
+use crate::build_tools::{is_strict, py_error, SchemaDict};
+use crate::errors::{
+    as_internal, context, err_val_error, val_line_error, ErrorKind, InputValue, LocItem, ValError, ValLineError,
+};
+use crate::input::{GenericSequence, Input, SequenceLenIter, StrictCoercions};
 
+use super::function::convert_err;
+use super::list::construct_items;
 use super::{build_validator, BuildContext, BuildValidator, CombinedValidator, Extra, ValResult, Validator};
 
 #[derive(Debug, Clone)]
 pub struct SetValidator {
     strict: bool,
+    strict_coercions: StrictCoercions,
     item_validator: Option<Box<CombinedValidator>>,
     min_items: Option<usize>,
     max_items: Option<usize>,
+    // if true, distinct input items which are equal once validated (e.g. `1` and `'1'`) are an error rather than
+    // silently collapsing to one item
+    unique: bool,
+    item_key: Option<ItemKey>,
 }
 
 impl BuildValidator for SetValidator {
@@ -25,12 +36,15 @@ impl BuildValidator for SetValidator {
     ) -> PyResult<CombinedValidator> {
         Ok(Self {
             strict: is_strict(schema, config)?,
+            strict_coercions: StrictCoercions::from_schema(schema, config)?,
             item_validator: match schema.get_item("items") {
                 Some(d) => Some(Box::new(build_validator(d, config, build_context)?.0)),
                 None => None,
             },
             min_items: schema.get_as("min_items")?,
             max_items: schema.get_as("max_items")?,
+            unique: schema.get_as("unique")?.unwrap_or(false),
+            item_key: ItemKey::from_schema(schema)?,
         }
         .into())
     }
@@ -44,8 +58,8 @@ impl Validator for SetValidator {
         extra: &Extra,
         slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        let set = match self.strict {
-            true => input.strict_set()?,
+        let set = match self.strict || extra.overrides.strict {
+            true => input.strict_set_coerced(&self.strict_coercions)?,
             false => input.lax_set()?,
         };
         self._validation_logic(py, input, set, extra, slots)
@@ -58,7 +72,17 @@ impl Validator for SetValidator {
         extra: &Extra,
         slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        self._validation_logic(py, input, input.strict_set()?, extra, slots)
+        let set = input.strict_set_coerced(&self.strict_coercions)?;
+        self._validation_logic(py, input, set, extra, slots)
+    }
+
+    /// duplicates aren't looked for, with `item_key` set the output is a list of the items as they are
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        match construct_items(py, input, |_| self.item_validator.as_deref(), slots)? {
+            Some(output) if self.item_key.is_some() => Ok(PyList::new(py, &output).into_py(py)),
+            Some(output) => Ok(PySet::new(py, &output)?.into_py(py)),
+            None => Ok(input.into_py(py)),
+        }
     }
 
     fn get_name(&self, py: Python) -> String {
@@ -98,30 +122,193 @@ impl SetValidator {
             }
         }
 
-        match self.item_validator {
-            Some(ref validator) => {
-                let mut errors: Vec<ValLineError> = Vec::new();
-                let mut output: Vec<PyObject> = Vec::with_capacity(length);
-                for (index, item) in set.generic_iter() {
-                    match validator.validate(py, item, extra, slots) {
-                        Ok(item) => output.push(item),
+        let _depth = extra.enter_container(input, length)?;
+        let mut errors: Vec<ValLineError> = Vec::new();
+        let mut output: Vec<PyObject> = Vec::with_capacity(length);
+        // the index each validated item, or its key, was first found at, only used when duplicates are rejected or
+        // found by key
+        let first_indexes = match self.unique || self.item_key.is_some() {
+            true => Some(PyDict::new(py)),
+            false => None,
+        };
+        // duplicates are found from the validated items, so they must be built
+        let item_extra = Extra {
+            check_only: extra.check_only && first_indexes.is_none(),
+            ..*extra
+        };
+        // locations are the index of the item in the order the input is iterated, for python sets (unlike lists
+        // and tuples) that order is arbitrary, but it's the same each time a given set is iterated
+        for (index, raw_item) in set.generic_iter() {
+            let item = match self.item_validator {
+                Some(ref validator) => {
+                    let checkpoint = extra.checkpoint();
+                    let result = validator.validate(py, raw_item, &item_extra, slots);
+                    extra.prefix_location(checkpoint, &index);
+                    match result {
+                        Ok(item) => item,
                         Err(ValError::LineErrors(line_errors)) => {
-                            let loc = vec![LocItem::I(index)];
+                            let loc = smallvec![LocItem::I(index)];
                             errors.extend(line_errors.into_iter().map(|err| err.with_prefix_location(&loc)));
+                            continue;
                         }
                         Err(err) => return Err(err),
-                    };
+                    }
                 }
-                if errors.is_empty() {
-                    Ok(PySet::new(py, &output).map_err(as_internal)?.into_py(py))
-                } else {
-                    Err(ValError::LineErrors(errors))
+                None => raw_item.to_py(py),
+            };
+            if let Some(first_indexes) = first_indexes {
+                let key = match self.item_key {
+                    Some(ref item_key) => match item_key.key(py, &item) {
+                        Ok(key) => key,
+                        Err(err) => match convert_err(py, err, raw_item) {
+                            ValError::LineErrors(line_errors) => {
+                                let loc = smallvec![LocItem::I(index)];
+                                errors.extend(line_errors.into_iter().map(|err| err.with_prefix_location(&loc)));
+                                continue;
+                            }
+                            err => return Err(err),
+                        },
+                    },
+                    None => item.clone_ref(py),
+                };
+                if let Some(first_index) = first_indexes.get_item(&key) {
+                    if self.unique {
+                        errors.push(val_line_error!(
+                            input_value = InputValue::InputRef(raw_item),
+                            kind = ErrorKind::SetDuplicate,
+                            location = smallvec![LocItem::I(index)],
+                            context = context!("first_index" => first_index.extract::<usize>().map_err(as_internal)?)
+                        ));
+                    }
+                    continue;
                 }
+                first_indexes.set_item(key, index).map_err(as_internal)?;
+            }
+            output.push(item);
+        }
+        if !errors.is_empty() {
+            Err(ValError::LineErrors(errors))
+        } else if extra.check_only {
+            Ok(py.None())
+        } else if self.item_key.is_some() {
+            // items found by key needn't be hashable, so they're returned as a list
+            Ok(PyList::new(py, &output).into_py(py))
+        } else {
+            Ok(PySet::new(py, &output).map_err(as_internal)?.into_py(py))
+        }
+    }
+}
+
+/// How duplicates are found when items are compared by key rather than by their hash and equality, so items which
+/// can't be hashed, e.g. dicts and models, can be validated as sets
+#[derive(Debug, Clone)]
+enum ItemKey {
+    /// items with the same serialized form are duplicates, see `serialized_form`
+    Serialized,
+    /// items for which the function returns equal values are duplicates
+    Function(PyObject),
+}
+
+impl ItemKey {
+    fn from_schema(schema: &PyDict) -> PyResult<Option<Self>> {
+        let item_key = match schema.get_item("item_key") {
+            Some(item_key) => item_key,
+            None => return Ok(None),
+        };
+        match item_key.extract::<&str>() {
+            Ok("serialized") => Ok(Some(Self::Serialized)),
+            Ok(s) => py_error!(r#"Invalid item_key: "{}""#, s),
+            Err(_) if item_key.is_callable() => Ok(Some(Self::Function(item_key.into_py(schema.py())))),
+            Err(_) => py_error!("'item_key' must be 'serialized' or a callable"),
+        }
+    }
+
+    fn key(&self, py: Python, item: &PyObject) -> PyResult<PyObject> {
+        match self {
+            Self::Serialized => {
+                let mut output = String::new();
+                serialized_form(item.as_ref(py), &mut output, 0)?;
+                Ok(output.into_py(py))
             }
-            None => {
-                let output: Vec<PyObject> = set.generic_iter().map(|(_, item)| item.to_py(py)).collect();
-                Ok(PySet::new(py, &output).map_err(as_internal)?.into_py(py))
+            Self::Function(function) => function.call1(py, (item,)),
+        }
+    }
+}
+
+/// Items nested deeper than this, e.g. recursive dicts, can't be serialized
+const MAX_SERIALIZED_DEPTH: usize = 100;
+
+/// A JSON-like string which is the same for equal values: dict entries and set items are sorted, objects are
+/// serialized as their type name followed by their public `__dict__` entries, and any other values as their repr
+fn serialized_form(value: &PyAny, output: &mut String, depth: usize) -> PyResult<()> {
+    if depth > MAX_SERIALIZED_DEPTH {
+        return Err(PyValueError::new_err("item is nested too deeply to be serialized"));
+    }
+    if value.is_none() {
+        output.push_str("null");
+    } else if let Ok(bool) = value.cast_as::<PyBool>() {
+        output.push_str(if bool.is_true() { "true" } else { "false" });
+    } else if value.cast_as::<PyInt>().is_ok() || value.cast_as::<PyFloat>().is_ok() {
+        output.push_str(value.repr()?.to_str()?);
+    } else if let Ok(py_str) = value.cast_as::<PyString>() {
+        output.push_str(&serde_json::to_string(py_str.to_str()?).map_err(|e| PyValueError::new_err(e.to_string()))?);
+    } else if let Ok(dict) = value.cast_as::<PyDict>() {
+        serialize_dict(dict, output, depth)?;
+    } else if let Ok(list) = value.cast_as::<PyList>() {
+        serialize_items(list.iter(), false, output, depth)?;
+    } else if let Ok(tuple) = value.cast_as::<PyTuple>() {
+        serialize_items(tuple.iter(), false, output, depth)?;
+    } else if value.cast_as::<PySet>().is_ok() || value.cast_as::<PyFrozenSet>().is_ok() {
+        let items = value.iter()?.collect::<PyResult<Vec<&PyAny>>>()?;
+        serialize_items(items.into_iter(), true, output, depth)?;
+    } else if let Ok(dict) = value.getattr("__dict__").and_then(|d| Ok(d.cast_as::<PyDict>()?)) {
+        output.push_str(value.get_type().name()?);
+        let public = PyDict::new(value.py());
+        for (key, item) in dict.iter() {
+            match key.extract::<&str>() {
+                Ok(key) if key.starts_with('_') => (),
+                _ => public.set_item(key, item)?,
             }
         }
+        serialize_dict(public, output, depth)?;
+    } else {
+        output.push_str(value.repr()?.to_str()?);
     }
+    Ok(())
+}
+
+fn serialize_items<'a>(
+    items: impl Iterator<Item = &'a PyAny>,
+    sort: bool,
+    output: &mut String,
+    depth: usize,
+) -> PyResult<()> {
+    let mut items = items
+        .map(|item| {
+            let mut item_output = String::new();
+            serialized_form(item, &mut item_output, depth + 1)?;
+            Ok(item_output)
+        })
+        .collect::<PyResult<Vec<String>>>()?;
+    if sort {
+        items.sort();
+    }
+    output.push_str(&format!("[{}]", items.join(",")));
+    Ok(())
+}
+
+fn serialize_dict(dict: &PyDict, output: &mut String, depth: usize) -> PyResult<()> {
+    let mut entries = dict
+        .iter()
+        .map(|(key, value)| {
+            let mut entry = String::new();
+            serialized_form(key, &mut entry, depth + 1)?;
+            entry.push(':');
+            serialized_form(value, &mut entry, depth + 1)?;
+            Ok(entry)
+        })
+        .collect::<PyResult<Vec<String>>>()?;
+    entries.sort();
+    output.push_str(&format!("{{{}}}", entries.join(",")));
+    Ok(())
 }
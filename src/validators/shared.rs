@@ -0,0 +1,170 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use pyo3::AsPyPointer;
+
+use crate::errors::ValResult;
+use crate::input::{Input, JsonInput};
+
+use super::pure::{PureExtra, PureOutput};
+use super::{CombinedValidator, Extra, Validator};
+
+/// Schema types whose schemas contain recursive references by name, these resolve differently depending on where
+/// the schema is, so schemas containing them are never shared
+const NAMED_TYPES: [&str; 3] = ["recursive-container", "recursive-ref", "definitions"];
+
+/// A schema as built by `build_validator`, identical schemas built later reuse the validator, see
+/// `BuildContext::shared_validator`
+pub struct SharedSchema {
+    /// kept so objects identified by address in the key stay alive
+    pub schema: Py<PyDict>,
+    pub validator: CombinedValidator,
+    /// the slot holding a copy of the validator, once one's needed for an identical `model` schema
+    pub validator_id: Option<usize>,
+}
+
+/// Used in place of each `model` schema identical to an earlier one, the validator built for the first is copied to
+/// a slot which all identical schemas after the first use, see `BuildContext::shared_validator`
+#[derive(Debug, Clone)]
+pub struct SharedValidator {
+    validator_id: usize,
+    name: String,
+}
+
+impl SharedValidator {
+    pub fn for_slot(validator_id: usize, name: String) -> CombinedValidator {
+        Self { validator_id, name }.into()
+    }
+
+    pub fn validator_id(&self) -> usize {
+        self.validator_id
+    }
+}
+
+impl Validator for SharedValidator {
+    fn validate<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        slots[self.validator_id].validate(py, input, extra, slots)
+    }
+
+    fn validate_strict<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        slots[self.validator_id].validate_strict(py, input, extra, slots)
+    }
+
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        slots[self.validator_id].construct(py, input, slots)
+    }
+
+    fn get_name(&self, _py: Python) -> String {
+        self.name.clone()
+    }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        extra: PureExtra,
+        slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        slots[self.validator_id].validate_pure(input, extra, slots)
+    }
+}
+
+/// A key identifying a schema and its config by content: dicts, lists and tuples item by item, strings and numbers
+/// by value, other objects (e.g. functions and classes) by identity; `None` if the schema can't be shared, including
+/// schemas which contain themselves, building those fails anyway
+pub fn schema_key(schema: &PyDict, config: Option<&PyDict>) -> PyResult<Option<String>> {
+    let mut key = String::new();
+    if !write_key(&mut key, schema, &mut Vec::new())? {
+        return Ok(None);
+    }
+    key.push('|');
+    if let Some(config) = config {
+        if !write_key(&mut key, config, &mut Vec::new())? {
+            return Ok(None);
+        }
+    }
+    Ok(Some(key))
+}
+
+/// `containers` holds the addresses of the dicts, lists and tuples `value` is within
+fn write_key(key: &mut String, value: &PyAny, containers: &mut Vec<usize>) -> PyResult<bool> {
+    let address = value.as_ptr() as usize;
+    if containers.contains(&address) {
+        return Ok(false);
+    }
+    containers.push(address);
+    let result = write_value_key(key, value, containers);
+    containers.pop();
+    result
+}
+
+fn write_value_key(key: &mut String, value: &PyAny, containers: &mut Vec<usize>) -> PyResult<bool> {
+    if let Ok(dict) = value.cast_as::<PyDict>() {
+        if let Some(type_) = dict.get_item("type") {
+            if NAMED_TYPES.iter().any(|named| type_.eq(*named).unwrap_or(false)) {
+                return Ok(false);
+            }
+        }
+        key.push('{');
+        for (k, v) in dict.iter() {
+            write_key(key, k, containers)?;
+            key.push(':');
+            // defaults are used without copying, so mutable defaults must be the same object to be shared
+            let is_default = k.eq("default")?;
+            if is_default && !is_immutable(v) {
+                write_identity(key, v);
+            } else if !write_key(key, v, containers)? {
+                return Ok(false);
+            }
+            key.push(',');
+        }
+        key.push('}');
+    } else if let Ok(list) = value.cast_as::<PyList>() {
+        key.push('[');
+        for item in list.iter() {
+            if !write_key(key, item, containers)? {
+                return Ok(false);
+            }
+            key.push(',');
+        }
+        key.push(']');
+    } else if let Ok(tuple) = value.cast_as::<PyTuple>() {
+        key.push('(');
+        for item in tuple.iter() {
+            if !write_key(key, item, containers)? {
+                return Ok(false);
+            }
+            key.push(',');
+        }
+        key.push(')');
+    } else if is_immutable(value) {
+        // the type is included so e.g. `1`, `1.0` and `True` differ
+        key.push_str(&format!("{}:{}", value.get_type().name()?, value.repr()?));
+    } else {
+        write_identity(key, value);
+    }
+    Ok(true)
+}
+
+fn is_immutable(value: &PyAny) -> bool {
+    value.is_none()
+        || value.cast_as::<PyString>().is_ok()
+        || value.cast_as::<PyBool>().is_ok()
+        || value.cast_as::<PyInt>().is_ok()
+        || value.cast_as::<PyFloat>().is_ok()
+}
+
+fn write_identity(key: &mut String, value: &PyAny) {
+    // schemas are kept alive with their keys while the validator is built, so the address can't be reused
+    key.push_str(&format!("<{:p}>", value.as_ptr()));
+}
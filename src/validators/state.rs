@@ -0,0 +1,101 @@
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+use pyo3::conversion::AsPyPointer;
+use pyo3::prelude::*;
+
+use crate::errors::{
+    context, err_val_error, CoercionsCollector, ErrorKind, ErrorsCollector, InputValue, ValResult, WarningsCollector,
+};
+use crate::input::{Input, InputSource};
+
+/// Maximum number of recursive references followed while validating, this stops absurdly deep input from
+/// overflowing the stack when validated with a recursive schema
+pub(super) const MAX_RECURSION_DEPTH: usize = 255;
+
+/// Pooled states beyond this number are dropped, there's rarely more than one validation in progress per validator
+const MAX_POOLED_STATES: usize = 4;
+
+/// State for one call to validate, available to validators via `Extra.state`; once validation finishes the state
+/// is reset and kept by the `SchemaValidator` so later calls reuse its allocations, see `StatePool`
+#[derive(Debug, Default)]
+pub struct ValidationState {
+    pub warnings: WarningsCollector,
+    pub errors: ErrorsCollector,
+    pub coercions: CoercionsCollector,
+    /// `(input id, validator id)` of each recursive reference being followed, python input can contain
+    /// cycles, those are detected when the same input reaches the same recursive validator again
+    recursion_stack: RefCell<Vec<(usize, usize)>>,
+}
+
+impl ValidationState {
+    /// Called by recursive validators before following a reference, the reference is removed from the stack
+    /// when the guard is dropped
+    pub fn enter_recursion<'data>(
+        &self,
+        py: Python,
+        input: &'data dyn Input,
+        validator_id: usize,
+    ) -> ValResult<'data, RecursionGuard> {
+        let mut stack = self.recursion_stack.borrow_mut();
+        // JSON can't contain cycles, so only the depth is checked
+        let input_id = match input.source() {
+            InputSource::Python => input.to_py(py).as_ptr() as usize,
+            InputSource::Json => 0,
+        };
+        if input_id != 0 && stack.contains(&(input_id, validator_id)) {
+            return err_val_error!(
+                input_value = InputValue::InputRef(input),
+                kind = ErrorKind::RecursionLoop
+            );
+        }
+        if stack.len() >= MAX_RECURSION_DEPTH {
+            return err_val_error!(
+                input_value = InputValue::InputRef(input),
+                kind = ErrorKind::LimitDepth,
+                context = context!("max_depth" => MAX_RECURSION_DEPTH)
+            );
+        }
+        stack.push((input_id, validator_id));
+        Ok(RecursionGuard(&self.recursion_stack))
+    }
+
+    /// Clear everything recorded during validation, buffers keep their capacity
+    fn reset(&mut self) {
+        self.warnings.clear();
+        self.errors.clear();
+        self.coercions.clear();
+        self.recursion_stack.get_mut().clear();
+    }
+}
+
+/// Removes the reference from the recursion stack when the recursive validator finishes
+pub struct RecursionGuard<'a>(&'a RefCell<Vec<(usize, usize)>>);
+
+impl Drop for RecursionGuard<'_> {
+    fn drop(&mut self) {
+        self.0.borrow_mut().pop();
+    }
+}
+
+/// `ValidationState`s not currently in use, shared by clones of a `SchemaValidator`
+#[derive(Debug, Default)]
+pub struct StatePool(Mutex<Vec<ValidationState>>);
+
+impl StatePool {
+    pub fn take(&self) -> ValidationState {
+        match self.0.lock() {
+            Ok(mut states) => states.pop().unwrap_or_default(),
+            Err(_) => ValidationState::default(),
+        }
+    }
+
+    pub fn give_back(&self, mut state: ValidationState) {
+        state.reset();
+        if let Ok(mut states) = self.0.lock() {
+            if states.len() < MAX_POOLED_STATES {
+                states.push(state);
+            }
+        }
+    }
+}
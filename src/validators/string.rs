@@ -1,11 +1,17 @@
+use std::borrow::Cow;
+
+use pyo3::ffi;
+use pyo3::intern;
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyString};
+use pyo3::PyTypeInfo;
 use regex::Regex;
 
 use crate::build_tools::{is_strict, py_error, schema_or_config};
-use crate::errors::{context, err_val_error, ErrorKind, InputValue, ValResult};
-use crate::input::Input;
+use crate::errors::{as_internal, context, err_val_error, ErrorKind, InputValue, ValResult};
+use crate::input::{EitherString, Input, JsonInput, LaxCoercions, LaxType};
 
+use super::pure::{PureExtra, PureOutput};
 use super::{BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
 
 #[derive(Debug, Clone)]
@@ -25,6 +31,11 @@ impl BuildValidator for StrValidator {
             || schema.get_item("strip_whitespace").is_some()
             || schema.get_item("to_lower").is_some()
             || schema.get_item("to_upper").is_some()
+            || schema.get_item("normalize").is_some()
+            || schema.get_item("coerce_numbers_to_str").is_some()
+            || schema.get_item("subclass").is_some()
+            || schema.get_item("intern").is_some()
+            || schema.get_item("disabled_coercions").is_some()
             || match config {
                 Some(config) => {
                     config.get_item("str_pattern").is_some()
@@ -33,6 +44,11 @@ impl BuildValidator for StrValidator {
                         || config.get_item("str_strip_whitespace").is_some()
                         || config.get_item("str_to_lower").is_some()
                         || config.get_item("str_to_upper").is_some()
+                        || config.get_item("str_normalize").is_some()
+                        || config.get_item("str_coerce_numbers_to_str").is_some()
+                        || config.get_item("str_subclass").is_some()
+                        || config.get_item("str_intern").is_some()
+                        || config.get_item("disabled_coercions").is_some()
                 }
                 None => false,
             };
@@ -51,25 +67,41 @@ impl Validator for StrValidator {
         &'s self,
         py: Python<'data>,
         input: &'data dyn Input,
-        _extra: &Extra,
+        extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        Ok(input.lax_str()?.into_py(py))
+        let value = extra.lax_with_report(py, input, "str", |i| i.strict_str(), |i| i.lax_str())?;
+        extra.check_str(input, &value)?;
+        Ok(value.into_py(py))
     }
 
     fn validate_strict<'s, 'data>(
         &'s self,
         py: Python<'data>,
         input: &'data dyn Input,
-        _extra: &Extra,
+        extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        Ok(input.strict_str()?.into_py(py))
+        let value = input.strict_str()?;
+        extra.check_str(input, &value)?;
+        Ok(value.into_py(py))
     }
 
     fn get_name(&self, _py: Python) -> String {
         Self::EXPECTED_TYPE.to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        _extra: PureExtra,
+        _slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        match input {
+            JsonInput::String(s) => Some(PureOutput::Str(s)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -86,15 +118,29 @@ impl Validator for StrictStrValidator {
         &'s self,
         py: Python<'data>,
         input: &'data dyn Input,
-        _extra: &Extra,
+        extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        Ok(input.strict_str()?.into_py(py))
+        let value = input.strict_str()?;
+        extra.check_str(input, &value)?;
+        Ok(value.into_py(py))
     }
 
     fn get_name(&self, _py: Python) -> String {
         "strict-str".to_string()
     }
+
+    fn validate_pure<'s, 'data>(
+        &'s self,
+        input: &'data JsonInput,
+        _extra: PureExtra,
+        _slots: &'s [CombinedValidator],
+    ) -> Option<PureOutput<'s, 'data>> {
+        match input {
+            JsonInput::String(s) => Some(PureOutput::Str(s)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -106,6 +152,26 @@ pub struct StrConstrainedValidator {
     strip_whitespace: bool,
     to_lower: bool,
     to_upper: bool,
+    // one of "NFC", "NFD", "NFKC" or "NFKD"
+    normalize: Option<String>,
+    // if set, overrides whether numbers are converted to strings: by default only in lax mode
+    coerce_numbers_to_str: Option<bool>,
+    // other conversions lax mode doesn't make, `coerce_numbers_to_str` takes precedence for numbers
+    lax_coercions: LaxCoercions,
+    subclass: StrSubclass,
+    // intern output strings so repeated values share one object
+    intern: bool,
+}
+
+/// How instances of subclasses of `str` (e.g. `StrEnum` members) are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StrSubclass {
+    /// returned unchanged
+    Allow,
+    /// converted to a plain `str`
+    Convert,
+    /// rejected in strict mode, converted to a plain `str` in lax mode
+    Forbid,
 }
 
 impl Validator for StrConstrainedValidator {
@@ -113,24 +179,36 @@ impl Validator for StrConstrainedValidator {
         &'s self,
         py: Python<'data>,
         input: &'data dyn Input,
-        _extra: &Extra,
+        extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        let str = match self.strict {
-            true => input.strict_str()?,
-            false => input.lax_str()?,
+        let str = match self.coerce_numbers_to_str {
+            // ints and floats, bools are excluded by `strict_float`
+            Some(coerce) if input.strict_float().is_ok() => match coerce {
+                true => extra.lax_with_report(py, input, "str", |i| i.strict_str(), |i| i.lax_str())?,
+                false => return err_val_error!(input_value = InputValue::InputRef(input), kind = ErrorKind::StrType),
+            },
+            _ => match self.strict {
+                true => input.strict_str()?,
+                false => {
+                    self.lax_coercions.check(input, LaxType::Str)?;
+                    extra.lax_with_report(py, input, "str", |i| i.strict_str(), |i| i.lax_str())?
+                }
+            },
         };
-        self._validation_logic(py, input, str)
+        let str = self.check_subclass(py, input, str, self.strict)?;
+        self._validation_logic(py, input, str, extra)
     }
 
     fn validate_strict<'s, 'data>(
         &'s self,
         py: Python<'data>,
         input: &'data dyn Input,
-        _extra: &Extra,
+        extra: &Extra,
         _slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
-        self._validation_logic(py, input, input.strict_str()?)
+        let str = self.check_subclass(py, input, input.strict_str()?, true)?;
+        self._validation_logic(py, input, str, extra)
     }
 
     fn get_name(&self, _py: Python) -> String {
@@ -152,6 +230,22 @@ impl StrConstrainedValidator {
             schema_or_config(schema, config, "strip_whitespace", "str_strip_whitespace")?.unwrap_or(false);
         let to_lower: bool = schema_or_config(schema, config, "to_lower", "str_to_lower")?.unwrap_or(false);
         let to_upper: bool = schema_or_config(schema, config, "to_upper", "str_to_upper")?.unwrap_or(false);
+        let normalize: Option<String> = schema_or_config(schema, config, "normalize", "str_normalize")?;
+        if let Some(ref form) = normalize {
+            if !matches!(form.as_str(), "NFC" | "NFD" | "NFKC" | "NFKD") {
+                return py_error!(r#"Invalid normalize: "{}""#, form);
+            }
+        }
+        let coerce_numbers_to_str: Option<bool> =
+            schema_or_config(schema, config, "coerce_numbers_to_str", "str_coerce_numbers_to_str")?;
+        let subclass_str: Option<&str> = schema_or_config(schema, config, "subclass", "str_subclass")?;
+        let subclass = match subclass_str {
+            None | Some("allow") => StrSubclass::Allow,
+            Some("convert") => StrSubclass::Convert,
+            Some("forbid") => StrSubclass::Forbid,
+            Some(s) => return py_error!(r#"Invalid subclass: "{}""#, s),
+        };
+        let intern: bool = schema_or_config(schema, config, "intern", "str_intern")?.unwrap_or(false);
 
         Ok(Self {
             strict: is_strict(schema, config)?,
@@ -161,19 +255,54 @@ impl StrConstrainedValidator {
             strip_whitespace,
             to_lower,
             to_upper,
+            normalize,
+            coerce_numbers_to_str,
+            lax_coercions: LaxCoercions::from_schema(schema, config)?,
+            subclass,
+            intern,
         }
         .into())
     }
 
+    fn check_subclass<'data>(
+        &self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        either_str: EitherString<'data>,
+        strict: bool,
+    ) -> ValResult<'data, EitherString<'data>> {
+        match either_str {
+            EitherString::Py(py_str) if self.subclass != StrSubclass::Allow && !PyString::is_exact_type_of(py_str) => {
+                if strict && self.subclass == StrSubclass::Forbid {
+                    err_val_error!(input_value = InputValue::InputRef(input), kind = ErrorKind::StrSubclass)
+                } else {
+                    let str = py_str.to_str().map_err(as_internal)?;
+                    Ok(EitherString::Py(PyString::new(py, str)))
+                }
+            }
+            _ => Ok(either_str),
+        }
+    }
+
     fn _validation_logic<'s, 'data>(
         &'s self,
         py: Python<'data>,
         input: &'data dyn Input,
-        str: String,
+        either_str: EitherString<'data>,
+        extra: &Extra,
     ) -> ValResult<'data, PyObject> {
-        let mut str = str;
+        extra.check_str(input, &either_str)?;
+        let str = either_str.as_str()?;
+        let normalized = match self.normalize {
+            // ASCII is unchanged by any normalization form
+            Some(ref form) if !str.is_ascii() => Cow::Owned(normalize(py, form, str).map_err(as_internal)?),
+            _ => Cow::Borrowed(str),
+        };
+        let str = normalized.as_ref();
+
+        // lengths are in characters, the byte length is an upper bound so usually they needn't be counted
         if let Some(min_length) = self.min_length {
-            if str.len() < min_length {
+            if str.len() < min_length || str.chars().count() < min_length {
                 // return py_error!("{} is shorter than {}", str, min_length);
                 return err_val_error!(
                     input_value = InputValue::InputRef(input),
@@ -183,7 +312,7 @@ impl StrConstrainedValidator {
             }
         }
         if let Some(max_length) = self.max_length {
-            if str.len() > max_length {
+            if str.len() > max_length && str.chars().count() > max_length {
                 return err_val_error!(
                     input_value = InputValue::InputRef(input),
                     kind = ErrorKind::StrTooLong,
@@ -192,7 +321,7 @@ impl StrConstrainedValidator {
             }
         }
         if let Some(pattern) = &self.pattern {
-            if !pattern.is_match(&str) {
+            if !pattern.is_match(str) {
                 return err_val_error!(
                     input_value = InputValue::InputRef(input),
                     kind = ErrorKind::StrPatternMismatch,
@@ -201,20 +330,49 @@ impl StrConstrainedValidator {
             }
         }
 
-        if self.strip_whitespace {
-            str = str.trim().to_string();
-        }
+        let str = match self.strip_whitespace {
+            true => str.trim(),
+            false => str,
+        };
 
-        if self.to_lower {
-            str = str.to_lowercase()
+        let output = if self.to_lower {
+            Cow::Owned(str.to_lowercase())
         } else if self.to_upper {
-            str = str.to_uppercase()
+            Cow::Owned(str.to_uppercase())
+        } else {
+            Cow::Borrowed(str)
+        };
+        // if nothing changed, return the input string itself rather than creating a new one
+        let py_str = match output == either_str.as_str()? {
+            true => either_str.into_py(py),
+            false => PyString::new(py, &output).into_py(py),
+        };
+        match self.intern {
+            true => Ok(intern_str(py, py_str)),
+            false => Ok(py_str),
         }
-        let py_str = PyString::new(py, &str);
-        ValResult::Ok(py_str.into_py(py))
     }
 }
 
+/// Swap `py_str` for the interned string with the same value, subclasses of `str` are returned unchanged
+fn intern_str(py: Python, py_str: PyObject) -> PyObject {
+    let mut ptr = py_str.into_ptr();
+    unsafe {
+        // Safety: `ptr` is an owned reference to a str, `PyUnicode_InternInPlace` replaces it with an owned
+        // reference to the interned string
+        ffi::PyUnicode_InternInPlace(&mut ptr);
+        PyObject::from_owned_ptr(py, ptr)
+    }
+}
+
+fn normalize(py: Python, form: &str, str: &str) -> PyResult<String> {
+    let unicodedata = py.import(intern!(py, "unicodedata"))?;
+    unicodedata
+        .getattr(intern!(py, "normalize"))?
+        .call1((form, str))?
+        .extract()
+}
+
 fn build_regex(pattern: &str) -> PyResult<Regex> {
     match Regex::new(pattern) {
         Ok(r) => Ok(r),
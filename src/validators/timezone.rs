@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::build_tools::{is_strict, py_error, SchemaDict};
+use crate::errors::{as_internal, err_val_error, ErrorKind, InputValue, ValResult};
+use crate::input::Input;
+
+use super::{BuildContext, BuildValidator, CombinedValidator, Extra, Validator};
+
+/// Validates IANA timezone names (e.g. "Europe/London") using the database available to `zoneinfo`, in lax mode
+/// names are matched case-insensitively
+#[derive(Clone)]
+pub struct TimezoneNameValidator {
+    strict: bool,
+    /// lower case name -> canonical name
+    names: HashMap<String, String>,
+    /// `zoneinfo.ZoneInfo` if `return_zoneinfo` is set
+    zoneinfo_class: Option<PyObject>,
+}
+
+impl BuildValidator for TimezoneNameValidator {
+    const EXPECTED_TYPE: &'static str = "timezone-name";
+
+    fn build(
+        schema: &PyDict,
+        config: Option<&PyDict>,
+        _build_context: &mut BuildContext,
+    ) -> PyResult<CombinedValidator> {
+        let py = schema.py();
+        let zoneinfo = match py.import("zoneinfo").or_else(|_| py.import("backports.zoneinfo")) {
+            Ok(zoneinfo) => zoneinfo,
+            Err(_) => return py_error!("timezone-name requires zoneinfo (python 3.9+ or backports.zoneinfo)"),
+        };
+        let names = zoneinfo
+            .call_method0("available_timezones")?
+            .iter()?
+            .map(|name| {
+                let name: String = name?.extract()?;
+                Ok((name.to_lowercase(), name))
+            })
+            .collect::<PyResult<HashMap<String, String>>>()?;
+        let zoneinfo_class = match schema.get_as("return_zoneinfo")?.unwrap_or(false) {
+            true => Some(zoneinfo.getattr("ZoneInfo")?.into_py(py)),
+            false => None,
+        };
+        Ok(Self {
+            strict: is_strict(schema, config)?,
+            names,
+            zoneinfo_class,
+        }
+        .into())
+    }
+}
+
+impl Validator for TimezoneNameValidator {
+    fn validate<'data>(
+        &self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        if self.strict || extra.overrides.strict {
+            return self.validate_strict(py, input, extra, slots);
+        }
+        let either_name = extra.lax_with_report(py, input, "str", |i| i.strict_str(), |i| i.lax_str())?;
+        match self.names.get(&either_name.as_str()?.to_lowercase()) {
+            Some(canonical) => self.output(py, canonical),
+            None => err_val_error!(
+                input_value = InputValue::InputRef(input),
+                kind = ErrorKind::TimezoneName
+            ),
+        }
+    }
+
+    fn validate_strict<'data>(
+        &self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        _extra: &Extra,
+        _slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        let either_name = input.strict_str()?;
+        let name = either_name.as_str()?;
+        match self.names.get(&name.to_lowercase()) {
+            Some(canonical) if canonical == name => self.output(py, canonical),
+            _ => err_val_error!(
+                input_value = InputValue::InputRef(input),
+                kind = ErrorKind::TimezoneName
+            ),
+        }
+    }
+
+    fn get_name(&self, _py: Python) -> String {
+        Self::EXPECTED_TYPE.to_string()
+    }
+}
+
+impl TimezoneNameValidator {
+    fn output<'data>(&self, py: Python<'data>, name: &str) -> ValResult<'data, PyObject> {
+        match self.zoneinfo_class {
+            Some(ref zoneinfo_class) => zoneinfo_class.call1(py, (name,)).map_err(as_internal),
+            None => Ok(name.into_py(py)),
+        }
+    }
+}
+
+// the list of names is long, so it's omitted
+impl fmt::Debug for TimezoneNameValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimezoneNameValidator")
+            .field("strict", &self.strict)
+            .field("names", &self.names.len())
+            .field("zoneinfo_class", &self.zoneinfo_class)
+            .finish()
+    }
+}
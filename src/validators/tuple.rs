@@ -0,0 +1,211 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyTuple};
+use smallvec::smallvec;
+
+use crate::build_tools::{is_strict, py_error, SchemaDict};
+use crate::errors::{context, err_val_error, ErrorKind, InputValue, LocItem, ValError, ValLineError};
+use crate::input::{GenericSequence, Input, NumericArray, SequenceLenIter, StrictCoercions};
+
+use super::array::validate_array_items;
+use super::list::construct_items;
+use super::{build_validator, BuildContext, BuildValidator, CombinedValidator, Extra, ValResult, Validator};
+
+/// Tuples are described by `prefix_items`, a schema for each leading position, and `items`, the schema for
+/// any further items, e.g. `tuple[int, str, *tuple[float, ...]]` is
+/// `{'type': 'tuple', 'prefix_items': ['int', 'str'], 'items': 'float'}`;
+/// without `items` no further items are allowed unless `prefix_items` is also omitted
+#[derive(Debug, Clone)]
+pub struct TupleValidator {
+    strict: bool,
+    strict_coercions: StrictCoercions,
+    prefix_validators: Vec<CombinedValidator>,
+    item_validator: Option<Box<CombinedValidator>>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+}
+
+impl BuildValidator for TupleValidator {
+    const EXPECTED_TYPE: &'static str = "tuple";
+
+    fn build(
+        schema: &PyDict,
+        config: Option<&PyDict>,
+        build_context: &mut BuildContext,
+    ) -> PyResult<CombinedValidator> {
+        let prefix_validators = match schema.get_as::<&PyList>("prefix_items")? {
+            Some(prefix_items) => prefix_items
+                .iter()
+                .map(|item| Ok(build_validator(item, config, build_context)?.0))
+                .collect::<PyResult<Vec<CombinedValidator>>>()?,
+            None => Vec::new(),
+        };
+        let item_validator = match schema.get_item("items") {
+            Some(d) => Some(Box::new(build_validator(d, config, build_context)?.0)),
+            None => None,
+        };
+
+        let prefix_len = prefix_validators.len();
+        let min_items = match schema.get_as::<usize>("min_items")? {
+            Some(min_items) => Some(min_items.max(prefix_len)),
+            None if prefix_len > 0 => Some(prefix_len),
+            None => None,
+        };
+        let mut max_items: Option<usize> = schema.get_as("max_items")?;
+        if item_validator.is_none() && prefix_len > 0 {
+            max_items = Some(max_items.map_or(prefix_len, |max_items| max_items.min(prefix_len)));
+        }
+        if let Some(max_items) = max_items {
+            if max_items < prefix_len {
+                return py_error!("max_items ({}) is less than the number of prefix_items", max_items);
+            }
+        }
+
+        Ok(Self {
+            strict: is_strict(schema, config)?,
+            strict_coercions: StrictCoercions::from_schema(schema, config)?,
+            prefix_validators,
+            item_validator,
+            min_items,
+            max_items,
+        }
+        .into())
+    }
+}
+
+impl Validator for TupleValidator {
+    fn validate<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        let tuple = match self.strict || extra.overrides.strict {
+            true => input.strict_tuple_coerced(&self.strict_coercions)?,
+            false => match input.lax_numeric_array() {
+                Some(array) => return self._validate_array(py, input, array, extra, slots),
+                None => input.lax_tuple()?,
+            },
+        };
+        self._validation_logic(py, input, tuple, extra, slots)
+    }
+
+    fn validate_strict<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        let tuple = input.strict_tuple_coerced(&self.strict_coercions)?;
+        self._validation_logic(py, input, tuple, extra, slots)
+    }
+
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        let get_validator = |index| self.prefix_validators.get(index).or(self.item_validator.as_deref());
+        match construct_items(py, input, get_validator, slots)? {
+            Some(output) => Ok(PyTuple::new(py, &output).into_py(py)),
+            None => Ok(input.into_py(py)),
+        }
+    }
+
+    fn get_name(&self, py: Python) -> String {
+        let mut names: Vec<String> = self.prefix_validators.iter().map(|v| v.get_name(py)).collect();
+        if let Some(ref v) = self.item_validator {
+            names.push(format!("*{}", v.get_name(py)));
+        }
+        match names.is_empty() {
+            true => Self::EXPECTED_TYPE.to_string(),
+            false => format!("{}-{}", Self::EXPECTED_TYPE, names.join(",")),
+        }
+    }
+}
+
+impl TupleValidator {
+    fn check_length<'data>(&self, input: &'data dyn Input, length: usize) -> ValResult<'data, ()> {
+        if let Some(min_length) = self.min_items {
+            if length < min_length {
+                return err_val_error!(
+                    input_value = InputValue::InputRef(input),
+                    kind = ErrorKind::TupleTooShort,
+                    context = context!("min_length" => min_length)
+                );
+            }
+        }
+        if let Some(max_length) = self.max_items {
+            if length > max_length {
+                return err_val_error!(
+                    input_value = InputValue::InputRef(input),
+                    kind = ErrorKind::TupleTooLong,
+                    context = context!("max_length" => max_length)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn _validate_array<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        array: NumericArray,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        self.check_length(input, array.len())?;
+        let _depth = extra.enter_container(input, array.len())?;
+        let validator_for = |index| match self.prefix_validators.get(index) {
+            Some(validator) => Some(validator),
+            None => self.item_validator.as_deref(),
+        };
+        let output = validate_array_items(py, &array, validator_for, extra, slots)?;
+        Ok(PyTuple::new(py, &output).into_py(py))
+    }
+
+    fn _validation_logic<'s, 'data>(
+        &'s self,
+        py: Python<'data>,
+        input: &'data dyn Input,
+        tuple: GenericSequence<'data>,
+        extra: &Extra,
+        slots: &'data [CombinedValidator],
+    ) -> ValResult<'data, PyObject> {
+        let length = tuple.generic_len();
+        self.check_length(input, length)?;
+
+        let _depth = extra.enter_container(input, length)?;
+        let mut output: Vec<PyObject> = Vec::with_capacity(length);
+        let mut errors: Vec<ValLineError> = Vec::new();
+        for (index, item) in tuple.generic_iter() {
+            // the length checks above mean items beyond the prefix only exist if they're allowed
+            let validator = match self.prefix_validators.get(index) {
+                Some(validator) => validator,
+                None => match self.item_validator {
+                    Some(ref validator) => validator,
+                    None => {
+                        output.push(item.to_py(py));
+                        continue;
+                    }
+                },
+            };
+            let checkpoint = extra.checkpoint();
+            let result = validator.validate(py, item, extra, slots);
+            extra.prefix_location(checkpoint, &index);
+            match result {
+                Ok(item) => output.push(item),
+                Err(ValError::LineErrors(line_errors)) => {
+                    let loc = smallvec![LocItem::I(index)];
+                    errors.extend(line_errors.into_iter().map(|err| err.with_prefix_location(&loc)));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        if !errors.is_empty() {
+            Err(ValError::LineErrors(errors))
+        } else if extra.check_only {
+            Ok(py.None())
+        } else {
+            Ok(PyTuple::new(py, &output).into_py(py))
+        }
+    }
+}
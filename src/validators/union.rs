@@ -1,15 +1,28 @@
+use std::collections::HashMap;
+
+use pyo3::conversion::AsPyPointer;
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
+use smallvec::smallvec;
 
-use crate::build_tools::SchemaDict;
-use crate::errors::{LocItem, ValError, ValLineError};
-use crate::input::Input;
+use crate::build_tools::{py_error, schema_or_config, SchemaDict};
+use crate::errors::{val_line_error, ErrorKind, InputValue, LocItem, ValError, ValLineError};
+use crate::input::{Input, InputSource};
 
+use super::literal::LiteralLookup;
 use super::{build_validator, BuildContext, BuildValidator, CombinedValidator, Extra, ValResult, Validator};
 
 #[derive(Debug, Clone)]
 pub struct UnionValidator {
     choices: Vec<CombinedValidator>,
+    // location item prefixed to each choice's errors, see `choice_locations`
+    choice_locations: Vec<String>,
+    error_strategy: ErrorStrategy,
+    custom_error: Option<CustomError>,
+    // set when all choices are literals, the choices are then only used to build errors
+    literal_lookup: Option<LiteralLookup>,
+    // set when all choices are model classes, maps the id of each class to the index of its choice
+    class_lookup: Option<HashMap<usize, usize>>,
 }
 
 impl BuildValidator for UnionValidator {
@@ -20,12 +33,22 @@ impl BuildValidator for UnionValidator {
         config: Option<&PyDict>,
         build_context: &mut BuildContext,
     ) -> PyResult<CombinedValidator> {
-        let choices: Vec<CombinedValidator> = schema
-            .get_as_req::<&PyList>("choices")?
-            .iter()
-            .map(|choice| build_validator(choice, config, build_context).map(|result| result.0))
-            .collect::<PyResult<Vec<CombinedValidator>>>()?;
-        Ok(Self { choices }.into())
+        let mut choices: Vec<CombinedValidator> = Vec::new();
+        let mut tags: Vec<Option<String>> = Vec::new();
+        for choice in schema.get_as_req::<&PyList>("choices")?.iter() {
+            let (validator, choice_dict) = build_validator(choice, config, build_context)?;
+            choices.push(validator);
+            tags.push(choice_dict.get_as("tag")?);
+        }
+        Ok(Self {
+            literal_lookup: LiteralLookup::from_choices(&choices),
+            class_lookup: class_lookup(&choices),
+            choice_locations: choice_locations(schema.py(), &choices, tags)?,
+            choices,
+            error_strategy: ErrorStrategy::from_schema(schema, config)?,
+            custom_error: CustomError::from_schema(schema)?,
+        }
+        .into())
     }
 }
 
@@ -37,33 +60,210 @@ impl Validator for UnionValidator {
         extra: &Extra,
         slots: &'data [CombinedValidator],
     ) -> ValResult<'data, PyObject> {
+        if let Some(ref literal_lookup) = self.literal_lookup {
+            if literal_lookup.contains(input)? {
+                return Ok(input.to_py(py));
+            }
+        }
+
+        // models inside a union mustn't drop invalid fields, otherwise the first model would always match
+        let extra = &Extra {
+            collected_errors: None,
+            ..*extra
+        };
+
+        if let Some(ref class_lookup) = self.class_lookup {
+            if input.source() == InputSource::Python {
+                let input_obj = input.to_py(py);
+                let type_id = input_obj.as_ref(py).get_type().as_ptr() as usize;
+                if let Some(&index) = class_lookup.get(&type_id) {
+                    return self.choices[index].validate_strict(py, input, extra, slots);
+                }
+            }
+        }
+
         // 1st pass: check if the value is an exact instance of one of the Union types
-        if let Some(res) = self
-            .choices
-            .iter()
-            .map(|validator| validator.validate_strict(py, input, extra, slots))
-            .find(ValResult::is_ok)
-        {
-            return res;
+        let checkpoint = extra.checkpoint();
+        for validator in &self.choices {
+            match validator.validate_strict(py, input, extra, slots) {
+                Ok(output) => return Ok(output),
+                // warnings from choices which didn't match are irrelevant
+                Err(_) => extra.discard(checkpoint),
+            }
         }
 
-        let mut errors: Vec<ValLineError> = Vec::with_capacity(self.choices.len());
+        let mut choice_errors: Vec<(&String, Vec<ValLineError>)> = Vec::with_capacity(self.choices.len());
 
         // 3rd pass: check if the value can be coerced into one of the Union types
-        for validator in &self.choices {
+        for (validator, location) in self.choices.iter().zip(&self.choice_locations) {
             let line_errors = match validator.validate(py, input, extra, slots) {
                 Err(ValError::LineErrors(line_errors)) => line_errors,
                 otherwise => return otherwise,
             };
+            extra.discard(checkpoint);
+            choice_errors.push((location, line_errors));
+        }
 
-            let loc = vec![LocItem::S(validator.get_name(py))];
-            errors.extend(line_errors.into_iter().map(|err| err.with_prefix_location(&loc)));
+        if self.error_strategy == ErrorStrategy::BestMatch {
+            // `min_by_key` returns the first of equal choices, so earlier choices win ties
+            if let Some(best_index) = choice_errors
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (_, line_errors))| match_distance(line_errors))
+                .map(|(index, _)| index)
+            {
+                choice_errors = vec![choice_errors.swap_remove(best_index)];
+            }
         }
 
+        let mut errors = Vec::new();
+        if let Some(ref custom_error) = self.custom_error {
+            errors.push(custom_error.line_error(input));
+            if custom_error.replace {
+                return Err(ValError::LineErrors(errors));
+            }
+        }
+        // errors are always in the order choices are defined
+        errors.extend(choice_errors.into_iter().flat_map(|(location, line_errors)| {
+            let loc = smallvec![LocItem::Choice(location.clone())];
+            line_errors.into_iter().map(move |err| err.with_prefix_location(&loc))
+        }));
         Err(ValError::LineErrors(errors))
     }
 
+    /// The choice is the one validation would use, failing that the closest match (as with `best_match`) since
+    /// trusted input may still omit fields; if every choice rejects the input outright it's used unchanged
+    fn construct(&self, py: Python, input: &PyAny, slots: &[CombinedValidator]) -> PyResult<PyObject> {
+        let extra = Extra::default();
+        if let Some(validator) = self
+            .choices
+            .iter()
+            .find(|validator| validator.validate_strict(py, input, &extra, slots).is_ok())
+        {
+            return validator.construct(py, input, slots);
+        }
+        let mut best_match: Option<(&CombinedValidator, usize)> = None;
+        for validator in &self.choices {
+            match validator.validate(py, input, &extra, slots) {
+                Ok(_) => return validator.construct(py, input, slots),
+                Err(ValError::LineErrors(line_errors)) => match (match_distance(&line_errors), best_match) {
+                    ((true, _), _) => (),
+                    ((false, distance), Some((_, best_distance))) if distance >= best_distance => (),
+                    ((false, distance), _) => best_match = Some((validator, distance)),
+                },
+                Err(ValError::InternalErr(err)) => return Err(err),
+            }
+        }
+        match best_match {
+            Some((validator, _)) => validator.construct(py, input, slots),
+            None => Ok(input.into_py(py)),
+        }
+    }
+
     fn get_name(&self, _py: Python) -> String {
         Self::EXPECTED_TYPE.to_string()
     }
 }
+
+/// Errors from each choice are prefixed with the choice's `tag` if set, otherwise with its name; where several
+/// choices have the same name (e.g. two models) the index of the choice is added so each is identifiable, e.g. `Model[1]`
+fn choice_locations(py: Python, choices: &[CombinedValidator], tags: Vec<Option<String>>) -> PyResult<Vec<String>> {
+    let names: Vec<String> = choices.iter().map(|choice| choice.get_name(py)).collect();
+    let mut locations = Vec::with_capacity(choices.len());
+    for (index, (name, tag)) in names.iter().zip(tags).enumerate() {
+        let location = match tag {
+            Some(tag) => tag,
+            None if names.iter().filter(|other| *other == name).count() > 1 => format!("{}[{}]", name, index),
+            None => name.clone(),
+        };
+        if locations.contains(&location) {
+            return py_error!(r#"Duplicate union tag: "{}""#, location);
+        }
+        locations.push(location);
+    }
+    Ok(locations)
+}
+
+/// Instances of a model class are only accepted in strict mode by the choice for that class, so if every choice is a
+/// model class the choice for an instance can be found directly, the first choice for a class is used as usual
+fn class_lookup(choices: &[CombinedValidator]) -> Option<HashMap<usize, usize>> {
+    let mut lookup = HashMap::with_capacity(choices.len());
+    for (index, choice) in choices.iter().enumerate() {
+        match choice {
+            CombinedValidator::ModelClass(validator) => {
+                lookup.entry(validator.class_id()).or_insert(index);
+            }
+            _ => return None,
+        }
+    }
+    Some(lookup)
+}
+
+/// How errors are reported when no choice matches
+#[derive(Debug, Clone, PartialEq)]
+enum ErrorStrategy {
+    /// errors from every choice are included, this is the default
+    All,
+    /// only errors from the choice which came closest to matching are included, see `match_distance`
+    BestMatch,
+}
+
+impl ErrorStrategy {
+    fn from_schema(schema: &PyDict, config: Option<&PyDict>) -> PyResult<Self> {
+        let error_strategy: Option<&str> = schema_or_config(schema, config, "error_strategy", "union_error_strategy")?;
+        match error_strategy {
+            Some("all") | None => Ok(Self::All),
+            Some("best_match") => Ok(Self::BestMatch),
+            Some(s) => py_error!(r#"Invalid error_strategy: "{}""#, s),
+        }
+    }
+}
+
+/// A single error for the union as a whole, e.g. "Value must be either a Card or a BankAccount", which replaces the
+/// errors from each choice, or with `custom_error_mode: 'prepend'` comes before them
+#[derive(Debug, Clone)]
+struct CustomError {
+    message: String,
+    title: Option<String>,
+    replace: bool,
+}
+
+impl CustomError {
+    fn from_schema(schema: &PyDict) -> PyResult<Option<Self>> {
+        let title: Option<String> = schema.get_as("custom_error_title")?;
+        let mode: Option<&str> = schema.get_as("custom_error_mode")?;
+        let message: String = match schema.get_as("custom_error_message")? {
+            Some(message) => message,
+            None if title.is_some() || mode.is_some() => {
+                return py_error!("'custom_error_title' and 'custom_error_mode' require 'custom_error_message'")
+            }
+            None => return Ok(None),
+        };
+        let replace = match mode {
+            Some("replace") | None => true,
+            Some("prepend") => false,
+            Some(s) => return py_error!(r#"Invalid custom_error_mode: "{}""#, s),
+        };
+        Ok(Some(Self {
+            message,
+            title,
+            replace,
+        }))
+    }
+
+    fn line_error<'a>(&self, input: &'a dyn Input) -> ValLineError<'a> {
+        val_line_error!(
+            input_value = InputValue::InputRef(input),
+            kind = ErrorKind::UnionError,
+            message = Some(self.message.clone()),
+            title = self.title.clone()
+        )
+    }
+}
+
+/// How far a choice was from matching: choices where the input itself was rejected (e.g. the wrong type) are worst,
+/// otherwise fewer errors is better
+fn match_distance(line_errors: &[ValLineError]) -> (bool, usize) {
+    let input_rejected = line_errors.iter().any(|err| err.location.is_empty());
+    (input_rejected, line_errors.len())
+}
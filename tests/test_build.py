@@ -1,8 +1,11 @@
 import pickle
+import re
+from concurrent.futures import ThreadPoolExecutor
+from copy import deepcopy
 
 import pytest
 
-from pydantic_core import SchemaError, SchemaValidator
+from pydantic_core import SchemaError, SchemaValidator, ValidationError, register_schema_type
 
 
 def test_build_error_type():
@@ -13,7 +16,8 @@ def test_build_error_type():
 def test_build_error_internal():
     msg = (
         'Error building "str" validator:\n'
-        '  TypeError: \'str\' object cannot be interpreted as an integer'  # noqa Q003
+        '  SchemaError: Invalid "min_length": '
+        'TypeError: \'str\' object cannot be interpreted as an integer'  # noqa Q003
     )
     with pytest.raises(SchemaError, match=msg):
         SchemaValidator({'type': 'str', 'min_length': 'xxx', 'title': 'TestModel'})
@@ -24,12 +28,39 @@ def test_build_error_deep():
         'Error building "model" validator:\n'
         '  SchemaError: Key "age":\n'
         '  SchemaError: Error building "int" validator:\n'
-        '  TypeError: \'str\' object cannot be interpreted as an integer'  # noqa Q003
+        '  SchemaError: Invalid "fields.age.ge": '
+        'TypeError: \'str\' object cannot be interpreted as an integer'  # noqa Q003
     )
     with pytest.raises(SchemaError, match=msg):
         SchemaValidator({'title': 'MyTestModel', 'type': 'model', 'fields': {'age': {'type': 'int', 'ge': 'not-int'}}})
 
 
+def test_build_error_path():
+    with pytest.raises(SchemaError) as exc_info:
+        SchemaValidator({'type': 'list', 'items': {'type': 'union', 'choices': ['int', {'type': 'str', 'strict': 1}]}})
+    assert exc_info.value.args[0].endswith(
+        'SchemaError: Invalid "items.choices.1.strict": TypeError: \'int\' object cannot be converted to \'PyBool\''
+    )
+
+
+def test_build_error_missing_path():
+    with pytest.raises(SchemaError) as exc_info:
+        SchemaValidator({'type': 'dict', 'values': {'type': 'tuple', 'prefix_items': [{'type': 'union'}]}})
+    assert exc_info.value.args[0].endswith('SchemaError: "values.prefix_items.0.choices" is required')
+
+
+def test_build_error_class_path():
+    class Foobar:
+        @classmethod
+        def __get_pydantic_core_schema__(cls):
+            return {'type': 'list', 'items': {'type': 'int', 'gt': 'x'}}
+
+    # the path is relative to the schema returned by the class
+    with pytest.raises(SchemaError) as exc_info:
+        SchemaValidator({'type': 'optional', 'schema': Foobar})
+    assert 'SchemaError: Invalid "items.gt": ' in exc_info.value.args[0]
+
+
 def test_schema_as_string():
     v = SchemaValidator('bool')
     assert v.validate_python('tRuE') is True
@@ -38,9 +69,22 @@ def test_schema_as_string():
 def test_schema_wrong_type():
     with pytest.raises(SchemaError) as exc_info:
         SchemaValidator(1)
-    assert exc_info.value.args[0] == (
-        "Schema build error:\n  TypeError: 'int' object cannot be converted to 'PyString'"
+    assert exc_info.value.args[0] == 'Schema must be a dict or a string, got int'
+
+
+def test_build_error_unknown_type():
+    with pytest.raises(SchemaError) as exc_info:
+        SchemaValidator({'type': 'list', 'items': 'foobar'})
+    msg = exc_info.value.args[0]
+    assert msg.startswith(
+        'Error building "list" validator:\n  SchemaError: Unknown schema type: "foobar", expected one of:'
     )
+    assert '"int"' in msg and '"model"' in msg
+
+
+def test_build_error_missing_type():
+    with pytest.raises(SchemaError, match='^"type" is required$'):
+        SchemaValidator({'title': 'MyTestModel'})
 
 
 @pytest.mark.parametrize('pickle_protocol', range(1, pickle.HIGHEST_PROTOCOL + 1))
@@ -53,6 +97,35 @@ def test_pickle(pickle_protocol: int) -> None:
     assert repr(v1) == repr(v2)
 
 
+def upper_function(input_value, **kwargs):
+    return input_value.upper()
+
+
+@pytest.mark.parametrize('pickle_protocol', range(1, pickle.HIGHEST_PROTOCOL + 1))
+def test_pickle_model(pickle_protocol: int) -> None:
+    v1 = SchemaValidator(
+        {
+            'type': 'model',
+            'name': 'MyModel',
+            'fields': {
+                'name': {'type': 'function', 'mode': 'after', 'function': upper_function, 'schema': 'str'},
+                'tags': {'type': 'list', 'items': 'int'},
+            },
+        }
+    )
+    v2 = pickle.loads(pickle.dumps(v1, protocol=pickle_protocol))
+    assert v2.validate_python({'name': 'foo', 'tags': ['1', 2]}) == ({'name': 'FOO', 'tags': [1, 2]}, {'name', 'tags'})
+    assert repr(v1) == repr(v2)
+
+
+def test_deepcopy():
+    v1 = SchemaValidator({'type': 'list', 'items': 'int'})
+    v2 = deepcopy(v1)
+    assert v2 is not v1
+    assert v2.validate_python(['1', 2]) == [1, 2]
+    assert repr(v1) == repr(v2)
+
+
 def test_schema_recursive_error():
     schema = {'type': 'union', 'choices': []}
     schema['choices'].append(schema)
@@ -66,3 +139,116 @@ def test_not_schema_recursive_error():
     schema = {'type': 'model', 'fields': {f'f_{i}': {'type': 'optional', 'schema': 'int'} for i in range(101)}}
     v = SchemaValidator(schema)
     assert repr(v).count('ModelField') == 101
+
+
+def test_shared_between_threads():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'name': {'type': 'function', 'mode': 'after', 'function': upper_function, 'schema': 'str'},
+                'tags': {'type': 'list', 'items': 'int'},
+            },
+        }
+    )
+
+    def validate(i):
+        return v.validate_python({'name': f'foo{i}', 'tags': [i, str(i)], f'extra{i}': 1}, warnings='collect')
+
+    with ThreadPoolExecutor(max_workers=4) as pool:
+        results = list(pool.map(validate, range(50)))
+    # warnings (like other per-validation state) aren't shared between threads
+    for i, (output, warnings) in enumerate(results):
+        assert output == ({'name': f'FOO{i}', 'tags': [i, i]}, {'name', 'tags'})
+        assert warnings == [{'loc': [f'extra{i}'], 'message': 'Extra field ignored'}]
+
+
+def test_debug_repr_counters():
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'int', 'gt': 0}}, counters=True)
+    assert 'count: 0' in v.debug_repr()
+    assert v.validate_python([1, 2, 3]) == [1, 2, 3]
+    debug_repr = v.debug_repr()
+    assert re.search(r'count: 1,\s+validator: List', debug_repr)
+    assert re.search(r'count: 3,\s+validator: ConstrainedInt', debug_repr)
+
+    v2 = pickle.loads(pickle.dumps(v))
+    assert 'count: 0' in v2.debug_repr()
+
+
+def test_debug_repr_slots():
+    v = SchemaValidator(
+        {
+            'type': 'recursive-container',
+            'name': 'Branch',
+            'schema': {
+                'type': 'model',
+                'fields': {'branches': {'type': 'list', 'items': {'type': 'recursive-ref', 'name': 'Branch'}}},
+            },
+        }
+    )
+    assert 'Counted' not in v.debug_repr()
+    assert re.search(r'slot 0: Model\(', v.debug_repr())
+    assert 'slot 0' not in repr(v)
+
+
+def test_identical_models_shared():
+    def model(default):
+        return {'type': 'model', 'fields': {'x': 'int', 'y': {'type': 'str', 'default': default}}}
+
+    v = SchemaValidator(
+        {'type': 'model', 'fields': {'a': model('y'), 'b': model('y'), 'c': model('y'), 'd': model('z')}}
+    )
+    # the first "a" model is built in place, "b" and "c" share the validator in slot 0, "d" differs
+    assert repr(v).count('SharedValidator') == 2
+    assert re.search(r'slot 0: Model\(', v.debug_repr())
+    assert 'slot 1' not in v.debug_repr()
+    assert v.validate_python({'a': {'x': 1}, 'b': {'x': '2'}, 'c': {'x': 3, 'y': 'c'}, 'd': {'x': 4}}) == (
+        {
+            'a': ({'x': 1, 'y': 'y'}, {'x'}),
+            'b': ({'x': 2, 'y': 'y'}, {'x'}),
+            'c': ({'x': 3, 'y': 'c'}, {'x', 'y'}),
+            'd': ({'x': 4, 'y': 'z'}, {'x'}),
+        },
+        {'a', 'b', 'c', 'd'},
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': {'x': 1}, 'b': {'x': 'x'}, 'c': {}, 'd': {'x': 4}})
+    assert [e['loc'] for e in exc_info.value.errors()] == [['b', 'x'], ['c', 'x']]
+
+
+def test_identical_models_not_shared():
+    model = {'type': 'model', 'fields': {'x': {'type': 'list', 'default': []}}}
+    v = SchemaValidator({'type': 'model', 'fields': {'a': deepcopy(model), 'b': deepcopy(model)}})
+    # mutable defaults are returned without copying, so models with equal defaults aren't shared
+    assert 'Shared' not in repr(v)
+
+    v = SchemaValidator({'type': 'model', 'fields': {'a': model, 'b': model}}, counters=True)
+    assert 'Shared' not in repr(v)
+
+
+def test_identical_schemas_built_once():
+    calls = []
+
+    def build_positive(schema, config):
+        calls.append(schema)
+        return {'type': 'int', 'gt': 0}
+
+    register_schema_type('test-positive', build_positive)
+    model = {'type': 'model', 'fields': {'x': 'test-positive', 'y': {'type': 'list', 'items': 'test-positive'}}}
+    v = SchemaValidator({'type': 'model', 'fields': {'a': model, 'b': deepcopy(model), 'c': 'test-positive'}})
+    # identical schemas reuse the validator built for the first, "b" via a slot since it's a model
+    assert calls == [{'type': 'test-positive'}]
+    assert repr(v).count('SharedValidator') == 1
+    assert v.validate_python({'a': {'x': 1, 'y': [2]}, 'b': {'x': 3, 'y': []}, 'c': 4}) == (
+        {'a': ({'x': 1, 'y': [2]}, {'x', 'y'}), 'b': ({'x': 3, 'y': []}, {'x', 'y'}), 'c': 4},
+        {'a', 'b', 'c'},
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': {'x': 1, 'y': [0]}, 'b': {'x': -1, 'y': []}, 'c': 0})
+    assert [e['loc'] for e in exc_info.value.errors()] == [['a', 'y', 0], ['b', 'x'], ['c']]
+
+    core_schema = v.core_schema()
+    positive = {'type': 'int', 'gt': 0}
+    assert core_schema['fields']['a'] == core_schema['fields']['b']
+    assert core_schema['fields']['a']['fields']['y'] == {'type': 'list', 'items': positive}
+    assert core_schema['fields']['c'] == positive
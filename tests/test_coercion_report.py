@@ -0,0 +1,92 @@
+import pytest
+
+from pydantic_core import SchemaValidator
+
+
+@pytest.fixture
+def model_validator():
+    return SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': 'int',
+                'b': 'str',
+                'c': {'type': 'float', 'ge': 0},
+                'd': 'bool',
+                'items': {'type': 'list', 'items': 'int'},
+            },
+        }
+    )
+
+
+def test_no_coercions(model_validator):
+    value, coercions = model_validator.validate_python(
+        {'a': 1, 'b': 'x', 'c': 1.5, 'd': True, 'items': [1, 2]}, report_coercions=True
+    )
+    assert value == ({'a': 1, 'b': 'x', 'c': 1.5, 'd': True, 'items': [1, 2]}, {'a', 'b', 'c', 'd', 'items'})
+    assert coercions == []
+
+
+def test_coercions_python(model_validator):
+    value, coercions = model_validator.validate_python(
+        {'a': '1', 'b': b'x', 'c': '1.5', 'd': 'yes', 'items': [1, 2.0, '3']}, report_coercions=True
+    )
+    assert value == ({'a': 1, 'b': 'x', 'c': 1.5, 'd': True, 'items': [1, 2, 3]}, {'a', 'b', 'c', 'd', 'items'})
+    assert coercions == [
+        {'loc': ['a'], 'from_type': 'str', 'to_type': 'int'},
+        {'loc': ['b'], 'from_type': 'bytes', 'to_type': 'str'},
+        {'loc': ['c'], 'from_type': 'str', 'to_type': 'float'},
+        {'loc': ['d'], 'from_type': 'str', 'to_type': 'bool'},
+        {'loc': ['items', 1], 'from_type': 'float', 'to_type': 'int'},
+        {'loc': ['items', 2], 'from_type': 'str', 'to_type': 'int'},
+    ]
+
+
+def test_coercions_json(model_validator):
+    value, coercions = model_validator.validate_json(
+        '{"a": "1", "b": "x", "c": 1.5, "d": 1, "items": []}', report_coercions=True
+    )
+    assert value == ({'a': 1, 'b': 'x', 'c': 1.5, 'd': True, 'items': []}, {'a', 'b', 'c', 'd', 'items'})
+    assert coercions == [
+        {'loc': ['a'], 'from_type': 'str', 'to_type': 'int'},
+        {'loc': ['d'], 'from_type': 'int', 'to_type': 'bool'},
+    ]
+
+
+def test_not_reported():
+    v = SchemaValidator('int')
+    assert v.validate_python('1') == 1
+
+
+def test_union():
+    v = SchemaValidator({'type': 'union', 'choices': ['int', 'str']})
+    # the first choice matched strictly, so there's no coercion
+    assert v.validate_python('1', report_coercions=True) == ('1', [])
+    assert v.validate_python(b'1', report_coercions=True) == (1, [{'loc': [], 'from_type': 'bytes', 'to_type': 'int'}])
+
+
+def test_with_warnings():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': 'int'}})
+    (value, coercions), warnings = v.validate_python({'a': '1', 'b': 2}, report_coercions=True, warnings='collect')
+    assert value == ({'a': 1}, {'a'})
+    assert coercions == [{'loc': ['a'], 'from_type': 'str', 'to_type': 'int'}]
+    assert warnings == [{'loc': ['b'], 'message': 'Extra field ignored'}]
+
+
+def test_wrap_validator():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': {
+                    'type': 'function',
+                    'mode': 'wrap',
+                    'function': lambda input_value, validator, **kwargs: validator(input_value),
+                    'schema': 'int',
+                }
+            },
+        }
+    )
+    value, coercions = v.validate_python({'a': '1'}, report_coercions=True)
+    assert value == ({'a': 1}, {'a'})
+    assert coercions == [{'loc': ['a'], 'from_type': 'str', 'to_type': 'int'}]
@@ -0,0 +1,174 @@
+import pytest
+
+from pydantic_core import SchemaValidator
+
+
+def test_valid():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': 'int', 'b': 'str'}})
+    assert v.validate_python({'a': '1', 'b': 'x'}, collect_errors=True) == (({'a': 1, 'b': 'x'}, {'a', 'b'}), [])
+
+
+def test_invalid_fields_omitted():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': 'int',
+                'b': 'str',
+                'c': {'type': 'int', 'default': 42},
+                'sub': {'type': 'model', 'fields': {'x': 'int', 'y': 'int'}},
+                'items': {'type': 'list', 'items': 'int'},
+            },
+        }
+    )
+    value, errors = v.validate_python(
+        {'a': 'wrong', 'c': 'also wrong', 'sub': {'x': 1, 'y': 'nope'}, 'items': [1, 'x']}, collect_errors=True
+    )
+    assert value == ({'sub': ({'x': 1}, {'x'})}, {'sub'})
+    # errors from nested models are collected first
+    assert errors == [
+        {
+            'kind': 'int_parsing',
+            'loc': ['sub', 'y'],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'nope',
+            'title': 'Model',
+        },
+        {
+            'kind': 'int_parsing',
+            'loc': ['a'],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'wrong',
+        },
+        {
+            'kind': 'missing',
+            'loc': ['b'],
+            'message': 'Field required',
+            'input_value': {'a': 'wrong', 'c': 'also wrong', 'sub': {'x': 1, 'y': 'nope'}, 'items': [1, 'x']},
+        },
+        {
+            'kind': 'int_parsing',
+            'loc': ['c'],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'also wrong',
+        },
+        {
+            'kind': 'int_parsing',
+            'loc': ['items', 1],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'x',
+        },
+    ]
+
+
+def test_nested_in_list():
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'model', 'fields': {'a': 'int', 'b': 'int'}}})
+    value, errors = v.validate_json('[{"a": 1, "b": 2}, {"a": "x", "b": 3}]', collect_errors=True)
+    assert value == [({'a': 1, 'b': 2}, {'a', 'b'}), ({'b': 3}, {'b'})]
+    assert [e['loc'] for e in errors] == [[1, 'a']]
+
+
+
+@pytest.mark.parametrize(
+    'container,input_value,expected_locs',
+    [
+        ({'type': 'list'}, [[{'a': 'x'}], 1], [[0, 0, 'a'], [1]]),
+        ({'type': 'tuple'}, [[{'a': 'x'}], 1], [[0, 0, 'a'], [1]]),
+        ({'type': 'dict', 'keys': 'str'}, {'k': [{'a': 'x'}], 'j': 1}, [['k', 0, 'a'], ['j']]),
+    ],
+)
+def test_container_fails_after_nested_errors(container, input_value, expected_locs):
+    items_key = 'values' if container['type'] == 'dict' else 'items'
+    items_schema = {'type': 'list', 'items': {'type': 'model', 'fields': {'a': 'int'}}}
+    v = SchemaValidator({**container, items_key: items_schema})
+    value, errors = v.validate_python(input_value, collect_errors=True)
+    assert value is None
+    assert [e['loc'] for e in errors] == expected_locs
+
+
+def test_nested_container_fails_after_nested_errors():
+    model = {'type': 'model', 'fields': {'a': 'int', 'b': 'int'}}
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'dict', 'keys': 'int', 'values': model}})
+    value, errors = v.validate_python([{1: {'a': 1, 'b': 'x'}, 'zz': {'a': 2, 'b': 3}}], collect_errors=True)
+    assert value is None
+    assert [e['loc'] for e in errors] == [[0, 1, 'b'], [0, 'zz', '[key]']]
+
+
+@pytest.mark.parametrize('on_error,expected', [('default', ({'x': []}, set())), ('omit', ({}, set()))])
+def test_on_error_discards_nested_errors(on_error, expected):
+    items_schema = {'type': 'list', 'items': {'type': 'model', 'fields': {'a': 'int'}}}
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {'x': {'type': 'list', 'items': items_schema, 'default': [], 'on_error': on_error}},
+        }
+    )
+    # the errors collected from the first item are dropped along with the field
+    assert v.validate_python({'x': [[{'a': 'x'}], 1]}, collect_errors=True) == (expected, [])
+
+
+def test_top_level_failure():
+    v = SchemaValidator({'type': 'list', 'items': 'int'})
+    value, errors = v.validate_python([1, 'x'], collect_errors=True)
+    assert value is None
+    assert errors == [
+        {
+            'kind': 'int_parsing',
+            'loc': [1],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'x',
+        }
+    ]
+
+
+def test_invalid_json():
+    v = SchemaValidator('int')
+    value, errors = v.validate_json('[', collect_errors=True)
+    assert value is None
+    assert [e['kind'] for e in errors] == ['invalid_json']
+
+
+def test_union():
+    v = SchemaValidator(
+        {
+            'type': 'union',
+            'choices': [
+                {'type': 'model', 'fields': {'a': 'int'}},
+                {'type': 'model', 'fields': {'b': 'int'}},
+            ],
+        }
+    )
+    # the first choice isn't used since it's invalid, even though invalid fields would otherwise be dropped
+    assert v.validate_python({'b': 1}, collect_errors=True) == (({'b': 1}, {'b'}), [])
+    value, errors = v.validate_python({'c': 1}, collect_errors=True)
+    assert value is None
+    assert [e['loc'] for e in errors] == [['Model[0]', 'a'], ['Model[1]', 'b']]
+
+
+def test_with_warnings():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': 'int'}})
+    (value, errors), warnings = v.validate_python({'a': 'x', 'b': 2}, collect_errors=True, warnings='collect')
+    assert value == ({}, set())
+    assert [e['loc'] for e in errors] == [['a']]
+    assert warnings == [{'loc': ['b'], 'message': 'Extra field ignored'}]
+
+
+def test_wrap_validator():
+    v = SchemaValidator(
+        {
+            'type': 'function',
+            'mode': 'wrap',
+            'function': lambda input_value, validator, **kwargs: validator(input_value),
+            'schema': {'type': 'model', 'fields': {'a': 'int', 'b': 'str'}},
+        }
+    )
+    value, errors = v.validate_python({'a': 'wrong', 'b': 'x'}, collect_errors=True)
+    assert value == ({'b': 'x'}, {'b'})
+    assert errors == [
+        {
+            'kind': 'int_parsing',
+            'loc': ['a'],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'wrong',
+        }
+    ]
@@ -0,0 +1,216 @@
+import pytest
+
+from pydantic_core import SchemaValidator, ValidationError
+
+
+def test_construct_scalar():
+    v = SchemaValidator({'type': 'int', 'ge': 10})
+    assert v.construct('not an int') == 'not an int'
+
+
+def test_construct_model():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'field_a': {'type': 'str', 'alias': 'FieldA'},
+                'field_b': {'type': 'int', 'default': 42},
+                'field_c': {'type': 'str', 'max_length': 2},
+            },
+        }
+    )
+    assert v.construct({'FieldA': 123, 'field_c': 'too long', 'extra': 1}) == (
+        {'field_a': 123, 'field_b': 42, 'field_c': 'too long'},
+        {'field_a', 'field_c'},
+    )
+    # the field name can be used as well as the alias
+    assert v.construct({'field_a': 'x'}) == ({'field_a': 'x', 'field_b': 42}, {'field_a'})
+    # missing fields without a default are simply omitted
+    assert v.construct({}) == ({'field_b': 42}, set())
+
+
+def test_construct_allow_extra():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {'field_a': {'type': 'str', 'alias': 'FieldA'}},
+            'extra_validator': 'int',
+            'config': {'extra': 'allow'},
+        }
+    )
+    assert v.construct({'FieldA': 'x', 'other': 'not an int'}) == (
+        {'field_a': 'x', 'other': 'not an int'},
+        {'field_a', 'other'},
+    )
+
+
+def test_construct_not_dict():
+    v = SchemaValidator({'type': 'model', 'fields': {'field_a': 'str'}})
+    with pytest.raises(TypeError):
+        v.construct([1, 2])
+
+
+def test_construct_model_class():
+    class MyModel:
+        __slots__ = '__dict__', '__fields_set__'
+
+    v = SchemaValidator(
+        {
+            'type': 'model-class',
+            'class_type': MyModel,
+            'model': {
+                'type': 'model',
+                'fields': {
+                    'field_a': 'str',
+                    'sub': {
+                        'type': 'optional',
+                        'schema': {
+                            'type': 'model-class',
+                            'class_type': MyModel,
+                            'model': {'type': 'model', 'fields': {'field_b': {'type': 'int', 'default': 1}}},
+                        },
+                    },
+                },
+            },
+        }
+    )
+    m = v.construct({'field_a': 1, 'sub': {}})
+    assert isinstance(m, MyModel)
+    assert m.__dict__['field_a'] == 1
+    assert m.__fields_set__ == {'field_a', 'sub'}
+    assert isinstance(m.sub, MyModel)
+    assert m.sub.__dict__ == {'field_b': 1}
+    assert m.sub.__fields_set__ == set()
+
+    # existing instances are used as is
+    assert v.construct({'field_a': 'x', 'sub': m}).sub is m
+    assert v.construct({'field_a': 'x', 'sub': None}).sub is None
+
+
+def test_construct_skips_functions():
+    calls = []
+
+    def f(input_value, **kwargs):
+        calls.append(input_value)
+        return input_value
+
+    v = SchemaValidator(
+        {'type': 'function', 'mode': 'after', 'function': f, 'schema': {'type': 'model', 'fields': {'a': 'int'}}}
+    )
+    assert v.construct({'a': 'x'}) == ({'a': 'x'}, {'a'})
+    assert calls == []
+
+
+def test_construct_recursive():
+    v = SchemaValidator(
+        {
+            'type': 'recursive-container',
+            'name': 'Branch',
+            'schema': {
+                'type': 'model',
+                'fields': {
+                    'name': 'str',
+                    'sub_branch': {
+                        'type': 'optional',
+                        'schema': {'type': 'recursive-ref', 'name': 'Branch'},
+                        'default': None,
+                    },
+                },
+            },
+        }
+    )
+    assert v.construct({'name': 'root', 'sub_branch': {'name': 'leaf'}}) == (
+        {'name': 'root', 'sub_branch': ({'name': 'leaf', 'sub_branch': None}, {'name'})},
+        {'name', 'sub_branch'},
+    )
+
+
+class MyModel:
+    __slots__ = '__dict__', '__fields_set__'
+
+
+def model_class_schema(fields):
+    return {'type': 'model-class', 'class_type': MyModel, 'model': {'type': 'model', 'fields': fields}}
+
+
+def test_construct_containers():
+    sub_model = model_class_schema({'a': {'type': 'int', 'default': 1}})
+    v = SchemaValidator(
+        model_class_schema(
+            {
+                'x': sub_model,
+                'xs': {'type': 'list', 'items': sub_model},
+                'mapping': {'type': 'dict', 'keys': 'str', 'values': sub_model},
+                'pair': {'type': 'tuple', 'prefix_items': ['int', sub_model]},
+                'keyed': {'type': 'set', 'items': sub_model, 'item_key': 'serialized'},
+            }
+        )
+    )
+    m = v.construct({'x': {}, 'xs': [{}, {'a': 2}], 'mapping': {'k': {}}, 'pair': ('1', {}), 'keyed': [{}]})
+    assert isinstance(m.x, MyModel)
+    assert m.x.__dict__ == {'a': 1}
+    assert [item.__dict__ for item in m.xs] == [{'a': 1}, {'a': 2}]
+    assert m.xs[1].__fields_set__ == {'a'}
+    assert list(m.mapping) == ['k']
+    assert m.mapping['k'].__dict__ == {'a': 1}
+    assert isinstance(m.pair, tuple)
+    # values without a model are still used unchanged
+    assert m.pair[0] == '1'
+    assert m.pair[1].__dict__ == {'a': 1}
+    assert isinstance(m.keyed, list)
+    assert m.keyed[0].__dict__ == {'a': 1}
+
+
+def test_construct_container_not_iterable():
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'model', 'fields': {'a': 'int'}}})
+    assert v.construct('not a list') == 'not a list'
+    assert v.construct(({'a': 'x'},)) == [({'a': 'x'}, {'a'})]
+
+
+def test_construct_set():
+    v = SchemaValidator({'type': 'set', 'items': {'type': 'int'}})
+    assert v.construct(['1', 2]) == {'1', 2}
+
+
+def test_construct_union():
+    v = SchemaValidator(
+        {
+            'type': 'union',
+            'choices': [
+                'int',
+                model_class_schema({'a': 'int', 'b': 'int'}),
+                {'type': 'list', 'items': model_class_schema({'c': 'int'})},
+            ],
+        }
+    )
+    assert v.construct(1) == 1
+    assert v.construct({'a': 1, 'b': 2}).__dict__ == {'a': 1, 'b': 2}
+    # trusted input may omit fields, the closest choice is used
+    assert v.construct({'a': 1}).__dict__ == {'a': 1}
+    assert [item.__dict__ for item in v.construct([{'c': 1}])] == [{'c': 1}]
+
+
+def test_construct_union_no_match():
+    v = SchemaValidator({'type': 'union', 'choices': ['int', {'type': 'list', 'items': 'int'}]})
+    assert v.construct('not matching') == 'not matching'
+
+
+def test_construct_json():
+    v = SchemaValidator({'type': 'json', 'schema': {'type': 'list', 'items': model_class_schema({'a': 'int'})}})
+    output = v.construct('[{"a": "not an int"}]')
+    assert [item.__dict__ for item in output] == [{'a': 'not an int'}]
+    with pytest.raises(ValidationError, match='kind=invalid_json'):
+        v.construct('[')
+
+
+def test_construct_conditional():
+    v = SchemaValidator(
+        {
+            'type': 'conditional',
+            'if': 'str',
+            'then': {'type': 'str', 'max_length': 1},
+            'else': {'type': 'list', 'items': model_class_schema({'a': 'int'})},
+        }
+    )
+    assert v.construct('too long') == 'too long'
+    assert [item.__dict__ for item in v.construct([{'a': 1}])] == [{'a': 1}]
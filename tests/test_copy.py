@@ -0,0 +1,94 @@
+import pickle
+
+import pytest
+
+from pydantic_core import SchemaError, SchemaValidator, ValidationError
+
+
+@pytest.fixture
+def model_validator():
+    return SchemaValidator(
+        {
+            'type': 'model',
+            'title': 'MyModel',
+            'fields': {
+                'a': {'type': 'int'},
+                'b': {'type': 'list', 'items': {'type': 'str'}},
+                'c': {'type': 'bool', 'default': False},
+            },
+        }
+    )
+
+
+def test_copy_strict(model_validator):
+    strict = model_validator.copy(strict=True)
+    assert strict.validate_python({'a': 1, 'b': ['x'], 'c': True}) == ({'a': 1, 'b': ['x'], 'c': True}, {'a', 'b', 'c'})
+    with pytest.raises(ValidationError) as exc_info:
+        strict.validate_python({'a': '1', 'b': ('x',), 'c': 'true'})
+    assert [(e['loc'], e['kind']) for e in exc_info.value.errors()] == [
+        (['a'], 'int_type'),
+        (['b'], 'list_type'),
+        (['c'], 'bool_type'),
+    ]
+    assert not strict.isinstance_python({'a': '1', 'b': []})
+    assert not strict.isinstance_json('{"a": "1", "b": []}')
+
+    # the original is unchanged
+    assert model_validator.validate_python({'a': '1', 'b': ('x',), 'c': 'true'}) == (
+        {'a': 1, 'b': ['x'], 'c': True},
+        {'a', 'b', 'c'},
+    )
+
+    lax = strict.copy(strict=False)
+    assert lax.validate_python({'a': '1', 'b': ['x']}) == ({'a': 1, 'b': ['x'], 'c': False}, {'a', 'b'})
+
+
+def test_copy_title(model_validator):
+    v = model_validator.copy(title='Other')
+    with pytest.raises(ValidationError, match='1 validation error for Other'):
+        v.validate_python({'a': 'x', 'b': []})
+    with pytest.raises(ValidationError, match='1 validation error for MyModel'):
+        model_validator.validate_python({'a': 'x', 'b': []})
+
+
+def test_copy_extra_behavior(model_validator):
+    assert model_validator.validate_python({'a': 1, 'b': [], 'x': 1}) == ({'a': 1, 'b': [], 'c': False}, {'a', 'b'})
+
+    v = model_validator.copy(extra_behavior='allow')
+    assert v.validate_python({'a': 1, 'b': [], 'x': 1}) == ({'a': 1, 'b': [], 'c': False, 'x': 1}, {'a', 'b', 'x'})
+
+    v = model_validator.copy(extra_behavior='forbid')
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 1, 'b': [], 'x': 1})
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'extra_forbidden',
+            'loc': ['x'],
+            'message': 'Extra values are not permitted',
+            'input_value': {'a': 1, 'b': [], 'x': 1},
+        }
+    ]
+
+
+def test_copy_invalid_extra_behavior(model_validator):
+    with pytest.raises(SchemaError, match='Invalid extra_behavior: "wrong"'):
+        model_validator.copy(extra_behavior='wrong')
+
+
+def test_copy_function_wrap():
+    def f(input_value, *, validator, **kwargs):
+        return validator(input_value) * 2
+
+    v = SchemaValidator({'type': 'function', 'mode': 'wrap', 'function': f, 'schema': {'type': 'int'}})
+    assert v.validate_python('2') == 4
+    with pytest.raises(ValidationError, match='Value must be a valid integer'):
+        v.copy(strict=True).validate_python('2')
+
+
+def test_copy_pickle(model_validator):
+    v = model_validator.copy(strict=True, title='Other', extra_behavior='forbid')
+    v2 = pickle.loads(pickle.dumps(v))
+    assert repr(v2) == repr(v)
+    with pytest.raises(ValidationError, match='2 validation errors for Other') as exc_info:
+        v2.validate_python({'a': '1', 'b': [], 'x': 1})
+    assert [e['kind'] for e in exc_info.value.errors()] == ['int_type', 'extra_forbidden']
@@ -0,0 +1,177 @@
+import pickle
+
+import pytest
+
+from pydantic_core import SchemaValidator, ValidationError, register_schema_type
+
+
+def test_bare_type():
+    assert SchemaValidator('int').core_schema() == {'type': 'int'}
+    assert SchemaValidator({'type': 'list', 'items': 'int'}).core_schema() == {
+        'type': 'list',
+        'items': {'type': 'int'},
+    }
+
+
+def test_sub_schemas():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': 'int',
+                'b': {'type': 'union', 'choices': ['str', {'type': 'list', 'items': 'float'}]},
+                'c': {'type': 'tuple', 'prefix_items': ['int'], 'items': 'bool'},
+                'd': {'type': 'dict', 'keys': 'str', 'values': 'int', 'default': {'x': 1}},
+            },
+        }
+    )
+    assert v.core_schema() == {
+        'type': 'model',
+        'fields': {
+            'a': {'type': 'int'},
+            'b': {'type': 'union', 'choices': [{'type': 'str'}, {'type': 'list', 'items': {'type': 'float'}}]},
+            'c': {'type': 'tuple', 'prefix_items': [{'type': 'int'}], 'items': {'type': 'bool'}},
+            'd': {'type': 'dict', 'keys': {'type': 'str'}, 'values': {'type': 'int'}, 'default': {'x': 1}},
+        },
+    }
+
+
+def test_config():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': 'int',
+                'b': {'type': 'model', 'fields': {'c': 'str'}, 'config': {'extra': 'forbid'}},
+                'd': {'type': 'int', 'strict': False},
+            },
+            'config': {'strict': True},
+        }
+    )
+    assert v.core_schema() == {
+        'type': 'model',
+        'fields': {
+            'a': {'type': 'int', 'strict': True},
+            'b': {
+                'type': 'model',
+                'fields': {'c': {'type': 'str', 'strict': True}},
+                'config': {'strict': True, 'extra': 'forbid'},
+                'strict': True,
+            },
+            'd': {'type': 'int', 'strict': False},
+        },
+        'config': {'strict': True},
+        'strict': True,
+    }
+
+
+def test_other_values_unchanged():
+    # 'int' is interned, so the title and default are the same object as the field's schema
+    v = SchemaValidator(
+        {'type': 'model', 'title': 'int', 'fields': {'a': 'int', 'b': {'type': 'str', 'default': 'int'}}}
+    )
+    assert v.core_schema() == {
+        'type': 'model',
+        'title': 'int',
+        'fields': {'a': {'type': 'int'}, 'b': {'type': 'str', 'default': 'int'}},
+    }
+
+
+def test_class_schema():
+    class Foobar:
+        @classmethod
+        def __get_pydantic_core_schema__(cls):
+            return {'type': 'list', 'items': 'int'}
+
+    assert SchemaValidator(Foobar).core_schema() == {'type': 'list', 'items': {'type': 'int'}}
+    assert SchemaValidator({'type': 'optional', 'schema': Foobar}).core_schema() == {
+        'type': 'optional',
+        'schema': {'type': 'list', 'items': {'type': 'int'}},
+    }
+
+
+def test_custom_type():
+    register_schema_type('test-core-schema', lambda schema, config: {'type': 'int', 'gt': schema['gt']})
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'test-core-schema', 'gt': 1}})
+    assert v.core_schema() == {'type': 'list', 'items': {'type': 'int', 'gt': 1}}
+
+
+def test_shared_models():
+    def model_schema():
+        return {'type': 'model', 'fields': {'x': 'int'}}
+
+    v = SchemaValidator({'type': 'model', 'fields': {'a': model_schema(), 'b': model_schema(), 'c': model_schema()}})
+    assert v.core_schema() == {
+        'type': 'model',
+        'fields': {
+            'a': {'type': 'model', 'fields': {'x': {'type': 'int'}}},
+            'b': {'type': 'model', 'fields': {'x': {'type': 'int'}}},
+            'c': {'type': 'model', 'fields': {'x': {'type': 'int'}}},
+        },
+    }
+
+
+def test_definitions():
+    schema = {
+        'type': 'definitions',
+        'definitions': {
+            'Branch': {'type': 'list', 'items': {'type': 'recursive-ref', 'name': 'Branch'}},
+            'Leaf': 'int',
+        },
+        'schema': {'type': 'tuple', 'prefix_items': [{'type': 'recursive-ref', 'name': 'Branch'}], 'items': 'str'},
+    }
+    # references are kept since they may be recursive
+    assert SchemaValidator(schema).core_schema() == {
+        'type': 'definitions',
+        'definitions': {
+            'Branch': {'type': 'list', 'items': {'type': 'recursive-ref', 'name': 'Branch'}},
+            'Leaf': {'type': 'int'},
+        },
+        'schema': {
+            'type': 'tuple',
+            'prefix_items': [{'type': 'recursive-ref', 'name': 'Branch'}],
+            'items': {'type': 'str'},
+        },
+    }
+
+
+def test_from_definitions():
+    validators = SchemaValidator.from_definitions(
+        {'A': {'type': 'list', 'items': {'type': 'recursive-ref', 'name': 'B'}}, 'B': 'int'}
+    )
+    assert validators['B'].core_schema() == {
+        'type': 'definitions',
+        'title': 'int',
+        'definitions': {
+            'A': {'type': 'list', 'items': {'type': 'recursive-ref', 'name': 'B'}},
+            'B': {'type': 'int'},
+        },
+        'schema': {'type': 'recursive-ref', 'name': 'B'},
+    }
+
+
+@pytest.mark.parametrize(
+    'schema',
+    [
+        {'type': 'model', 'fields': {'a': 'int', 'b': {'type': 'set', 'items': 'str'}}, 'config': {'strict': True}},
+        {'type': 'union', 'choices': ['int', 'bool', {'type': 'list', 'items': {'type': 'float', 'gt': 1}}]},
+    ],
+)
+def test_rebuild(schema):
+    v = SchemaValidator(schema)
+    rebuilt = SchemaValidator(v.core_schema())
+    assert rebuilt.core_schema() == v.core_schema()
+    for input_value in [{'a': 1, 'b': ['x']}, {'a': '1', 'b': ['x']}, '1', [2, '3'], [0.5]]:
+        try:
+            expected = v.validate_python(input_value)
+        except ValidationError as e:
+            with pytest.raises(ValidationError) as exc_info:
+                rebuilt.validate_python(input_value)
+            assert exc_info.value.errors() == e.errors()
+        else:
+            assert rebuilt.validate_python(input_value) == expected
+
+
+def test_pickle():
+    v = SchemaValidator({'type': 'list', 'items': 'int'})
+    assert pickle.loads(pickle.dumps(v)).core_schema() == {'type': 'list', 'items': {'type': 'int'}}
@@ -0,0 +1,113 @@
+import re
+
+import pytest
+
+from pydantic_core import SchemaError, SchemaValidator, ValidationError, register_schema_type
+
+
+def build_country_code(schema, config):
+    codes = schema.get('codes', ['GB', 'US'])
+
+    def check_code(input_value, **kwargs):
+        if input_value not in codes:
+            raise ValueError(f'Unknown country code "{input_value}"')
+        return input_value
+
+    return {'type': 'function', 'mode': 'after', 'function': check_code, 'schema': {'type': 'str', 'to_upper': True}}
+
+
+register_schema_type('test-country-code', build_country_code)
+
+
+def test_custom_type():
+    v = SchemaValidator('test-country-code')
+    assert v.validate_python('gb') == 'GB'
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python('fr')
+    assert exc_info.value.errors() == [
+        {'kind': 'value_error', 'loc': [], 'message': 'Unknown country code "FR"', 'input_value': 'fr'}
+    ]
+
+
+def test_custom_type_args():
+    v = SchemaValidator(
+        {'type': 'model', 'fields': {'country': {'type': 'test-country-code', 'codes': ['FR']}}, 'config': {'x': 1}}
+    )
+    assert v.validate_json('{"country": "fr"}') == ({'country': 'FR'}, {'country'})
+    with pytest.raises(ValidationError, match='Unknown country code "GB"'):
+        v.validate_python({'country': 'gb'})
+
+
+def test_builder_args():
+    calls = []
+
+    def builder(schema, config):
+        calls.append((schema, config))
+        return 'int'
+
+    register_schema_type('test-builder-args', builder)
+    v = SchemaValidator({'type': 'model', 'fields': {'a': {'type': 'test-builder-args', 'foo': 'bar'}}})
+    assert v.validate_python({'a': '1'}) == ({'a': 1}, {'a'})
+    assert calls == [({'type': 'test-builder-args', 'foo': 'bar'}, None)]
+
+
+def test_builder_error():
+    def builder(schema, config):
+        raise RuntimeError('broken builder')
+
+    register_schema_type('test-broken', builder)
+    with pytest.raises(SchemaError, match=re.escape('Error building "test-broken" validator:\n  RuntimeError: broken')):
+        SchemaValidator('test-broken')
+
+
+def test_builder_recursive():
+    register_schema_type('test-recursive', lambda schema, config: schema)
+    with pytest.raises(SchemaError, match='Recursive detected, depth exceeded max allowed value of 100'):
+        SchemaValidator('test-recursive')
+
+
+def test_builtin_precedence():
+    register_schema_type('int', lambda schema, config: 'str')
+    assert SchemaValidator('int').validate_python('1') == 1
+
+
+def test_not_callable():
+    with pytest.raises(TypeError, match='builder must be callable'):
+        register_schema_type('test-not-callable', 123)
+
+
+class Percentage(float):
+    @classmethod
+    def __get_pydantic_core_schema__(cls):
+        return {'type': 'float', 'ge': 0, 'le': 100}
+
+
+def test_class_schema():
+    v = SchemaValidator(
+        {'type': 'model', 'fields': {'share': Percentage, 'history': {'type': 'list', 'items': Percentage}}}
+    )
+    assert v.validate_python({'share': '12.5', 'history': [1, 2.5]}) == (
+        {'share': 12.5, 'history': [1, 2.5]},
+        {'share', 'history'},
+    )
+    with pytest.raises(ValidationError, match=r'share\n  Value must be less than or equal to 100'):
+        v.validate_python({'share': 101, 'history': []})
+
+
+def test_class_schema_missing():
+    class Plain:
+        pass
+
+    msg = 'Class Plain must define __get_pydantic_core_schema__ to be used as a schema'
+    with pytest.raises(SchemaError, match=msg):
+        SchemaValidator({'type': 'list', 'items': Plain})
+
+
+def test_class_schema_recursive():
+    class Loop:
+        @classmethod
+        def __get_pydantic_core_schema__(cls):
+            return cls
+
+    with pytest.raises(SchemaError, match='Recursive detected, depth exceeded max allowed value of 100'):
+        SchemaValidator(Loop)
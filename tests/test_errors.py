@@ -0,0 +1,451 @@
+import pickle
+from concurrent.futures import ThreadPoolExecutor
+from copy import deepcopy
+
+import pytest
+
+from pydantic_core import SchemaValidator, ValidationError, __version__, list_all_errors, set_errors_url
+from pydantic_core._pydantic_core import PyLineError
+
+
+def test_pretty_truncate_length():
+    v = SchemaValidator('int')
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python('x' * 100)
+
+    assert str(exc_info.value) == (
+        '1 validation error for int\n'
+        '  Value must be a valid integer, unable to parse string as an integer '
+        "[kind=int_parsing, input_value='xxxxxxxxxxxxxxxxxxxxxxxx...xxxxxxxxxxxxxxxxxxxxxxx', input_type=str]"
+    )
+    assert exc_info.value.pretty(truncate_length=10) == (
+        '1 validation error for int\n'
+        '  Value must be a valid integer, unable to parse string as an integer '
+        "[kind=int_parsing, input_value='xxxx...xxx', input_type=str]"
+    )
+    assert exc_info.value.pretty(truncate_length=200) == (
+        '1 validation error for int\n'
+        '  Value must be a valid integer, unable to parse string as an integer '
+        f"[kind=int_parsing, input_value='{'x' * 100}', input_type=str]"
+    )
+
+
+def test_truncate_multibyte():
+    v = SchemaValidator('int')
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python('é' * 100)
+
+    assert str(exc_info.value) == (
+        '1 validation error for int\n'
+        '  Value must be a valid integer, unable to parse string as an integer '
+        "[kind=int_parsing, input_value='éééééééééééééééééééééééé...ééééééééééééééééééééééé', input_type=str]"
+    )
+
+
+KEYCAP = '1\ufe0f\u20e3'
+FLAG = '\U0001f1ec\U0001f1e7'
+THUMB = '\U0001f44d\U0001f3fd'
+
+
+@pytest.mark.parametrize(
+    'input_value,truncate_length,expected',
+    [
+        # keycap, a digit followed by a variation selector and a combining mark
+        (KEYCAP * 30, 10, f'{KEYCAP}...{KEYCAP}'),
+        (KEYCAP * 30, 12, f'{KEYCAP}...{KEYCAP}'),
+        (KEYCAP * 30, 14, f'{KEYCAP}{KEYCAP}...{KEYCAP}'),
+        # flags, pairs of regional indicators
+        (FLAG * 20, 10, f'{FLAG}{FLAG}...{FLAG}'),
+        # skin tone modifier
+        (THUMB * 30, 10, f'{THUMB}{THUMB}...{THUMB}'),
+        # combining acute accent
+        ('e\u0301' * 50, 10, 'e\u0301e\u0301...e\u0301'),
+        # hangul syllable from conjoining jamo
+        ('\u1100\u1161\u11a8' * 50, 10, '\u1100\u1161\u11a8...\u1100\u1161\u11a8'),
+        ('\U0001f600' * 50, 10, '\U0001f600\U0001f600\U0001f600\U0001f600...\U0001f600\U0001f600\U0001f600'),
+    ],
+)
+def test_truncate_graphemes(input_value, truncate_length, expected):
+    v = SchemaValidator('int')
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(input_value)
+
+    assert exc_info.value.pretty(truncate_length=truncate_length) == (
+        '1 validation error for int\n'
+        '  Value must be a valid integer, unable to parse string as an integer '
+        f"[kind=int_parsing, input_value='{expected}', input_type=str]"
+    )
+
+
+def test_group_errors():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'name': 'MyModel',
+            'fields': {
+                'a': 'int',
+                'b': {'type': 'union', 'choices': [{'type': 'int', 'strict': True}, {'type': 'bool', 'strict': True}]},
+            },
+        }
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 'x', 'b': 'y'})
+
+    assert exc_info.value.group_errors() == [
+        {
+            'loc': ['a'],
+            'errors': [
+                {
+                    'kind': 'int_parsing',
+                    'message': 'Value must be a valid integer, unable to parse string as an integer',
+                    'input_value': 'x',
+                }
+            ],
+        },
+        {
+            'loc': ['b'],
+            'errors': [],
+            'alternatives': [
+                {
+                    'loc': ['strict-int'],
+                    'errors': [{'kind': 'int_type', 'message': 'Value must be a valid integer', 'input_value': 'y'}],
+                },
+                {
+                    'loc': ['strict-bool'],
+                    'errors': [{'kind': 'bool_type', 'message': 'Value must be a valid boolean', 'input_value': 'y'}],
+                },
+            ],
+        },
+    ]
+    # the alternatives are nested under the field's location
+    assert exc_info.value.pretty(group_by_location=True) == (
+        '3 validation errors for MyModel\n'
+        'a\n'
+        '  Value must be a valid integer, unable to parse string as an integer '
+        "[kind=int_parsing, input_value='x', input_type=str]\n"
+        'b\n'
+        '  strict-int\n'
+        "    Value must be a valid integer [kind=int_type, input_value='y', input_type=str]\n"
+        '  strict-bool\n'
+        "    Value must be a valid boolean [kind=bool_type, input_value='y', input_type=str]"
+    )
+
+
+def test_group_errors_top_level_union():
+    v = SchemaValidator(
+        {'type': 'union', 'choices': [{'type': 'model', 'fields': {'a': 'int', 'b': 'int'}}, {'type': 'int'}]}
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 'x'})
+
+    assert [(g['loc'], g['errors'], [a['loc'] for a in g['alternatives']]) for g in exc_info.value.group_errors()] == [
+        ([], [], [['Model', 'a'], ['Model', 'b'], ['int']])
+    ]
+    assert exc_info.value.pretty(group_by_location=True) == (
+        '3 validation errors for union\n'
+        'Model -> a\n'
+        '  Value must be a valid integer, unable to parse string as an integer '
+        "[kind=int_parsing, input_value='x', input_type=str]\n"
+        'Model -> b\n'
+        "  Field required [kind=missing, input_value={'a': 'x'}, input_type=dict]\n"
+        'int\n'
+        "  Value must be a valid integer [kind=int_type, input_value={'a': 'x'}, input_type=dict]"
+    )
+    # choices are kept when pickled, so the errors are still equal and grouped the same
+    unpickled = pickle.loads(pickle.dumps(exc_info.value))
+    assert unpickled == exc_info.value
+    assert unpickled.pretty(group_by_location=True) == exc_info.value.pretty(group_by_location=True)
+
+
+def test_line_error_choices():
+    choice = PyLineError('int_type', ['int', 'a'], None, 'x', None, None, [0])
+    plain = PyLineError('int_type', ['int', 'a'], None, 'x', None)
+    assert ValidationError([choice], 'x').errors() == ValidationError([plain], 'x').errors()
+    assert choice != plain
+    assert len({choice, plain}) == 2
+    assert pickle.loads(pickle.dumps(choice)) == choice
+
+    with pytest.raises(ValueError, match='Invalid choice 1, not the index of a str in location'):
+        PyLineError('int_type', ['int', 1], None, 'x', None, None, [1])
+
+
+def test_pretty_group_by_location():
+    def f(input_value, **kwargs):
+        raise ValueError('custom error')
+
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'name': 'MyModel',
+            'fields': {
+                'a': {
+                    'type': 'union',
+                    'choices': [
+                        {'type': 'function', 'mode': 'plain', 'function': f},
+                        {'type': 'function', 'mode': 'plain', 'function': f},
+                    ],
+                },
+                'b': 'int',
+            },
+        }
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 1, 'b': 'x'})
+
+    assert exc_info.value.error_count() == 3
+    assert exc_info.value.pretty(group_by_location=True) == (
+        '3 validation errors for MyModel\n'
+        'a\n'
+        '  function-plain[0]\n'
+        '    custom error [kind=value_error, input_value=1, input_type=int]\n'
+        '  function-plain[1]\n'
+        '    custom error [kind=value_error, input_value=1, input_type=int]\n'
+        'b\n'
+        '  Value must be a valid integer, unable to parse string as an integer '
+        "[kind=int_parsing, input_value='x', input_type=str]"
+    )
+    assert exc_info.value.group_errors() == [
+        {
+            'loc': ['a'],
+            'errors': [],
+            'alternatives': [
+                {
+                    'loc': ['function-plain[0]'],
+                    'errors': [{'kind': 'value_error', 'message': 'custom error', 'input_value': 1}],
+                },
+                {
+                    'loc': ['function-plain[1]'],
+                    'errors': [{'kind': 'value_error', 'message': 'custom error', 'input_value': 1}],
+                },
+            ],
+        },
+        {
+            'loc': ['b'],
+            'errors': [
+                {
+                    'kind': 'int_parsing',
+                    'message': 'Value must be a valid integer, unable to parse string as an integer',
+                    'input_value': 'x',
+                }
+            ],
+        },
+    ]
+
+
+def test_error_url():
+    v = SchemaValidator({'type': 'model', 'name': 'MyModel', 'fields': {'a': 'int'}})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 'x'})
+
+    url = f'https://errors.pydantic.dev/{__version__}/v/int_parsing'
+    assert exc_info.value.errors(include_url=True) == [
+        {
+            'kind': 'int_parsing',
+            'loc': ['a'],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'x',
+            'url': url,
+        }
+    ]
+    assert 'url' not in exc_info.value.errors()[0]
+    assert exc_info.value.pretty(include_url=True) == (
+        '1 validation error for MyModel\n'
+        'a\n'
+        '  Value must be a valid integer, unable to parse string as an integer '
+        "[kind=int_parsing, input_value='x', input_type=str]\n"
+        f'    For further information visit {url}'
+    )
+    assert url not in str(exc_info.value)
+
+
+def test_set_errors_url():
+    v = SchemaValidator('int')
+    set_errors_url('https://docs.example.com/errors#')
+    try:
+        with pytest.raises(ValidationError) as exc_info:
+            v.validate_python('x')
+        assert exc_info.value.errors(include_url=True)[0]['url'] == 'https://docs.example.com/errors#int_parsing'
+        assert exc_info.value.pretty(include_url=True).endswith(
+            'For further information visit https://docs.example.com/errors#int_parsing'
+        )
+    finally:
+        set_errors_url()
+
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python('x')
+    url = f'https://errors.pydantic.dev/{__version__}/v/int_parsing'
+    assert exc_info.value.errors(include_url=True)[0]['url'] == url
+
+
+def test_pickle_validation_error():
+    v = SchemaValidator(
+        {'type': 'model', 'name': 'MyModel', 'fields': {'a': 'int', 'b': {'type': 'str', 'max_length': 3}}}
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 'x', 'b': 'abcd'})
+    e1 = exc_info.value
+
+    for protocol in range(1, pickle.HIGHEST_PROTOCOL + 1):
+        e2 = pickle.loads(pickle.dumps(e1, protocol=protocol))
+        assert isinstance(e2, ValidationError)
+        assert e2.title == 'MyModel'
+        assert e2.errors() == e1.errors()
+        assert str(e2) == str(e1)
+
+
+def test_pickle_unpicklable_input():
+    class Foobar:
+        def __getstate__(self):
+            raise TypeError('not picklable')
+
+        def __repr__(self):
+            return 'Foobar()'
+
+    v = SchemaValidator('int')
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(Foobar())
+
+    e = pickle.loads(pickle.dumps(exc_info.value))
+    assert e.errors() == [
+        {'kind': 'int_type', 'loc': [], 'message': 'Value must be a valid integer', 'input_value': 'Foobar()'}
+    ]
+
+
+def test_deepcopy_validation_error():
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'int', 'gt': 1}})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python([1, 2, 3])
+
+    e = deepcopy(exc_info.value)
+    assert e is not exc_info.value
+    assert e.errors() == [
+        {
+            'kind': 'int_greater_than',
+            'loc': [0],
+            'message': 'Value must be greater than 1',
+            'input_value': 1,
+            'context': {'gt': 1},
+        }
+    ]
+    assert str(e) == str(exc_info.value)
+
+
+
+class ReprCounter:
+    calls = 0
+
+    def __repr__(self):
+        ReprCounter.calls += 1
+        return 'Foobar()'
+
+
+def test_repr_rendered_once():
+    v = SchemaValidator({'type': 'list', 'items': 'int'})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python([ReprCounter(), 'x'])
+
+    expected = (
+        '2 validation errors for list-int\n'
+        '0\n'
+        '  Value must be a valid integer [kind=int_type, input_value=Foobar(), input_type=ReprCounter]\n'
+        '1\n'
+        '  Value must be a valid integer, unable to parse string as an integer '
+        "[kind=int_parsing, input_value='x', input_type=str]"
+    )
+    assert str(exc_info.value) == expected
+    assert repr(exc_info.value) == expected
+    assert exc_info.value.pretty(truncate_length=5) == expected.replace('Foobar()', 'Fo...()')
+    assert ReprCounter.calls == 1
+
+
+def test_repr_threads():
+    v = SchemaValidator({'type': 'list', 'items': 'int'})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(['x'] * 100)
+
+    with ThreadPoolExecutor(4) as executor:
+        outputs = set(executor.map(lambda _: str(exc_info.value), range(20)))
+    assert len(outputs) == 1
+    assert outputs.pop().startswith('100 validation errors for list-int\n0\n')
+
+
+def test_validation_error_eq():
+    v = SchemaValidator(
+        {'type': 'model', 'name': 'MyModel', 'fields': {'a': 'int', 'b': {'type': 'str', 'max_length': 3}}}
+    )
+
+    def validation_error(input_value):
+        with pytest.raises(ValidationError) as exc_info:
+            v.validate_python(input_value)
+        return exc_info.value
+
+    e1 = validation_error({'a': 'x', 'b': 'abcd'})
+    # input values are ignored
+    assert e1 == validation_error({'a': 'y', 'b': 'wxyz'})
+    assert hash(e1) == hash(validation_error({'a': 'y', 'b': 'wxyz'}))
+    assert not e1 != validation_error({'a': 'y', 'b': 'wxyz'})
+    assert e1 == pickle.loads(pickle.dumps(e1))
+    assert e1 == deepcopy(e1)
+
+    assert e1 != validation_error({'a': 'x', 'b': 'abc'})
+    assert e1 != validation_error({'a': 'x', 'c': 'abcd'})
+    assert e1 != ValueError(str(e1))
+    assert e1 != 'x'
+
+    expected = ValidationError(
+        [
+            PyLineError('int_parsing', ['a'], None, 'anything', None),
+            PyLineError('str_too_long', ['b'], None, 'anything', {'max_length': 3}),
+        ],
+        'MyModel',
+    )
+    assert e1 == expected
+    assert len({e1, expected}) == 1
+    assert e1 != ValidationError([PyLineError('int_parsing', ['a'], None, 'x', None)], 'MyModel')
+    assert e1 != ValidationError(
+        [
+            PyLineError('int_parsing', ['a'], None, 'x', None),
+            PyLineError('str_too_long', ['b'], None, 'abcd', {'max_length': 3}),
+        ],
+        'OtherModel',
+    )
+
+
+def test_list_context():
+    v = SchemaValidator({'type': 'literal', 'expected': ['a', 'b']})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python('c')
+    e1 = exc_info.value
+    assert e1.errors()[0]['context'] == {'expected': ['a', 'b']}
+
+    e2 = pickle.loads(pickle.dumps(e1))
+    assert e2.errors() == e1.errors()
+    assert str(e2) == str(e1)
+    assert e2 == e1
+    assert hash(e2) == hash(e1)
+
+    context = {'expected_schemes': ['http', 'https']}
+    e3 = ValidationError([PyLineError('url_scheme', [], None, 'ftp://x', context)], 'url')
+    assert e3.errors()[0]['message'] == 'URL scheme must be one of: http, https'
+    assert e3.errors()[0]['context'] == context
+
+
+def test_error_kinds_shared():
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'literal', 'expected': ['a', 'b']}})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(['x', 'y'])
+    first, second = exc_info.value.errors()
+    assert first['kind'] == 'literal_error'
+    # kind names are interned python strings, shared between errors
+    assert first['kind'] is second['kind']
+
+
+def test_list_all_errors():
+    errors = list_all_errors()
+    assert {'kind': 'missing', 'message_template': 'Field required', 'context_fields': []} in errors
+    assert {
+        'kind': 'str_too_short',
+        'message_template': 'String must have at least {min_length} characters',
+        'context_fields': ['min_length'],
+    } in errors
+    assert len({e['kind'] for e in errors}) > 50
@@ -0,0 +1,65 @@
+import pytest
+
+from pydantic_core import SchemaValidator, ValidationError, set_validation_hooks
+
+
+def record_events():
+    events = []
+    set_validation_hooks(
+        on_start=lambda title: events.append(('start', title)),
+        on_end=lambda title, duration, error_count: events.append(('end', title, duration >= 0, error_count)),
+    )
+    return events
+
+
+def test_hooks():
+    v = SchemaValidator({'title': 'MyModel', 'type': 'model', 'fields': {'a': 'int', 'b': 'int'}})
+    events = record_events()
+    try:
+        assert v.validate_python({'a': 1, 'b': 2}) == ({'a': 1, 'b': 2}, {'a', 'b'})
+        with pytest.raises(ValidationError):
+            v.validate_json('{"a": "x", "b": "y"}')
+    finally:
+        set_validation_hooks()
+    assert events == [
+        ('start', 'MyModel'),
+        ('end', 'MyModel', True, 0),
+        ('start', 'MyModel'),
+        ('end', 'MyModel', True, 2),
+    ]
+
+
+def test_hooks_collect_errors():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': 'int', 'b': 'int'}})
+    events = record_events()
+    try:
+        v.validate_python({'a': 1, 'b': 'x'}, collect_errors=True)
+    finally:
+        set_validation_hooks()
+    assert events == [('start', 'Model'), ('end', 'Model', True, 1)]
+
+
+def test_remove_hooks():
+    v = SchemaValidator('int')
+    events = record_events()
+    v.validate_python(1)
+    set_validation_hooks()
+    v.validate_python(2)
+    assert events == [('start', 'int'), ('end', 'int', True, 0)]
+
+
+def test_hook_error():
+    def on_start(title):
+        raise RuntimeError('broken hook')
+
+    set_validation_hooks(on_start=on_start)
+    try:
+        with pytest.raises(RuntimeError, match='broken hook'):
+            SchemaValidator('int').validate_python(1)
+    finally:
+        set_validation_hooks()
+
+
+def test_not_callable():
+    with pytest.raises(TypeError, match='validation hooks must be callable'):
+        set_validation_hooks(on_end=1)
@@ -0,0 +1,83 @@
+import pytest
+
+from pydantic_core import SchemaValidator
+
+
+@pytest.mark.parametrize(
+    'input_value,expected', [(1, True), ('123', True), (1.0, True), ('x', False), (1.5, False), ([], False)]
+)
+def test_isinstance_python(input_value, expected):
+    v = SchemaValidator('int')
+    assert v.isinstance_python(input_value) is expected
+
+
+@pytest.mark.parametrize(
+    'input_value,expected', [('1', True), ('"123"', True), ('"x"', False), ('[]', False), ('{invalid json', False)]
+)
+def test_isinstance_json(input_value, expected):
+    v = SchemaValidator('int')
+    assert v.isinstance_json(input_value) is expected
+
+
+def test_isinstance_model():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': 'int', 'b': {'type': 'list', 'items': 'str'}}})
+    assert v.isinstance_python({'a': 1, 'b': ['x']}) is True
+    assert v.isinstance_python({'a': 1, 'b': [[]]}) is False
+    assert v.isinstance_json('{"a": 1, "b": ["x"]}') is True
+    assert v.isinstance_json('{"a": 1}') is False
+
+
+
+def test_output_not_built():
+    calls = []
+
+    def output_factory(**kwargs):
+        calls.append(kwargs)
+        return kwargs
+
+    model = {'type': 'model', 'fields': {'a': 'int'}, 'output_factory': output_factory}
+    v = SchemaValidator({'type': 'dict', 'keys': 'str', 'values': {'type': 'list', 'items': model}})
+    assert v.isinstance_python({'x': [{'a': 1}, {'a': '2'}]}) is True
+    assert v.isinstance_json('{"x": [{"a": 1}]}') is True
+    assert v.isinstance_python({'x': [{'a': 'x'}]}) is False
+    assert calls == []
+    assert v.validate_python({'x': [{'a': 1}]}) == {'x': [{'a': 1}]}
+    assert calls == [{'a': 1}]
+
+
+def test_output_built_when_used():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': {'type': 'list', 'items': 'int'},
+                'b': {
+                    'type': 'function',
+                    'mode': 'after',
+                    'function': lambda value, data, **kwargs: value[0] + sum(data['a']),
+                    'schema': {'type': 'tuple', 'items': 'int'},
+                },
+                'c': {
+                    'type': 'function',
+                    'mode': 'after',
+                    'function': lambda value, **kwargs: value[0],
+                    'schema': {'type': 'list', 'items': 'int'},
+                },
+                'd': {'type': 'set', 'items': {'type': 'list', 'items': 'int'}, 'item_key': 'serialized'},
+                'e': {'type': 'set', 'items': 'int', 'unique': True},
+            },
+        }
+    )
+    valid = {'a': [1], 'b': (2,), 'c': [3], 'd': [[1], [2]], 'e': [1, 2]}
+    assert v.isinstance_python(valid) is True
+    # duplicates are still found from the validated items
+    assert v.isinstance_python({**valid, 'e': [1, '1']}) is False
+
+
+def test_internal_error():
+    def f(input_value, **kwargs):
+        raise RuntimeError('internal')
+
+    v = SchemaValidator({'type': 'function', 'mode': 'plain', 'function': f})
+    with pytest.raises(RuntimeError, match='internal'):
+        v.isinstance_python(1)
@@ -1,3 +1,7 @@
+import io
+import json
+from concurrent.futures import ThreadPoolExecutor
+
 import pytest
 
 from pydantic_core import SchemaValidator, ValidationError
@@ -102,3 +106,281 @@ def test_invalid_json():
             'input_value': '[1,\n2,\n3,]',
         }
     ]
+
+
+def test_json_threads():
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'dict', 'keys': 'str', 'values': 'int'}})
+    input_json = json.dumps([{f'key_{i}': i} for i in range(1000)])
+    expected = [{f'key_{i}': i} for i in range(1000)]
+
+    with ThreadPoolExecutor(max_workers=4) as pool:
+        results = list(pool.map(v.validate_json, [input_json] * 8))
+    assert results == [expected] * 8
+
+
+def test_validate_without_gil():
+    class MyModel:
+        __slots__ = '__dict__', '__fields_set__'
+
+    v = SchemaValidator(
+        {
+            'type': 'model-class',
+            'class_type': MyModel,
+            'model': {
+                'type': 'model',
+                'fields': {
+                    'int': {'type': 'int', 'alias': 'Int'},
+                    'float': {'type': 'float', 'ge': 0},
+                    'str': 'str',
+                    'bool': 'bool',
+                    'none': {'type': 'optional', 'schema': 'int'},
+                    'tree': {
+                        'type': 'recursive-container',
+                        'name': 'Branch',
+                        'schema': {'type': 'list', 'items': {'type': 'recursive-ref', 'name': 'Branch'}},
+                    },
+                    'dict': {'type': 'dict', 'keys': 'str', 'values': {'type': 'list', 'items': 'float'}},
+                    'any': 'any',
+                    'password': {'type': 'str', 'exclude': True},
+                    'confirm': {
+                        'type': 'str',
+                        'default_factory': lambda data: data['password'],
+                        'default_factory_takes_data': True,
+                    },
+                },
+                'extra_validator': 'int',
+                'config': {'extra': 'allow', 'sort_extra': True},
+            },
+        }
+    )
+    input_json = json.dumps(
+        {
+            'z': 2,
+            'Int': 1,
+            'float': 1,
+            'str': 'a',
+            'bool': True,
+            'none': None,
+            'tree': [[], [[]]],
+            'dict': {'a': [1, 2.5]},
+            'any': {'b': [None]},
+            'password': 'secret',
+            'y': 1,
+        }
+    )
+    expected = {
+        'int': 1,
+        'float': 1.0,
+        'str': 'a',
+        'bool': True,
+        'none': None,
+        'tree': [[], [[]]],
+        'dict': {'a': [1.0, 2.5]},
+        'any': {'b': [None]},
+        'confirm': 'secret',
+        'y': 1,
+        'z': 2,
+    }
+    m = v.validate_json(input_json)
+    assert isinstance(m, MyModel)
+    assert m.__dict__ == expected
+    assert list(m.__dict__) == list(expected)
+    assert m.__fields_set__ == set(expected) - {'confirm'}
+    assert type(m.__dict__['float']) is float
+
+    # with coercions reported validation holds the GIL throughout, the output is the same
+    m, coercions = v.validate_json(input_json, report_coercions=True)
+    assert m.__dict__ == expected
+    assert list(m.__dict__) == list(expected)
+    assert coercions == []
+
+
+def test_validate_without_gil_invalid():
+    v = SchemaValidator(
+        {'type': 'list', 'items': {'type': 'model', 'fields': {'a': 'int', 'b': {'type': 'float', 'lt': 10}}}}
+    )
+    # input the GIL-free validation can't handle is validated again as usual, e.g. lax mode coercions
+    assert v.validate_json('[{"a": 1, "b": 2}, {"a": "3", "b": 4}]') == [
+        ({'a': 1, 'b': 2.0}, {'a', 'b'}),
+        ({'a': 3, 'b': 4.0}, {'a', 'b'}),
+    ]
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_json('[{"a": 1, "b": 2}, {"a": 1, "b": 20}, {"b": 3}]')
+    assert [(e['kind'], e['loc']) for e in exc_info.value.errors()] == [
+        ('float_less_than', [1, 'b']),
+        ('missing', [2, 'a']),
+    ]
+
+
+@pytest.mark.parametrize(
+    'input_value',
+    [
+        b'{"a": [1, 2]}',
+        bytearray(b'{"a": [1, 2]}'),
+        memoryview(b'{"a": [1, 2]}'),
+        b'\xef\xbb\xbf{"a": [1, 2]}',
+        '{"a": [1, 2]}'.encode('utf-16'),
+        b'\xfe\xff' + '{"a": [1, 2]}'.encode('utf-16-be'),
+        '\ufeff{"a": [1, 2]}'.encode('utf-16-le'),
+    ],
+    ids=['bytes', 'bytearray', 'memoryview', 'utf-8-bom', 'utf-16', 'utf-16-be', 'utf-16-le'],
+)
+def test_bytes_input(input_value):
+    v = SchemaValidator({'type': 'dict', 'keys': 'str', 'values': {'type': 'list', 'items': 'int'}})
+    assert v.validate_json(input_value) == {'a': [1, 2]}
+    assert v.isinstance_json(input_value) is True
+
+
+def test_bytes_input_invalid():
+    v = SchemaValidator('int')
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_json(b'"\xff"')
+    assert exc_info.value.errors()[0]['kind'] == 'invalid_json'
+    assert exc_info.value.errors()[0]['input_value'] == b'"\xff"'
+    with pytest.raises(ValidationError, match='invalid utf-16: odd number of bytes'):
+        v.validate_json(b'\xff\xfe1\x00\x00')
+    assert v.isinstance_json(b'\xff\xfe\x00\xd8') is False
+
+    with pytest.raises(TypeError, match='JSON input should be str, bytes, bytearray or memoryview, got int'):
+        v.validate_json(123)
+
+
+def test_return_input():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': 'int', 'b': {'type': 'list', 'items': 'float'}}})
+    assert v.validate_json('{"a": "1", "b": [1, "2.5"]}', return_input=True) == (
+        ({'a': 1, 'b': [1.0, 2.5]}, {'a', 'b'}),
+        {'a': '1', 'b': [1, '2.5']},
+    )
+
+
+def test_return_input_collect_errors():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': 'int'}})
+    (value, errors), parsed = v.validate_json('{"a": "x"}', collect_errors=True, return_input=True)
+    assert value == ({}, set())
+    assert [e['loc'] for e in errors] == [['a']]
+    assert parsed == {'a': 'x'}
+
+    (value, errors), parsed = v.validate_json('{"a": ', collect_errors=True, return_input=True)
+    assert value is None
+    assert [e['kind'] for e in errors] == ['invalid_json']
+    assert parsed is None
+
+
+def test_validate_json_items():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': 'int'}})
+    results = []
+    invalid = v.validate_json_items('[{"a": 1}, {"a": "x"}, {}, {"a": "3"}]', lambda *args: results.append(args))
+    assert invalid == 2
+    assert results[0] == (({'a': 1}, {'a'}), None)
+    assert results[3] == (({'a': 3}, {'a'}), None)
+
+    value, error = results[1]
+    assert value is None
+    assert isinstance(error, ValidationError)
+    assert [(e['loc'], e['kind']) for e in error.errors()] == [([1, 'a'], 'int_parsing')]
+    assert [e['loc'] for e in results[2][1].errors()] == [[2, 'a']]
+
+
+@pytest.mark.parametrize('input_value', ['[1, 2]', b'[1, 2]', bytearray(b'[1, 2]'), b'\xef\xbb\xbf[1, 2]'])
+def test_validate_json_items_input_types(input_value):
+    v = SchemaValidator('int')
+    results = []
+    assert v.validate_json_items(input_value, lambda item, error: results.append(item)) == 0
+    assert results == [1, 2]
+
+
+class ChunkedFile(io.BytesIO):
+    """returns at most 3 bytes from each `read` and records the reads"""
+
+    def __init__(self, data):
+        super().__init__(data)
+        self.reads = 0
+
+    def read(self, size=-1):
+        self.reads += 1
+        return super().read(min(size, 3))
+
+
+@pytest.mark.parametrize(
+    'data',
+    [
+        b'[1, 2, 3]',
+        b'\xef\xbb\xbf[1, 2, 3]',
+        '[1, 2, 3]'.encode('utf-16'),
+        '\ufeff[1, 2, 3]'.encode('utf-16-be'),
+    ],
+)
+def test_validate_json_items_file(data):
+    v = SchemaValidator('int')
+    f = ChunkedFile(data)
+    results = []
+    assert v.validate_json_items(f, lambda item, error: results.append(item)) == 0
+    assert results == [1, 2, 3]
+    assert f.reads > 1
+
+
+def test_validate_json_items_file_streamed():
+    v = SchemaValidator('int')
+    f = ChunkedFile(b'[1, 2, ' + b' ' * 1000 + b'3]')
+    positions = []
+    v.validate_json_items(f, lambda item, error: positions.append(f.tell()))
+    # items are validated before the rest of the file is read
+    assert positions[0] < 100
+    assert positions[2] == len(f.getvalue())
+
+
+def test_validate_json_items_file_errors():
+    v = SchemaValidator('int')
+    results = []
+    with pytest.raises(ValidationError, match='kind=invalid_json'):
+        v.validate_json_items(io.BytesIO(b'[1, x]'), lambda item, error: results.append(item))
+    assert results == [1]
+
+    with pytest.raises(ValidationError, match='invalid utf-16'):
+        v.validate_json_items(io.BytesIO(b'\xff\xfe[\x00\x00\xdc]\x00'), lambda item, error: None)
+
+    with pytest.raises(TypeError, match='JSON files should be opened in binary mode, read\\(\\) returned str'):
+        v.validate_json_items(io.StringIO('[1]'), lambda item, error: None)
+
+    class BrokenFile:
+        def read(self, size):
+            raise OSError('disk on fire')
+
+    with pytest.raises(OSError, match='disk on fire'):
+        v.validate_json_items(BrokenFile(), lambda item, error: None)
+
+
+def test_validate_json_items_invalid_json():
+    v = SchemaValidator('int')
+    results = []
+    with pytest.raises(ValidationError, match='kind=invalid_json'):
+        v.validate_json_items('[1, 2, x]', lambda item, error: results.append(item))
+    # items before the invalid JSON have already been validated
+    assert results == [1, 2]
+
+    with pytest.raises(ValidationError, match='expected a JSON array'):
+        v.validate_json_items('{"a": 1}', lambda item, error: None)
+
+
+def test_validate_json_items_callback_error():
+    v = SchemaValidator('int')
+    results = []
+
+    def callback(item, error):
+        if item == 2:
+            raise RuntimeError('stop')
+        results.append(item)
+
+    with pytest.raises(RuntimeError, match='stop'):
+        v.validate_json_items('[1, 2, 3]', callback)
+    assert results == [1]
+
+
+def test_validate_json_items_context():
+    def f(input_value, *, context, **kwargs):
+        return input_value + context
+
+    v = SchemaValidator({'type': 'function', 'mode': 'after', 'function': f, 'schema': 'int'})
+    results = []
+    v.validate_json_items('[1, 2]', lambda item, error: results.append(item), context=10)
+    assert results == [11, 12]
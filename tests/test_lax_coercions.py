@@ -0,0 +1,79 @@
+import re
+
+import pytest
+
+from pydantic_core import SchemaError, SchemaValidator, ValidationError
+
+from .conftest import Err
+
+
+@pytest.mark.parametrize(
+    'schema,input_value,expected',
+    [
+        ({'type': 'int', 'disabled_coercions': ['float_to_int']}, 1, 1),
+        ({'type': 'int', 'disabled_coercions': ['float_to_int']}, '2', 2),
+        ({'type': 'int', 'disabled_coercions': ['float_to_int']}, 3.0, Err('kind=int_type')),
+        ({'type': 'int', 'disabled_coercions': ['str_to_int', 'bool_to_int']}, '4', Err('kind=int_type')),
+        ({'type': 'int', 'disabled_coercions': ['str_to_int', 'bool_to_int']}, True, Err('kind=int_type')),
+        ({'type': 'int', 'gt': 0, 'disabled_coercions': ['str_to_int']}, '5', Err('kind=int_type')),
+        ({'type': 'float', 'disabled_coercions': ['str_to_float']}, 1, 1.0),
+        ({'type': 'float', 'disabled_coercions': ['str_to_float']}, '1.5', Err('kind=float_type')),
+        ({'type': 'str', 'disabled_coercions': ['int_to_str']}, 'x', 'x'),
+        ({'type': 'str', 'disabled_coercions': ['int_to_str']}, 1.5, '1.5'),
+        ({'type': 'str', 'disabled_coercions': ['int_to_str']}, 1, Err('kind=str_type')),
+        ({'type': 'bool', 'disabled_coercions': ['str_to_bool']}, 1, True),
+        ({'type': 'bool', 'disabled_coercions': ['str_to_bool']}, 'true', Err('kind=bool_type')),
+    ],
+)
+def test_disabled_coercions(py_or_json, schema, input_value, expected):
+    v = py_or_json(schema)
+    if isinstance(expected, Err):
+        with pytest.raises(ValidationError, match=re.escape(expected.message)):
+            v.validate_test(input_value)
+    else:
+        assert v.validate_test(input_value) == expected
+
+
+def test_disabled_bytes():
+    v = SchemaValidator({'type': 'str', 'disabled_coercions': ['bytes_to_str']})
+    assert v.validate_python('x') == 'x'
+    assert v.validate_python(1) == '1'
+    for input_value in (b'x', bytearray(b'x'), memoryview(b'x')):
+        with pytest.raises(ValidationError, match='kind=str_type'):
+            v.validate_python(input_value)
+
+    v = SchemaValidator({'type': 'int', 'disabled_coercions': ['bytes_to_int']})
+    assert v.validate_python('1') == 1
+    with pytest.raises(ValidationError, match='kind=int_type'):
+        v.validate_python(b'1')
+
+
+def test_disabled_config():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {'a': 'int', 'b': 'str', 'c': {'type': 'int', 'disabled_coercions': []}},
+            'config': {'disabled_coercions': ['str_to_int', 'int_to_str']},
+        }
+    )
+    assert v.validate_python({'a': 1, 'b': 'x', 'c': '3'}) == ({'a': 1, 'b': 'x', 'c': 3}, {'a', 'b', 'c'})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': '1', 'b': 2, 'c': 3})
+    assert [(e['loc'], e['kind']) for e in exc_info.value.errors()] == [(['a'], 'int_type'), (['b'], 'str_type')]
+
+
+def test_disabled_strict_unaffected():
+    v = SchemaValidator({'type': 'int', 'strict': True, 'disabled_coercions': ['float_to_int']})
+    with pytest.raises(ValidationError, match='kind=int_type'):
+        v.validate_python('1')
+    assert v.validate_python(1) == 1
+
+
+def test_coerce_numbers_to_str_precedence():
+    v = SchemaValidator({'type': 'str', 'coerce_numbers_to_str': True, 'disabled_coercions': ['int_to_str']})
+    assert v.validate_python(1) == '1'
+
+
+def test_invalid_coercion():
+    with pytest.raises(SchemaError, match="Invalid lax coercion: 'str_to_str'"):
+        SchemaValidator({'type': 'int', 'disabled_coercions': ['str_to_str']})
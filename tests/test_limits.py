@@ -0,0 +1,141 @@
+import time
+
+import pytest
+
+from pydantic_core import SchemaValidator, ValidationError
+
+
+def test_max_items_total():
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'list', 'items': 'int'}})
+    assert v.validate_python([[1, 2], [3]], limits={'max_items_total': 5}) == [[1, 2], [3]]
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python([[1, 2], [3, 4]], limits={'max_items_total': 5})
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'limit_items_total',
+            'loc': [1],
+            'message': 'Input must not have more than 5 items in total',
+            'input_value': [3, 4],
+            'context': {'max_items_total': 5},
+        }
+    ]
+
+
+def test_wrap_validator():
+    v = SchemaValidator(
+        {
+            'type': 'function',
+            'mode': 'wrap',
+            'function': lambda input_value, validator, **kwargs: validator(input_value),
+            'schema': {'type': 'list', 'items': 'int'},
+        }
+    )
+    assert v.validate_python(list(range(10)), limits={'max_items_total': 10}) == list(range(10))
+    with pytest.raises(ValidationError, match=r'Input must not have more than 10 items in total'):
+        v.validate_python(list(range(100)), limits={'max_items_total': 10})
+
+
+def test_max_string_length():
+    v = SchemaValidator({'type': 'dict', 'keys': 'str', 'values': 'str'})
+    assert v.validate_json('{"a": "ñññ"}', limits={'max_string_length': 3}) == {'a': 'ñññ'}
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 'abcd'}, limits={'max_string_length': 3})
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'limit_string_length',
+            'loc': ['a'],
+            'message': 'String must not be longer than 3 characters',
+            'input_value': 'abcd',
+            'context': {'max_string_length': 3},
+        }
+    ]
+
+
+
+def test_max_string_bytes_total():
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'list', 'items': 'str'}})
+    assert v.validate_python([['ab', 'cd'], ['ñ']], limits={'max_string_bytes_total': 6}) == [['ab', 'cd'], ['ñ']]
+    assert v.validate_json('[["ab", "cd"], ["ñ"]]', limits={'max_string_bytes_total': 6}) == [['ab', 'cd'], ['ñ']]
+    with pytest.raises(ValidationError) as exc_info:
+        # 'ñ' is two bytes
+        v.validate_python([['ab', 'cd'], ['ñ', 'x'], [1]], limits={'max_string_bytes_total': 5})
+    # the only error is the limit, not other errors before or after it
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'limit_string_bytes_total',
+            'loc': [],
+            'message': 'Input must not have more than 5 bytes of strings in total',
+            'input_value': None,
+            'context': {'max_string_bytes_total': 5},
+        }
+    ]
+
+
+def test_max_string_bytes_total_collect_errors():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': 'int', 'b': 'str', 'c': 'str'}})
+    input_value = {'a': 'x', 'b': 'abc', 'c': 'd'}
+    output, errors = v.validate_python(input_value, collect_errors=True, limits={'max_string_bytes_total': 4})
+    assert output == ({'b': 'abc', 'c': 'd'}, {'b', 'c'})
+    assert [e['kind'] for e in errors] == ['int_parsing']
+    # collected errors are discarded too
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(input_value, collect_errors=True, limits={'max_string_bytes_total': 3})
+    assert [e['kind'] for e in exc_info.value.errors()] == ['limit_string_bytes_total']
+
+def test_max_depth():
+    v = SchemaValidator(
+        {
+            'type': 'recursive-container',
+            'name': 'Branch',
+            'schema': {'type': 'list', 'items': {'type': 'recursive-ref', 'name': 'Branch'}},
+        }
+    )
+    assert v.validate_python([[[]]], limits={'max_depth': 3}) == [[[]]]
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python([[[[]]]], limits={'max_depth': 3})
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'limit_depth',
+            'loc': [0, 0, 0],
+            'message': 'Input must not be nested more than 3 levels deep',
+            'input_value': [],
+            'context': {'max_depth': 3},
+        }
+    ]
+    # depth is tracked per branch, not in total
+    assert v.validate_python([[[]], [[]], [[]]], limits={'max_depth': 3}) == [[[]], [[]], [[]]]
+
+
+def test_timeout():
+    def slow(input_value, **kwargs):
+        time.sleep(0.02)
+        return input_value
+
+    v = SchemaValidator(
+        {'type': 'list', 'items': {'type': 'function', 'mode': 'after', 'function': slow, 'schema': 'list'}}
+    )
+    assert v.validate_python([[1]], limits={'timeout': 10}) == [[1]]
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python([[1], [2]], limits={'timeout': 0.01})
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'limit_timeout',
+            'loc': [1],
+            'message': 'Validation took longer than 0.01 seconds',
+            'input_value': [2],
+            'context': {'timeout': 0.01},
+        }
+    ]
+
+
+def test_no_limits():
+    v = SchemaValidator({'type': 'list', 'items': 'str'})
+    assert v.validate_python(['x' * 1000] * 1000) == ['x' * 1000] * 1000
+
+
+def test_invalid_limits():
+    v = SchemaValidator('str')
+    with pytest.raises(ValueError, match='Unknown limit: "max_foobar"'):
+        v.validate_python('x', limits={'max_foobar': 1})
+    with pytest.raises(ValueError, match='Invalid timeout: -1'):
+        v.validate_python('x', limits={'timeout': -1})
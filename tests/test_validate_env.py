@@ -0,0 +1,174 @@
+import os
+
+import pytest
+
+from pydantic_core import SchemaValidator, ValidationError
+
+
+@pytest.fixture(scope='module')
+def settings_validator():
+    return SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'port': 'int',
+                'debug': {'type': 'bool', 'default': False},
+                'api_key': {'type': 'str', 'env': ['API_KEY', 'LEGACY_KEY']},
+                'db': {
+                    'type': 'model',
+                    'fields': {'host': 'str', 'port': {'type': 'int', 'default': 5432}},
+                    'config': {'extra': 'forbid'},
+                },
+            },
+        }
+    )
+
+
+def test_env(settings_validator):
+    env = {'APP_PORT': '8000', 'APP_DB__HOST': 'localhost', 'LEGACY_KEY': 'secret', 'OTHER': 'x'}
+    assert settings_validator.validate_env(env, env_prefix='APP_', env_nested_delimiter='__') == (
+        {'port': 8000, 'debug': False, 'api_key': 'secret', 'db': ({'host': 'localhost', 'port': 5432}, {'host'})},
+        {'port', 'api_key', 'db'},
+    )
+
+
+def test_env_names_order(settings_validator):
+    env = {'port': '1', 'legacy_key': 'legacy', 'api_key': 'key', 'db__host': 'h', 'db__port': '1'}
+    output, _ = settings_validator.validate_env(env, env_nested_delimiter='__')
+    assert output['api_key'] == 'key'
+    assert output['db'] == ({'host': 'h', 'port': 1}, {'host', 'port'})
+
+
+def test_case_sensitive(settings_validator):
+    env = {'PORT': '1', 'API_KEY': 'key', 'DB__HOST': 'h'}
+    assert settings_validator.validate_env(env, env_nested_delimiter='__')[0]['port'] == 1
+
+    with pytest.raises(ValidationError) as exc_info:
+        settings_validator.validate_env(env, env_nested_delimiter='__', case_sensitive=True)
+    assert [(e['kind'], e['loc']) for e in exc_info.value.errors()] == [('missing', ['port']), ('missing', ['db'])]
+
+
+def test_errors(settings_validator):
+    env = {'PORT': 'x', 'API_KEY': 'key', 'DB__HOST': 'h', 'DB__NAME': 'n'}
+    with pytest.raises(ValidationError) as exc_info:
+        settings_validator.validate_env(env, env_nested_delimiter='__')
+    assert [(e['kind'], e['loc'], e['input_value']) for e in exc_info.value.errors()] == [
+        ('int_parsing', ['port'], 'x'),
+        # keys which aren't fields keep the case of the variable
+        ('extra_forbidden', ['db', 'NAME'], {'host': 'h', 'NAME': 'n'}),
+    ]
+
+
+def test_nested_without_delimiter(settings_validator):
+    # without a delimiter nested models can't be set
+    with pytest.raises(ValidationError) as exc_info:
+        settings_validator.validate_env({'PORT': '1', 'API_KEY': 'key', 'DB__HOST': 'h'})
+    assert [(e['kind'], e['loc']) for e in exc_info.value.errors()] == [('missing', ['db'])]
+
+
+def test_deeply_nested():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': {
+                    'type': 'model',
+                    'fields': {'b': {'type': 'model', 'fields': {'c': 'int'}}, 'd': {'type': 'dict', 'values': 'int'}},
+                }
+            },
+        }
+    )
+    env = {'A_B_C': '1', 'A_D_X': '2', 'A_D_y': '3'}
+    output, _ = v.validate_env(env, env_nested_delimiter='_')
+    assert output == {'a': ({'b': ({'c': 1}, {'c'}), 'd': {'X': 2, 'y': 3}}, {'b', 'd'})}
+
+
+def test_nested_field_case():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'db': {
+                    'type': 'optional',
+                    'schema': {
+                        'type': 'model',
+                        'fields': {
+                            'hostName': 'str',
+                            'replica': {'type': 'model', 'fields': {'host_name': {'type': 'str', 'alias': 'HostName'}}},
+                        },
+                    },
+                }
+            },
+        }
+    )
+    env = {'DB__HOSTNAME': 'x', 'db__Replica__hostname': 'y'}
+    assert v.validate_env(env, env_nested_delimiter='__') == (
+        {'db': ({'hostName': 'x', 'replica': ({'host_name': 'y'}, {'host_name'})}, {'hostName', 'replica'})},
+        {'db'},
+    )
+
+    # with case_sensitive keys must match fields exactly
+    env = {'db__hostname': 'x', 'db__replica__HostName': 'y'}
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_env(env, env_nested_delimiter='__', case_sensitive=True)
+    assert [(e['kind'], e['loc']) for e in exc_info.value.errors()] == [('missing', ['db', 'hostName'])]
+
+
+def test_alias():
+    v = SchemaValidator({'type': 'model', 'fields': {'field_a': {'type': 'int', 'alias': 'FieldA'}}})
+    assert v.validate_env({'MY_FIELDA': '1'}, env_prefix='my_') == ({'field_a': 1}, {'field_a'})
+
+
+def test_secrets_dir(settings_validator, tmp_path):
+    (tmp_path / 'Api_Key').write_text('from-file\n')
+    (tmp_path / 'APP_PORT').write_text('9000')
+    env = {'APP_PORT': '8000', 'APP_DB__HOST': 'h'}
+    output, _ = settings_validator.validate_env(
+        env, env_prefix='APP_', env_nested_delimiter='__', secrets_dir=tmp_path
+    )
+    # variables take priority over secrets, file names match case-insensitively
+    assert output['port'] == 8000
+    assert output['api_key'] == 'from-file'
+
+    with pytest.raises(ValidationError, match='api_key\n  Field required'):
+        settings_validator.validate_env(
+            env, env_prefix='APP_', env_nested_delimiter='__', secrets_dir=tmp_path, case_sensitive=True
+        )
+
+    with pytest.raises(ValueError, match='is not a directory'):
+        settings_validator.validate_env(env, secrets_dir=tmp_path / 'missing')
+
+
+def test_model_class():
+    class MyModel:
+        __slots__ = '__dict__', '__fields_set__'
+
+    v = SchemaValidator(
+        {'type': 'model-class', 'class_type': MyModel, 'model': {'type': 'model', 'fields': {'port': 'int'}}}
+    )
+    m = v.validate_env({'PORT': '1'})
+    assert isinstance(m, MyModel)
+    assert m.port == 1
+
+
+def test_os_environ():
+    v = SchemaValidator({'type': 'model', 'fields': {'port': 'int'}})
+    os.environ['PYDANTIC_CORE_TEST_PORT'] = '1234'
+    try:
+        assert v.validate_env(os.environ, env_prefix='PYDANTIC_CORE_TEST_') == ({'port': 1234}, {'port'})
+    finally:
+        del os.environ['PYDANTIC_CORE_TEST_PORT']
+
+
+@pytest.mark.parametrize(
+    'schema,env,error',
+    [
+        ('int', {}, 'validate_env requires a model schema'),
+        ({'type': 'model', 'fields': {'port': 'int'}}, ['PORT'], 'validate_env input should be a mapping, got list'),
+        ({'type': 'model', 'fields': {'port': 'int'}}, {'PORT': 1}, 'validate_env keys and values should be str'),
+    ],
+)
+def test_input_errors(schema, env, error):
+    v = SchemaValidator(schema)
+    with pytest.raises(TypeError, match=error):
+        v.validate_env(env)
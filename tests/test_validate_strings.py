@@ -0,0 +1,74 @@
+import re
+
+import pytest
+
+from pydantic_core import SchemaValidator, ValidationError
+
+
+def test_model():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'port': 'int',
+                'debug': 'bool',
+                'ratio': 'float',
+                'name': 'str',
+                'hosts': {'type': 'list', 'items': 'str'},
+                'db': {'type': 'model', 'fields': {'timeout': 'float'}},
+            },
+        }
+    )
+    data = {
+        'port': '8000',
+        'debug': 'true',
+        'ratio': '0.5',
+        'name': 'app',
+        'hosts': ['a', 'b'],
+        'db': {'timeout': '2.5'},
+    }
+    assert v.validate_strings(data) == (
+        {
+            'port': 8000,
+            'debug': True,
+            'ratio': 0.5,
+            'name': 'app',
+            'hosts': ['a', 'b'],
+            'db': ({'timeout': 2.5}, {'timeout'}),
+        },
+        {'port', 'debug', 'ratio', 'name', 'hosts', 'db'},
+    )
+
+
+def test_leaf():
+    v = SchemaValidator({'type': 'dict', 'keys': 'str', 'values': 'int'})
+    assert v.validate_strings({'a': '1', 'b': '2'}) == {'a': 1, 'b': 2}
+    assert SchemaValidator('int').validate_strings('42') == 42
+
+
+def test_errors():
+    v = SchemaValidator({'type': 'model', 'fields': {'port': 'int'}})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_strings({'port': 'x'})
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'int_parsing',
+            'loc': ['port'],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'x',
+        }
+    ]
+
+
+@pytest.mark.parametrize(
+    'input_value,message',
+    [
+        (1, 'validate_strings input should be str, or a dict or list of str, got int'),
+        ({'a': 1}, 'validate_strings input should be str, or a dict or list of str, got int'),
+        ({1: 'a'}, 'validate_strings dict keys should be str, got int'),
+    ],
+)
+def test_invalid_input(input_value, message):
+    v = SchemaValidator({'type': 'dict'})
+    with pytest.raises(TypeError, match=re.escape(message)):
+        v.validate_strings(input_value)
@@ -0,0 +1,139 @@
+import pytest
+
+from pydantic_core import SchemaValidator
+
+
+@pytest.fixture
+def model_validator():
+    return SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': 'int',
+                'sub': {'type': 'model', 'fields': {'b': 'str'}},
+                'items': {'type': 'list', 'items': {'type': 'model', 'fields': {'c': 'int'}}},
+            },
+        }
+    )
+
+
+def test_no_warnings(model_validator):
+    data = {'a': 1, 'sub': {'b': 'x', 'extra': 2}, 'items': []}
+    assert model_validator.validate_python(data) == (
+        {'a': 1, 'sub': ({'b': 'x'}, {'b'}), 'items': []},
+        {'a', 'sub', 'items'},
+    )
+
+
+def test_collect(model_validator):
+    data = {'a': 1, 'sub': {'b': 'x', 'extra': 2}, 'items': [{'c': 1}, {'c': 2, 'd': 3}], 'other': 4}
+    value, warnings = model_validator.validate_python(data, warnings='collect')
+    assert value == (
+        {'a': 1, 'sub': ({'b': 'x'}, {'b'}), 'items': [({'c': 1}, {'c'}), ({'c': 2}, {'c'})]},
+        {'a', 'sub', 'items'},
+    )
+    assert warnings == [
+        {'loc': ['sub', 'extra'], 'message': 'Extra field ignored'},
+        {'loc': ['items', 1, 'd'], 'message': 'Extra field ignored'},
+        {'loc': ['other'], 'message': 'Extra field ignored'},
+    ]
+
+
+def test_collect_json(model_validator):
+    input_json = '{"a": 1, "sub": {"b": "x"}, "items": [], "x": 1}'
+    value, warnings = model_validator.validate_json(input_json, warnings='collect')
+    assert value == ({'a': 1, 'sub': ({'b': 'x'}, {'b'}), 'items': []}, {'a', 'sub', 'items'})
+    assert warnings == [{'loc': ['x'], 'message': 'Extra field ignored'}]
+
+
+def test_collect_none():
+    v = SchemaValidator('int')
+    assert v.validate_python('123', warnings='collect') == (123, [])
+
+
+def test_warn(model_validator):
+    data = {'a': 1, 'sub': {'b': 'x', 'extra': 2}, 'items': []}
+    with pytest.warns(UserWarning, match='sub -> extra: Extra field ignored'):
+        value = model_validator.validate_python(data, warnings='warn')
+    assert value == ({'a': 1, 'sub': ({'b': 'x'}, {'b'}), 'items': []}, {'a', 'sub', 'items'})
+
+
+def test_union_discards_warnings():
+    v = SchemaValidator(
+        {
+            'type': 'union',
+            'choices': [
+                {'type': 'model', 'fields': {'a': 'int'}},
+                {'type': 'model', 'fields': {'b': 'int'}},
+            ],
+        }
+    )
+    value, warnings = v.validate_python({'b': 2, 'c': 3}, warnings='collect')
+    assert value == ({'b': 2}, {'b'})
+    # the first choice failed, so only warnings from the second choice are kept
+    assert warnings == [{'loc': ['c'], 'message': 'Extra field ignored'}]
+
+
+def test_wrap_validator():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'sub': {
+                    'type': 'function',
+                    'mode': 'wrap',
+                    'function': lambda input_value, validator, **kwargs: validator(input_value),
+                    'schema': {'type': 'model', 'fields': {'b': 'str'}},
+                }
+            },
+        }
+    )
+    value, warnings = v.validate_python({'sub': {'b': 'x', 'extra': 2}}, warnings='collect')
+    assert value == ({'sub': ({'b': 'x'}, {'b'})}, {'sub'})
+    assert warnings == [{'loc': ['sub', 'extra'], 'message': 'Extra field ignored'}]
+
+
+def test_invalid_mode():
+    calls = []
+
+    def f(input_value, **kwargs):
+        calls.append(input_value)
+        return input_value
+
+    v = SchemaValidator({'type': 'function', 'mode': 'plain', 'function': f})
+    with pytest.raises(ValueError, match='Invalid warnings mode: "foobar"'):
+        v.validate_python(1, warnings='foobar')
+    # the mode is checked before validating
+    assert calls == []
+
+
+def test_deprecated_field():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': 'int',
+                'b': {'type': 'int', 'deprecated': True},
+                'c': {'type': 'int', 'deprecated': 'Use "a" instead', 'default': 0},
+                'sub': {'type': 'model', 'fields': {'d': {'type': 'str', 'deprecated': True}}},
+            },
+        }
+    )
+    value, warnings = v.validate_python({'a': 1, 'b': 2, 'sub': {'d': 'x'}}, warnings='collect')
+    assert value == ({'a': 1, 'b': 2, 'c': 0, 'sub': ({'d': 'x'}, {'d'})}, {'a', 'b', 'sub'})
+    # "c" isn't in the input, so there's no warning
+    assert warnings == [
+        {'loc': ['b'], 'message': 'Field is deprecated'},
+        {'loc': ['sub', 'd'], 'message': 'Field is deprecated'},
+    ]
+
+    value, warnings = v.validate_json('{"a": 1, "b": 2, "c": 3, "sub": {"d": "x"}}', warnings='collect')
+    assert warnings[1] == {'loc': ['c'], 'message': 'Use "a" instead'}
+
+    with pytest.warns(DeprecationWarning, match='c: Use "a" instead'):
+        v.validate_python({'a': 1, 'b': 2, 'c': 3, 'sub': {'d': 'x'}}, warnings='warn')
+
+
+def test_deprecated_false():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': {'type': 'int', 'deprecated': False}}})
+    assert v.validate_python({'a': 1}, warnings='collect') == (({'a': 1}, {'a'}), [])
@@ -0,0 +1,37 @@
+import pytest
+
+from pydantic_core import SchemaValidator
+
+
+@pytest.mark.parametrize('input_value', [None, 1, 'foobar', [1, 2], {'a': {'b': [1]}}])
+def test_any(py_or_json, input_value):
+    v = py_or_json('any')
+    assert v.validate_test(input_value) == input_value
+
+
+def test_any_no_copy():
+    v = SchemaValidator('any')
+    input_value = {'a': [1, 2]}
+    assert v.validate_python(input_value) is input_value
+
+
+def test_any_deep_copy():
+    v = SchemaValidator({'type': 'any', 'deep_copy': True})
+    input_value = {'a': [1, 2], 'b': ({'c': 3},)}
+    output = v.validate_python(input_value)
+    assert output == input_value
+    assert output is not input_value
+    assert output['a'] is not input_value['a']
+    assert output['b'][0] is not input_value['b'][0]
+
+    output['a'].append(3)
+    assert input_value == {'a': [1, 2], 'b': ({'c': 3},)}
+
+
+def test_any_deep_copy_model_field():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': {'type': 'any', 'deep_copy': True}}})
+    items = [1, 2]
+    output, fields_set = v.validate_python({'a': items})
+    assert output == {'a': [1, 2]}
+    assert output['a'] is not items
+    assert fields_set == {'a'}
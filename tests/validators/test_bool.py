@@ -38,6 +38,12 @@ def test_bool(py_or_json, input_value, expected):
         assert v.validate_test(input_value) == expected
 
 
+@pytest.mark.parametrize('input_value', [b'yes', bytearray(b'yes'), memoryview(b'yes')])
+def test_bool_from_buffer(input_value):
+    v = SchemaValidator({'type': 'bool'})
+    assert v.validate_python(input_value) is True
+
+
 def test_bool_strict(py_or_json):
     v = py_or_json({'type': 'bool', 'strict': True})
     assert v.validate_test(True) is True
@@ -0,0 +1,77 @@
+import re
+
+import pytest
+
+from pydantic_core import SchemaValidator, ValidationError
+
+from ..conftest import Err
+
+
+@pytest.mark.parametrize(
+    'input_value,expected',
+    [
+        (0, 0j),
+        (1.5, 1.5 + 0j),
+        ('1+2j', 1 + 2j),
+        ('(1-2.5J)', 1 - 2.5j),
+        ('-3j', -3j),
+        ('j', 1j),
+        ('1e3-1e-3j', 1e3 - 1e-3j),
+        ('  4  ', 4 + 0j),
+        ([1, 2], 1 + 2j),
+        ('wrong', Err('unable to parse string as a complex number [kind=complex_parsing')),
+        ('1+2', Err('unable to parse string as a complex number [kind=complex_parsing')),
+        ([1, 2, 3], Err('Value must be a valid complex number [kind=complex_type')),
+        (['a', 'b'], Err('Value must be a valid complex number [kind=complex_type')),
+        ({}, Err('Value must be a valid complex number [kind=complex_type')),
+    ],
+)
+def test_complex(py_or_json, input_value, expected):
+    v = py_or_json('complex')
+    if isinstance(expected, Err):
+        with pytest.raises(ValidationError, match=re.escape(expected.message)):
+            v.validate_test(input_value)
+    else:
+        output = v.validate_test(input_value)
+        assert output == expected
+        assert isinstance(output, complex)
+
+
+@pytest.mark.parametrize(
+    'input_value,expected',
+    [
+        (1 + 2j, 1 + 2j),
+        (b'1+2j', 1 + 2j),
+        ((3, -4.5), 3 - 4.5j),
+        (None, Err('Value must be a valid complex number [kind=complex_type')),
+    ],
+)
+def test_complex_python(input_value, expected):
+    v = SchemaValidator({'type': 'complex'})
+    if isinstance(expected, Err):
+        with pytest.raises(ValidationError, match=re.escape(expected.message)):
+            v.validate_python(input_value)
+    else:
+        assert v.validate_python(input_value) == expected
+
+
+def test_complex_strict():
+    v = SchemaValidator({'type': 'complex', 'strict': True})
+    assert v.validate_python(1 + 2j) == 1 + 2j
+    assert v.validate_json('"1+2j"') == 1 + 2j
+    for input_value in ('1+2j', 1, [1, 2]):
+        with pytest.raises(ValidationError, match=r'kind=complex_type'):
+            v.validate_python(input_value)
+    with pytest.raises(ValidationError, match=r'kind=complex_type'):
+        v.validate_json('1')
+    with pytest.raises(ValidationError, match=r'kind=complex_parsing'):
+        v.validate_json('"wrong"')
+
+
+def test_complex_strict_coercions():
+    v = SchemaValidator(
+        {'type': 'complex', 'strict': True, 'strict_coercions': {'python': ['str_to_complex'], 'json': []}}
+    )
+    assert v.validate_python('1+2j') == 1 + 2j
+    with pytest.raises(ValidationError, match=r'kind=complex_type'):
+        v.validate_json('"1+2j"')
@@ -0,0 +1,58 @@
+import pytest
+
+from pydantic_core import SchemaError, SchemaValidator, ValidationError
+
+
+def test_conditional():
+    v = SchemaValidator({'type': 'conditional', 'if': 'int', 'then': {'type': 'int', 'gt': 0}, 'else': 'str'})
+    assert v.validate_python(1) == 1
+    assert v.validate_python('1') == 1
+    assert v.validate_python('foobar') == 'foobar'
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(-1)
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'int_greater_than',
+            'loc': [],
+            'message': 'Value must be greater than 0',
+            'input_value': -1,
+            'context': {'gt': 0},
+        }
+    ]
+
+
+@pytest.fixture(scope='module')
+def pet_validator():
+    return SchemaValidator(
+        {
+            'type': 'conditional',
+            'if': {'type': 'model', 'fields': {'kind': {'type': 'literal', 'expected': ['cat']}}},
+            'then': {'type': 'model', 'fields': {'kind': 'str', 'lives': 'int'}},
+            'else': {'type': 'model', 'fields': {'kind': 'str', 'barks': 'bool'}},
+        }
+    )
+
+
+def test_conditional_discriminator(pet_validator):
+    v = pet_validator
+    assert v.validate_python({'kind': 'cat', 'lives': '9'}) == ({'kind': 'cat', 'lives': 9}, {'kind', 'lives'})
+    assert v.validate_json('{"kind": "dog", "barks": true}') == ({'kind': 'dog', 'barks': True}, {'kind', 'barks'})
+
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'kind': 'dog', 'lives': 9})
+    assert exc_info.value.errors() == [
+        {'kind': 'missing', 'loc': ['barks'], 'message': 'Field required', 'input_value': {'kind': 'dog', 'lives': 9}}
+    ]
+
+
+def test_conditional_predicate_warnings(pet_validator):
+    value, warnings = pet_validator.validate_python({'kind': 'cat', 'lives': 9, 'x': 1}, warnings='collect')
+    assert value == ({'kind': 'cat', 'lives': 9}, {'kind', 'lives'})
+    # the `if` model also ignores "x", but only warnings from `then` are kept
+    assert warnings == [{'loc': ['x'], 'message': 'Extra field ignored'}]
+
+
+def test_conditional_missing_key():
+    with pytest.raises(SchemaError) as exc_info:
+        SchemaValidator({'type': 'conditional', 'if': 'int', 'then': 'int'})
+    assert exc_info.value.args[0] == 'Error building "conditional" validator:\n  SchemaError: "else" is required'
@@ -0,0 +1,69 @@
+import pickle
+
+import pytest
+
+from pydantic_core import SchemaError, SchemaValidator, ValidationError
+
+
+@pytest.fixture(scope='module')
+def definitions():
+    return {
+        'Foo': {
+            'type': 'model',
+            'name': 'Foo',
+            'fields': {'a': 'int', 'bar': {'type': 'recursive-ref', 'name': 'Bar'}},
+        },
+        'Bar': {
+            'type': 'model',
+            'name': 'Bar',
+            'fields': {
+                'b': 'str',
+                'foo': {'type': 'optional', 'schema': {'type': 'recursive-ref', 'name': 'Foo'}, 'default': None},
+            },
+        },
+    }
+
+
+def test_definitions_schema(definitions):
+    v = SchemaValidator(
+        {
+            'type': 'definitions',
+            'definitions': definitions,
+            'schema': {'type': 'list', 'items': {'type': 'recursive-ref', 'name': 'Foo'}},
+        }
+    )
+    assert v.validate_python([{'a': '1', 'bar': {'b': 'x'}}]) == [
+        ({'a': 1, 'bar': ({'b': 'x', 'foo': None}, {'b'})}, {'a', 'bar'})
+    ]
+
+
+def test_from_definitions(definitions):
+    validators = SchemaValidator.from_definitions(definitions)
+    assert list(validators) == ['Foo', 'Bar']
+    foo, bar = validators['Foo'], validators['Bar']
+
+    assert bar.validate_python({'b': 'x', 'foo': {'a': 1, 'bar': {'b': 'y'}}}) == (
+        {'b': 'x', 'foo': ({'a': 1, 'bar': ({'b': 'y', 'foo': None}, {'b'})}, {'a', 'bar'})},
+        {'b', 'foo'},
+    )
+
+    with pytest.raises(ValidationError) as exc_info:
+        foo.validate_python({'a': 'x', 'bar': {'foo': {'a': 1, 'bar': {'b': 'y'}}}})
+    assert exc_info.value.title == 'Foo'
+    assert [e['loc'] for e in exc_info.value.errors()] == [['a'], ['bar', 'b']]
+
+
+def test_from_definitions_pickle(definitions):
+    foo = SchemaValidator.from_definitions(definitions)['Foo']
+    foo2 = pickle.loads(pickle.dumps(foo))
+    assert foo2.validate_python({'a': 1, 'bar': {'b': 'x'}}) == (
+        {'a': 1, 'bar': ({'b': 'x', 'foo': None}, {'b'})},
+        {'a', 'bar'},
+    )
+
+
+def test_from_definitions_errors():
+    with pytest.raises(SchemaError, match='Recursive reference error: ref \'Missing\' not found'):
+        SchemaValidator.from_definitions({'Foo': {'type': 'recursive-ref', 'name': 'Missing'}})
+    with pytest.raises(SchemaError, match='^Definition "Foo":\n  SchemaError: Unknown schema type: "foobar"'):
+        SchemaValidator.from_definitions({'Foo': 'foobar'})
@@ -54,6 +54,19 @@ def test_mapping():
     with pytest.raises(ValidationError, match='Value must be a valid dictionary'):
         v.validate_python(MyMapping({'1': 2, 3: '4'}))
 
+    v = SchemaValidator(
+        {'type': 'dict', 'strict': True, 'strict_mapping': True, 'keys': {'type': 'int'}, 'values': {'type': 'int'}}
+    )
+    assert v.validate_python(MyMapping({1: 2, 3: 4})) == {1: 2, 3: 4}
+    assert v.validate_python({1: 2}) == {1: 2}
+    with pytest.raises(ValidationError, match='Value must be a valid dictionary'):
+        v.validate_python([(1, 2)])
+
+    v = SchemaValidator(
+        {'type': 'model', 'fields': {'a': {'type': 'dict', 'strict': True}}, 'config': {'dict_strict_mapping': True}}
+    )
+    assert v.validate_python({'a': MyMapping({'b': 1})}) == ({'a': {'b': 1}}, {'a'})
+
 
 def test_dict_mapping():
     class ClassWithDict:
@@ -70,6 +83,65 @@ def test_dict_mapping():
     assert v.validate_python(ClassWithDict()) == {'a': 1, 'b': 2, 'c': 'ham'}
 
 
+
+def test_dict_from_slots():
+    class Base:
+        __slots__ = ('a',)
+
+    class ClassWithSlots(Base):
+        __slots__ = 'b', 'c', '_private'
+
+        def __init__(self):
+            self.a = 1
+            self.b = 2
+            self._private = 3
+
+        def method(self):
+            pass
+
+    v = SchemaValidator({'type': 'dict', 'keys': {'type': 'str'}, 'try_instance_as_dict': True})
+    # `c` is never set so it's omitted
+    assert v.validate_python(ClassWithSlots()) == {'a': 1, 'b': 2}
+
+
+def test_dict_from_custom_dir():
+    class ClassWithDir:
+        def __init__(self):
+            self.a = 1
+            self.callback = print
+
+        def __dir__(self):
+            return ['b']
+
+        @property
+        def b(self):
+            return 2
+
+    v = SchemaValidator({'type': 'dict', 'keys': {'type': 'str'}, 'try_instance_as_dict': True})
+    # attributes set on the instance are included even if they're callable
+    assert v.validate_python(ClassWithDir()) == {'a': 1, 'callback': print, 'b': 2}
+
+
+def test_dict_from_attrs_fields():
+    class Attribute:
+        def __init__(self, name):
+            self.name = name
+
+    class AttrsLike:
+        # like classes generated by attrs with `slots=True`
+        __slots__ = ('x', 'y')
+        __attrs_attrs__ = (Attribute('x'), Attribute('y'))
+
+        def __init__(self):
+            self.x = 1
+            self.y = 'a'
+
+        def __dir__(self):
+            return []
+
+    v = SchemaValidator({'type': 'dict', 'keys': {'type': 'str'}, 'try_instance_as_dict': True})
+    assert v.validate_python(AttrsLike()) == {'x': 1, 'y': 'a'}
+
 def test_key_error():
     v = SchemaValidator({'type': 'dict', 'keys': {'type': 'int'}, 'values': {'type': 'int'}})
     assert v.validate_python({'1': True}) == {1: 1}
@@ -83,3 +155,42 @@ def test_key_error():
             'input_value': 'x',
         }
     ]
+
+
+def test_exotic_key_locations():
+    v = SchemaValidator({'type': 'dict', 'keys': 'any', 'values': 'int'})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({(1, 'a'): 'x', -1: 'y', 1.5: 'z'})
+    # keys which aren't strings or indexes are included in loc as they are
+    assert [e['loc'] for e in exc_info.value.errors()] == [[(1, 'a')], [-1], [1.5]]
+    assert "(1, 'a')\n  Value must be a valid integer" in str(exc_info.value)
+    assert '\n-1\n' in str(exc_info.value)
+
+
+def test_exotic_key_error():
+    v = SchemaValidator({'type': 'dict', 'keys': 'str', 'values': 'any'})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({(1, 2): 1})
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'str_type',
+            'loc': [(1, 2), '[key]'],
+            'message': 'Value must be a valid string',
+            'input_value': (1, 2),
+        }
+    ]
+    assert str(exc_info.value).startswith('1 validation error for dict\n(1, 2) -> [key]\n')
+
+
+def test_key_vs_value_errors():
+    v = SchemaValidator({'type': 'dict', 'keys': 'int', 'values': 'int'})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_json('{"1x": 2, "3": "4x"}')
+    assert [e['loc'] for e in exc_info.value.errors()] == [['1x', '[key]'], ['3']]
+
+    value, coercions = v.validate_python({'1': 2, 3: '4'}, report_coercions=True)
+    assert value == {1: 2, 3: 4}
+    assert coercions == [
+        {'loc': ['1', '[key]'], 'from_type': 'str', 'to_type': 'int'},
+        {'loc': [3], 'from_type': 'str', 'to_type': 'int'},
+    ]
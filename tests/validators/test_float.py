@@ -4,7 +4,7 @@ from decimal import Decimal
 
 import pytest
 
-from pydantic_core import SchemaValidator, ValidationError
+from pydantic_core import SchemaError, SchemaValidator, ValidationError
 
 from ..conftest import Err
 
@@ -37,6 +37,12 @@ def test_float(py_or_json, input_value, expected):
         assert isinstance(output, float)
 
 
+@pytest.mark.parametrize('input_value', [b'4.5', bytearray(b'4.5'), memoryview(b'4.5')])
+def test_float_from_buffer(input_value):
+    v = SchemaValidator({'type': 'float'})
+    assert v.validate_python(input_value) == 4.5
+
+
 @pytest.mark.parametrize(
     'input_value,expected',
     [
@@ -94,6 +100,15 @@ def test_float_strict(py_or_json, input_value, expected):
         ({'multiple_of': 0.5}, 0.5, 0.5),
         ({'multiple_of': 0.5}, 1, 1),
         ({'multiple_of': 0.5}, 0.6, Err('Value must be a multiple of 0.5')),
+        ({'multiple_of': 0.1}, 0.3, Err('Value must be a multiple of 0.1')),
+        ({'multiple_of': 0.1, 'epsilon': 1e-9}, 0.3, 0.3),
+        ({'multiple_of': 0.1, 'epsilon': 1e-9}, -0.7, -0.7),
+        ({'multiple_of': 0.1, 'epsilon': 1e-9}, 0.35, Err('Value must be a multiple of 0.1')),
+        ({'le': 0.3}, 0.1 + 0.2, Err('Value must be less than or equal to 0.3')),
+        ({'le': 0.3, 'epsilon': 1e-9}, 0.1 + 0.2, 0.1 + 0.2),
+        ({'ge': 0.3, 'epsilon': 1e-9}, 0.29999999999, 0.29999999999),
+        ({'lt': 0.3, 'epsilon': 1e-9}, 0.1 + 0.2, Err('Value must be less than 0.3')),
+        ({'gt': 0.3, 'epsilon': 1e-9}, 0.1 + 0.2, Err('Value must be greater than 0.3')),
     ],
 )
 def test_float_kwargs(py_or_json, kwargs, input_value, expected):
@@ -145,9 +160,15 @@ def test_union_float_simple(py_or_json):
 
 def test_float_repr():
     v = SchemaValidator({'type': 'float'})
-    assert repr(v) == 'SchemaValidator(name="float", validator=Float(\n    FloatValidator,\n))'
+    assert repr(v) == (
+        'SchemaValidator(name="float", validator=Float(\n'
+        '    FloatValidator {\n        strict_protocols: true,\n    },\n))'
+    )
     v = SchemaValidator({'type': 'float', 'strict': True})
-    assert repr(v) == 'SchemaValidator(name="strict-float", validator=StrictFloat(\n    StrictFloatValidator,\n))'
+    assert repr(v) == (
+        'SchemaValidator(name="strict-float", validator=StrictFloat(\n'
+        '    StrictFloatValidator {\n        strict_protocols: true,\n    },\n))'
+    )
     v = SchemaValidator({'type': 'float', 'multiple_of': 7})
     assert repr(v).startswith('SchemaValidator(name="constrained-float", validator=ConstrainedFloat(\n')
 
@@ -170,3 +191,44 @@ def test_float_nan(py_or_json):
     assert v.validate_test('-' + '1' * 800) == float('-inf')
     r = v.validate_test('nan')
     assert math.isnan(r)
+
+
+def test_invalid_epsilon():
+    with pytest.raises(SchemaError, match='Invalid epsilon: -1, must be a non-negative number'):
+        SchemaValidator({'type': 'float', 'le': 1, 'epsilon': -1})
+
+
+class FloatLike:
+    """Like numpy floats (without subclassing `float`), implements `__float__`"""
+
+    def __init__(self, value):
+        self.value = value
+
+    def __float__(self):
+        return self.value
+
+
+class IndexInt:
+    def __index__(self):
+        return 42
+
+
+@pytest.mark.parametrize('input_value,expected', [(FloatLike(4.5), 4.5), (IndexInt(), 42.0)])
+@pytest.mark.parametrize('schema', [{'type': 'float'}, {'type': 'float', 'strict': True}, {'type': 'float', 'ge': 0}])
+def test_float_protocols(schema, input_value, expected):
+    v = SchemaValidator(schema)
+    output = v.validate_python(input_value)
+    assert output == expected
+    assert type(output) is float
+
+
+def test_float_strict_protocols():
+    v = SchemaValidator({'type': 'float', 'strict': True, 'strict_protocols': False})
+    assert v.validate_python(4.5) == 4.5
+    assert v.validate_python(4) == 4.0
+    for input_value in FloatLike(4.5), IndexInt(), Decimal('4.5'), True:
+        with pytest.raises(ValidationError, match='Value must be a valid number'):
+            v.validate_python(input_value)
+
+    v = SchemaValidator({'type': 'float', 'strict_protocols': False})
+    assert v.validate_python(FloatLike(4.5)) == 4.5
@@ -1,9 +1,11 @@
+import asyncio
 import re
 from copy import deepcopy
 
 import pytest
 
 from pydantic_core import SchemaValidator, ValidationError
+from pydantic_core._pydantic_core import PyLineError
 
 
 def test_function_before():
@@ -125,7 +127,7 @@ def test_function_after_data():
         {'field_a': 123, 'field_b': '321 Changed'},
         {'field_b', 'field_a'},
     )
-    assert f_kwargs == {'data': {'field_a': 123}, 'config': None}
+    assert f_kwargs == {'data': {'field_a': 123}, 'config': None, 'context': None}
 
 
 def test_function_after_config():
@@ -146,7 +148,7 @@ def test_function_after_config():
     )
 
     assert v.validate_python({'test_field': 321}) == ({'test_field': '321 Changed'}, {'test_field'})
-    assert f_kwargs == {'data': {}, 'config': {'foo': 'bar'}}
+    assert f_kwargs == {'data': {}, 'config': {'foo': 'bar'}, 'context': None}
 
 
 def test_config_no_model():
@@ -162,7 +164,7 @@ def test_config_no_model():
     )
 
     assert v.validate_python(123) == '123 Changed'
-    assert f_kwargs == {'data': None, 'config': None}
+    assert f_kwargs == {'data': None, 'config': None, 'context': None}
 
 
 def test_function_plain():
@@ -233,3 +235,278 @@ def test_class_with_validator():
     assert exc_info.value.errors() == [
         {'kind': 'str_type', 'loc': [], 'message': 'Value must be a valid string', 'input_value': True}
     ]
+
+
+def test_function_raise_cause():
+    def f(input_value, **kwargs):
+        raise ValueError('foobar')
+
+    v = SchemaValidator({'type': 'function', 'mode': 'plain', 'function': f})
+
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python('input value')
+    cause = exc_info.value.__cause__
+    assert isinstance(cause, ValueError)
+    assert str(cause) == 'foobar'
+    assert cause.__traceback__ is not None
+
+
+def test_function_cause_model():
+    def f(input_value, **kwargs):
+        assert input_value > 10, 'too small'
+        return input_value
+
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': 'int',
+                'b': {'type': 'function', 'mode': 'after', 'function': f, 'schema': 'int'},
+            },
+        }
+    )
+
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 'x', 'b': 5})
+    assert exc_info.value.error_count() == 2
+    assert isinstance(exc_info.value.__cause__, AssertionError)
+    assert str(exc_info.value.__cause__) == 'too small'
+
+
+def test_no_cause():
+    v = SchemaValidator('int')
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python('x')
+    assert exc_info.value.__cause__ is None
+
+
+@pytest.mark.parametrize('mode', ['before', 'after', 'plain', 'wrap'])
+def test_function_context(mode):
+    f_kwargs = None
+
+    def f(input_value, **kwargs):
+        nonlocal f_kwargs
+        f_kwargs = kwargs
+        if mode == 'wrap':
+            return kwargs['validator'](input_value)
+        return input_value
+
+    schema = {'type': 'function', 'mode': mode, 'function': f}
+    if mode != 'plain':
+        schema['schema'] = 'str'
+    v = SchemaValidator(schema)
+
+    context = {'user': 'spam'}
+    assert v.validate_python('x', context=context) == 'x'
+    assert f_kwargs['context'] is context
+    assert v.validate_json('"x"', context=context) == 'x'
+    assert f_kwargs['context'] is context
+    assert v.validate_python('x') == 'x'
+    assert f_kwargs['context'] is None
+
+
+def test_function_context_nested():
+    def f(input_value, *, context, **kwargs):
+        return f'{input_value} by {context["user"]}'
+
+    def g(input_value, *, validator, **kwargs):
+        return validator(input_value)
+
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': {
+                    'type': 'function',
+                    'mode': 'wrap',
+                    'function': g,
+                    'schema': {
+                        'type': 'list',
+                        'items': {'type': 'function', 'mode': 'after', 'function': f, 'schema': 'str'},
+                    },
+                }
+            },
+        }
+    )
+
+    assert v.validate_python({'a': ['x', 'y']}, context={'user': 'spam'}) == ({'a': ['x by spam', 'y by spam']}, {'a'})
+
+
+def test_function_data_order():
+    data_seen = {}
+
+    def f(input_value, data, **kwargs):
+        data_seen[input_value] = dict(data)
+        return input_value
+
+    field_schema = {'type': 'function', 'mode': 'after', 'function': f, 'schema': 'str'}
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': field_schema,
+                'b': 'int',
+                'c': {'type': 'int', 'default': 42},
+                'd': {'type': 'list', 'items': field_schema},
+            },
+        }
+    )
+    with pytest.raises(ValidationError):
+        v.validate_python({'a': 'x', 'b': 'wrong', 'd': ['y', 'z']})
+    # only preceding fields which are valid or have a default are included, in schema order
+    assert data_seen == {'x': {}, 'y': {'a': 'x', 'c': 42}, 'z': {'a': 'x', 'c': 42}}
+
+
+def test_function_data_assignment():
+    f_data = None
+
+    def f(input_value, data, **kwargs):
+        nonlocal f_data
+        f_data = dict(data)
+        return input_value
+
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': 'int',
+                'b': {'type': 'function', 'mode': 'after', 'function': f, 'schema': 'str'},
+                'c': {'type': 'model', 'fields': {'x': 'int'}},
+            },
+        }
+    )
+    m = {'a': 1, 'b': 'old', 'c': ({'x': 1}, {'x'})}
+    assert v.validate_assignment('b', 'new', m) == ({'a': 1, 'b': 'new', 'c': ({'x': 1}, {'x'})}, {'b'})
+    # the value being replaced isn't included
+    assert f_data == {'a': 1, 'c': ({'x': 1}, {'x'})}
+
+    # nested models are validated as a whole
+    assert v.validate_assignment('c', {'x': '2'}, m) == ({'a': 1, 'b': 'new', 'c': ({'x': 2}, {'x'})}, {'c'})
+
+
+def test_function_async():
+    async def f(input_value, **kwargs):
+        await asyncio.sleep(0)
+        return input_value + ' Changed'
+
+    v = SchemaValidator({'type': 'function', 'mode': 'after', 'function': f, 'schema': 'str'})
+
+    async def main():
+        return await v.validate_python_async(b'abc')
+
+    assert asyncio.run(main()) == 'abc Changed'
+
+
+def test_function_async_modes():
+    async def before(input_value, **kwargs):
+        return input_value * 2
+
+    def after(input_value, **kwargs):
+        return input_value + 1
+
+    async def wrap(input_value, *, validator, **kwargs):
+        return f'{validator(input_value)} wrapped'
+
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': {'type': 'function', 'mode': 'before', 'function': before, 'schema': 'int'},
+                'b': {'type': 'function', 'mode': 'after', 'function': after, 'schema': 'int'},
+                'c': {'type': 'function', 'mode': 'wrap', 'function': wrap, 'schema': 'str'},
+            },
+        }
+    )
+
+    async def main():
+        return await asyncio.gather(*(v.validate_python_async({'a': i, 'b': i, 'c': i}) for i in range(3)))
+
+    assert asyncio.run(main()) == [
+        ({'a': 0, 'b': 1, 'c': '0 wrapped'}, {'a', 'b', 'c'}),
+        ({'a': 2, 'b': 2, 'c': '1 wrapped'}, {'a', 'b', 'c'}),
+        ({'a': 4, 'b': 3, 'c': '2 wrapped'}, {'a', 'b', 'c'}),
+    ]
+
+
+def test_function_async_error():
+    async def f(input_value, **kwargs):
+        raise ValueError('foobar')
+
+    v = SchemaValidator({'type': 'function', 'mode': 'plain', 'function': f})
+
+    async def main():
+        return await v.validate_python_async('x')
+
+    with pytest.raises(ValidationError) as exc_info:
+        asyncio.run(main())
+    assert exc_info.value.errors() == [
+        {'kind': 'value_error', 'loc': [], 'message': 'foobar', 'input_value': 'x'}
+    ]
+
+
+def test_function_async_no_loop():
+    v = SchemaValidator('int')
+    with pytest.raises(RuntimeError, match='no running event loop'):
+        v.validate_python_async(1)
+
+
+def test_function_raise_multiple_errors():
+    def check_range(input_value, **kwargs):
+        model_dict, fields_set = input_value
+        if model_dict['start'] > model_dict['end']:
+            raise ValidationError(
+                [
+                    PyLineError('value_error', ['start'], 'must not be after end', model_dict['start'], None),
+                    PyLineError('value_error', ['end'], 'must not be before start', model_dict['end'], None),
+                ],
+                'Range',
+            )
+        return input_value
+
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'range': {
+                    'type': 'function',
+                    'mode': 'after',
+                    'function': check_range,
+                    'schema': {'type': 'model', 'fields': {'start': 'int', 'end': 'int'}},
+                }
+            },
+        }
+    )
+    assert v.validate_python({'range': {'start': 1, 'end': 2}}) == (
+        {'range': ({'start': 1, 'end': 2}, {'start', 'end'})},
+        {'range'},
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'range': {'start': 3, 'end': 2}})
+    # locations are relative to the function
+    assert exc_info.value.errors() == [
+        {'kind': 'value_error', 'loc': ['range', 'start'], 'message': 'must not be after end', 'input_value': 3},
+        {'kind': 'value_error', 'loc': ['range', 'end'], 'message': 'must not be before start', 'input_value': 2},
+    ]
+
+
+def test_function_wrap_inner_errors():
+    def f(input_value, *, validator, **kwargs):
+        return validator(input_value)
+
+    v = SchemaValidator(
+        {'type': 'function', 'mode': 'wrap', 'function': f, 'schema': {'type': 'list', 'items': 'int'}}
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python([1, 'x', 'y'])
+    assert [(e['kind'], e['loc']) for e in exc_info.value.errors()] == [('int_parsing', [1]), ('int_parsing', [2])]
+
+
+def test_function_raise_empty_validation_error():
+    def f(input_value, **kwargs):
+        raise ValidationError([], 'Empty')
+
+    v = SchemaValidator({'type': 'function', 'mode': 'plain', 'function': f})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python('x')
+    # with no errors of its own it's treated like any other `ValueError`
+    assert [(e['kind'], e['loc']) for e in exc_info.value.errors()] == [('value_error', [])]
@@ -150,6 +150,31 @@ def test_int_kwargs(py_or_json, kwargs, input_value, expected):
         assert isinstance(output, int)
 
 
+@pytest.mark.parametrize('input_value', [b'42', bytearray(b'42'), memoryview(b'42'), memoryview(b'x42')[1:]])
+def test_int_from_buffer(input_value):
+    v = SchemaValidator({'type': 'int'})
+    assert v.validate_python(input_value) == 42
+
+
+def test_int_large_bounds(py_or_json):
+    v = py_or_json({'type': 'int', 'le': 2**70, 'ge': -(2**70)})
+    assert v.validate_test(2**62) == 2**62
+    assert v.validate_test(-(2**62)) == -(2**62)
+
+    v = py_or_json({'type': 'int', 'gt': 2**64})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_test(1)
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'int_greater_than',
+            'loc': [],
+            'message': 'Value must be greater than 18446744073709551616',
+            'input_value': 1,
+            'context': {'gt': 2**64},
+        }
+    ]
+
+
 def test_union_int(py_or_json):
     v = py_or_json({'type': 'union', 'choices': [{'type': 'int', 'strict': True}, {'type': 'int', 'multiple_of': 7}]})
     assert v.validate_test('14') == 14
@@ -187,9 +212,15 @@ def test_union_int_simple(py_or_json):
 
 def test_int_repr():
     v = SchemaValidator({'type': 'int'})
-    assert repr(v) == 'SchemaValidator(name="int", validator=Int(\n    IntValidator,\n))'
+    assert repr(v) == (
+        'SchemaValidator(name="int", validator=Int(\n'
+        '    IntValidator {\n        strict_protocols: true,\n    },\n))'
+    )
     v = SchemaValidator({'type': 'int', 'strict': True})
-    assert repr(v) == 'SchemaValidator(name="strict-int", validator=StrictInt(\n    StrictIntValidator,\n))'
+    assert repr(v) == (
+        'SchemaValidator(name="strict-int", validator=StrictInt(\n'
+        '    StrictIntValidator {\n        strict_protocols: true,\n    },\n))'
+    )
     v = SchemaValidator({'type': 'int', 'multiple_of': 7})
     assert repr(v).startswith('SchemaValidator(name="constrained-int", validator=ConstrainedInt(\n')
 
@@ -225,3 +256,48 @@ def test_int_nan(py_or_json):
 
     with pytest.raises(ValidationError, match='Value must be a valid integer, got NaN'):
         v.validate_test('nan')
+
+
+class IndexInt:
+    """Like numpy integers, not an `int` but implements `__index__`"""
+
+    def __init__(self, value):
+        self.value = value
+
+    def __index__(self):
+        return self.value
+
+
+@pytest.mark.parametrize(
+    'schema',
+    [
+        {'type': 'int'},
+        {'type': 'int', 'strict': True},
+        {'type': 'int', 'ge': 0},
+        {'type': 'int', 'strict': True, 'ge': 0},
+    ],
+)
+def test_int_index(schema):
+    v = SchemaValidator(schema)
+    output = v.validate_python(IndexInt(42))
+    assert output == 42
+    assert type(output) is int
+
+
+def test_int_strict_protocols():
+    v = SchemaValidator({'type': 'int', 'strict': True, 'strict_protocols': False})
+    assert v.validate_python(42) == 42
+    with pytest.raises(ValidationError, match='Value must be a valid integer'):
+        v.validate_python(IndexInt(42))
+    with pytest.raises(ValidationError, match='Value must be a valid integer'):
+        v.validate_python(True)
+
+    # lax mode always accepts `__index__`
+    v = SchemaValidator({'type': 'int', 'strict_protocols': False})
+    assert v.validate_python(IndexInt(42)) == 42
+
+    v = SchemaValidator(
+        {'type': 'model', 'fields': {'a': {'type': 'int'}}, 'config': {'strict': True, 'int_strict_protocols': False}}
+    )
+    with pytest.raises(ValidationError, match='Value must be a valid integer'):
+        v.validate_python({'a': IndexInt(42)})
@@ -0,0 +1,109 @@
+import pytest
+
+from pydantic_core import SchemaError, SchemaValidator, ValidationError
+
+
+class Foo:
+    pass
+
+
+class Bar(Foo):
+    pass
+
+
+class Spam:
+    pass
+
+
+def test_is_instance():
+    v = SchemaValidator({'type': 'is-instance', 'class_type': Foo})
+    foo = Foo()
+    assert v.validate_python(foo) is foo
+    bar = Bar()
+    assert v.validate_python(bar) is bar
+    assert v.isinstance_python(foo) is True
+    assert v.isinstance_python(Spam()) is False
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(Foo)
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'is_instance_of',
+            'loc': [],
+            'message': 'Value must be an instance of Foo',
+            'input_value': Foo,
+            'context': {'class_name': 'Foo'},
+        }
+    ]
+
+
+def test_is_instance_tuple():
+    v = SchemaValidator({'type': 'is-instance', 'class_type': (Bar, Spam)})
+    spam = Spam()
+    assert v.validate_python(spam) is spam
+    with pytest.raises(ValidationError, match=r'Value must be an instance of \(Bar, Spam\)'):
+        v.validate_python(Foo())
+
+
+def test_is_instance_json():
+    v = SchemaValidator({'type': 'is-instance', 'class_type': str})
+    assert v.validate_python('foo') == 'foo'
+    # JSON can't contain instances of python classes
+    with pytest.raises(ValidationError, match='Value must be an instance of str'):
+        v.validate_json('"foo"')
+
+
+def test_is_instance_check_error():
+    class Meta(type):
+        def __instancecheck__(cls, instance):
+            raise RuntimeError('broken')
+
+    class Broken(metaclass=Meta):
+        pass
+
+    v = SchemaValidator({'type': 'is-instance', 'class_type': Broken})
+    with pytest.raises(RuntimeError, match='broken'):
+        v.validate_python(1)
+
+
+def test_is_instance_in_model():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': {'type': 'is-instance', 'class_type': Foo}}})
+    foo = Foo()
+    output, fields_set = v.validate_python({'a': foo})
+    assert output['a'] is foo
+    with pytest.raises(ValidationError, match='a\n  Value must be an instance of Foo'):
+        v.validate_python({'a': 1})
+
+
+def test_is_subclass():
+    v = SchemaValidator({'type': 'is-subclass', 'class_type': Foo})
+    assert v.validate_python(Foo) is Foo
+    assert v.validate_python(Bar) is Bar
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(Spam)
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'is_subclass_of',
+            'loc': [],
+            'message': 'Value must be a subclass of Foo',
+            'input_value': Spam,
+            'context': {'class_name': 'Foo'},
+        }
+    ]
+    # not a class
+    with pytest.raises(ValidationError, match='Value must be a subclass of Foo'):
+        v.validate_python(Foo())
+    with pytest.raises(ValidationError, match='Value must be a subclass of Foo'):
+        v.validate_json('"Foo"')
+
+
+def test_is_subclass_tuple():
+    v = SchemaValidator({'type': 'is-subclass', 'class_type': (Bar, Spam)})
+    assert v.validate_python(Spam) is Spam
+    assert v.isinstance_python(Foo) is False
+
+
+@pytest.mark.parametrize('schema_type', ['is-instance', 'is-subclass'])
+@pytest.mark.parametrize('class_type', [1, 'Foo', (Foo, 1)])
+def test_invalid_class_type(schema_type, class_type):
+    with pytest.raises(SchemaError, match="'class_type' must be a class or a tuple of classes"):
+        SchemaValidator({'type': schema_type, 'class_type': class_type})
@@ -0,0 +1,97 @@
+import pytest
+
+from pydantic_core import SchemaValidator, ValidationError
+
+
+@pytest.mark.parametrize(
+    'input_value,expected',
+    [
+        ('{"a": 1}', {'a': 1}),
+        (b'{"a": 1}', {'a': 1}),
+        ('[1, "two", null]', [1, 'two', None]),
+        ('123', 123),
+    ],
+)
+def test_json_any(input_value, expected):
+    v = SchemaValidator({'type': 'json'})
+    assert v.validate_python(input_value) == expected
+
+
+def test_json_invalid():
+    v = SchemaValidator({'type': 'json'})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python('{"a": 1')
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'invalid_json',
+            'loc': [],
+            'message': 'EOF while parsing an object at line 1 column 7',
+            'input_value': '{"a": 1',
+        }
+    ]
+
+
+@pytest.mark.parametrize('input_value', [123, None, {'a': 1}])
+def test_json_type(input_value):
+    v = SchemaValidator({'type': 'json'})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(input_value)
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'json_type',
+            'loc': [],
+            'message': 'JSON input must be a string or bytes',
+            'input_value': input_value,
+        }
+    ]
+
+
+def test_json_inner_schema():
+    v = SchemaValidator({'type': 'json', 'schema': {'type': 'dict', 'keys': 'str', 'values': 'int'}})
+    assert v.validate_python('{"a": 1, "b": "2"}') == {'a': 1, 'b': 2}
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python('{"a": 1, "b": "x"}')
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'int_parsing',
+            'loc': ['b'],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'x',
+        }
+    ]
+
+
+def test_json_model_field():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'a': 'int',
+                'b': {'type': 'json', 'schema': {'type': 'list', 'items': 'int'}},
+            },
+        }
+    )
+    assert v.validate_python({'a': 1, 'b': '[1, 2, "3"]'}) == ({'a': 1, 'b': [1, 2, 3]}, {'a', 'b'})
+    assert v.validate_json('{"a": 1, "b": "[1, 2, 3]"}') == ({'a': 1, 'b': [1, 2, 3]}, {'a', 'b'})
+
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_json('{"a": 1, "b": "[1, \\"x\\"]"}')
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'int_parsing',
+            'loc': ['b', 1],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'x',
+        }
+    ]
+
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_json('{"a": 1, "b": [1, 2]}')
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'json_type',
+            'loc': ['b'],
+            'message': 'JSON input must be a string or bytes',
+            'input_value': [1, 2],
+        }
+    ]
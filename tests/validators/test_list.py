@@ -1,3 +1,6 @@
+import json
+from array import array
+
 import pytest
 from dirty_equals import IsList, IsNonNegative
 
@@ -56,3 +59,149 @@ def test_list_error(input_value, index):
             'input_value': 'wrong',
         }
     ]
+
+
+@pytest.mark.parametrize(
+    'items_schema,input_value,expected',
+    [
+        ({'type': 'int'}, array('i', [1, 2, -3]), [1, 2, -3]),
+        ({'type': 'int'}, array('q', [2**62]), [2**62]),
+        ({'type': 'int'}, array('B', [1, 255]), [1, 255]),
+        ({'type': 'int'}, array('d', [1.0, 2.0]), [1, 2]),
+        ({'type': 'int'}, memoryview(array('h', [1, 2])), [1, 2]),
+        ({'type': 'int', 'strict': True}, array('l', [1, 2]), [1, 2]),
+        ({'type': 'int', 'ge': 1}, array('i', [1, 2]), [1, 2]),
+        ({'type': 'float'}, array('d', [1.5, 2.5]), [1.5, 2.5]),
+        ({'type': 'float'}, array('f', [1.5]), [1.5]),
+        ({'type': 'float'}, array('i', [1, 2]), [1.0, 2.0]),
+        ({'type': 'any'}, array('i', [1, 2]), [1, 2]),
+        ({'type': 'str', 'coerce_numbers_to_str': True}, array('i', [1, 2]), ['1', '2']),
+    ],
+)
+def test_list_from_array(items_schema, input_value, expected):
+    v = SchemaValidator({'type': 'list', 'items': items_schema})
+    output = v.validate_python(input_value)
+    assert output == expected
+    assert [type(item) for item in output] == [type(item) for item in expected]
+
+
+def test_list_from_array_no_items():
+    v = SchemaValidator({'type': 'list'})
+    assert v.validate_python(array('i', [1, 2])) == [1, 2]
+    assert v.validate_python(array('d', [1.5])) == [1.5]
+
+
+def test_list_from_array_errors():
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'int', 'gt': 2}, 'max_items': 3})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(array('i', [1, 5, 2]))
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'int_greater_than',
+            'loc': [0],
+            'message': 'Value must be greater than 2',
+            'input_value': 1,
+            'context': {'gt': 2},
+        },
+        {
+            'kind': 'int_greater_than',
+            'loc': [2],
+            'message': 'Value must be greater than 2',
+            'input_value': 2,
+            'context': {'gt': 2},
+        },
+    ]
+    with pytest.raises(ValidationError, match='List must have at most 3 items'):
+        v.validate_python(array('i', [3, 4, 5, 6]))
+
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'int'}})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(array('d', [1.0, 1.5]))
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'int_from_float',
+            'loc': [1],
+            'message': 'Value must be a valid integer, got a number with a fractional part',
+            'input_value': 1.5,
+        }
+    ]
+
+
+@pytest.mark.parametrize(
+    'input_value',
+    [b'123', bytearray(b'123'), array('Q', [2**64 - 1]), array('u', 'abc'), memoryview(b'ab').cast('B', [1, 2])],
+)
+def test_list_array_not_accepted(input_value):
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'int'}})
+    with pytest.raises(ValidationError, match='Value must be a valid list/array'):
+        v.validate_python(input_value)
+
+
+def test_list_array_strict():
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'int'}, 'strict': True})
+    with pytest.raises(ValidationError, match='Value must be a valid list/array'):
+        v.validate_python(array('i', [1, 2]))
+
+
+@pytest.mark.parametrize(
+    'items_schema',
+    [
+        {'type': 'int'},
+        {'type': 'int', 'strict': True},
+        {'type': 'int', 'ge': 0},
+        {'type': 'float'},
+        {'type': 'float', 'lt': 100_000},
+        {'type': 'str'},
+    ],
+)
+def test_parallel(items_schema):
+    v = SchemaValidator({'type': 'list', 'items': items_schema, 'parallel': True})
+    v_sequential = SchemaValidator({'type': 'list', 'items': items_schema})
+    data = json.dumps(list(range(10_000)))
+    output = v.validate_json(data)
+    assert output == v_sequential.validate_json(data)
+    assert [type(item) for item in output[:2]] == [type(item) for item in v_sequential.validate_json('[0, 1]')]
+
+    output = v.validate_python(array('i', range(10_000)))
+    assert output == v_sequential.validate_python(array('i', range(10_000)))
+
+
+def test_parallel_errors():
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'int', 'lt': 9_000}, 'parallel': True})
+    items = list(range(10_000))
+    items[5] = 'wrong'
+    items[6] = '6'
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_json(json.dumps(items))
+    errors = exc_info.value.errors()
+    assert len(errors) == 1_001
+    assert errors[:2] == [
+        {
+            'kind': 'int_parsing',
+            'loc': [5],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'wrong',
+        },
+        {
+            'kind': 'int_less_than',
+            'loc': [9_000],
+            'message': 'Value must be less than 9000',
+            'input_value': 9_000,
+            'context': {'lt': 9_000},
+        },
+    ]
+
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(array('q', range(10_000)))
+    assert [e['loc'] for e in exc_info.value.errors()] == [[i] for i in range(9_000, 10_000)]
+
+
+def test_parallel_config():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {'a': {'type': 'list', 'items': {'type': 'float'}}},
+            'config': {'list_parallel': True},
+        }
+    )
+    assert v.validate_json(json.dumps({'a': [1.5] * 10_000})) == ({'a': [1.5] * 10_000}, {'a'})
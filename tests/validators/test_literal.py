@@ -81,7 +81,7 @@ def test_literal_py_or_json(py_or_json, kwarg_expected, input_value, expected):
             3,
             Err(
                 "Value must be one of: 1, b'whatever' "
-                "[kind=literal_error, context={expected: 1, b'whatever'}, input_value=3, input_type=int]"
+                "[kind=literal_error, context={expected: 1, \"b'whatever'\"}, input_value=3, input_type=int]"
             ),
             id='wrong-general',
         ),
@@ -96,6 +96,32 @@ def test_literal_not_json(kwarg_expected, input_value, expected):
         assert v.validate_python(input_value) == expected
 
 
+@pytest.mark.parametrize(
+    'kwarg_expected,input_value,message,context',
+    [
+        (['foo'], 'bar', "Value must be 'foo'", {'expected': ['foo']}),
+        ([1], 2, 'Value must be 1', {'expected': [1]}),
+        (['a', 'b'], 'c', "Value must be one of: 'a', 'b'", {'expected': ['a', 'b']}),
+        ([1, 2], 3, 'Value must be one of: 1, 2', {'expected': [1, 2]}),
+        ([1, 'a', 1.5], 2, "Value must be one of: 1, 'a', 1.5", {'expected': [1, 'a', 1.5]}),
+    ],
+)
+def test_error_context(kwarg_expected, input_value, message, context):
+    v = SchemaValidator({'type': 'literal', 'expected': kwarg_expected})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(input_value)
+    (error,) = exc_info.value.errors()
+    assert error['message'] == message
+    assert error['context'] == context
+
+
+def test_context_repr():
+    v = SchemaValidator({'type': 'literal', 'expected': ["it's", 'a\nb', 1]})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python('c')
+    assert 'context={expected: "it\'s", \'a\\nb\', 1}' in str(exc_info.value)
+
+
 def test_build_error():
     with pytest.raises(SchemaError, match='SchemaError: "expected" must have length > 0'):
         SchemaValidator({'type': 'literal', 'expected': []})
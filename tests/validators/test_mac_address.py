@@ -0,0 +1,53 @@
+import pytest
+
+from pydantic_core import SchemaValidator, ValidationError
+
+
+@pytest.mark.parametrize(
+    'input_value,expected',
+    [
+        ('00:1a:2b:3c:4d:5e', '00:1a:2b:3c:4d:5e'),
+        ('00:1A:2B:3C:4D:5E', '00:1a:2b:3c:4d:5e'),
+        ('00-1A-2B-3C-4D-5E', '00:1a:2b:3c:4d:5e'),
+        ('001a.2b3c.4d5e', '00:1a:2b:3c:4d:5e'),
+        ('00:1a:2b:ff:fe:3c:4d:5e', '00:1a:2b:ff:fe:3c:4d:5e'),
+        ('00-1A-2B-FF-FE-3C-4D-5E', '00:1a:2b:ff:fe:3c:4d:5e'),
+        ('001a.2bff.fe3c.4d5e', '00:1a:2b:ff:fe:3c:4d:5e'),
+    ],
+)
+def test_mac_address(py_or_json, input_value, expected):
+    v = py_or_json('mac-address')
+    assert v.validate_test(input_value) == expected
+
+
+@pytest.mark.parametrize(
+    'input_value',
+    [
+        '',
+        '00:1a:2b:3c:4d',
+        '00:1a:2b:3c:4d:5e:6f',
+        '00:1a:2b:3c:4d:5g',
+        '00:1a-2b:3c:4d:5e',
+        '0:1a:2b:3c:4d:5e',
+        '001a2b3c4d5e',
+        '001a.2b3c.4d5',
+        '+0:1a:2b:3c:4d:5e',
+    ],
+)
+def test_mac_address_invalid(input_value):
+    v = SchemaValidator('mac-address')
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(input_value)
+    assert exc_info.value.errors() == [
+        {'kind': 'mac_address', 'loc': [], 'message': 'Value must be a valid MAC address', 'input_value': input_value}
+    ]
+
+
+def test_mac_address_strict():
+    v = SchemaValidator({'type': 'mac-address', 'strict': True})
+    assert v.validate_python('00-1A-2B-3C-4D-5E') == '00:1a:2b:3c:4d:5e'
+    with pytest.raises(ValidationError, match='Value must be a valid string'):
+        v.validate_python(b'00:1a:2b:3c:4d:5e')
+
+    v = SchemaValidator('mac-address')
+    assert v.validate_python(b'00:1a:2b:3c:4d:5e') == '00:1a:2b:3c:4d:5e'
@@ -1,6 +1,6 @@
 import pytest
 
-from pydantic_core import SchemaValidator, ValidationError
+from pydantic_core import SchemaError, SchemaValidator, ValidationError
 
 
 def test_simple():
@@ -215,3 +215,550 @@ def test_validate_assignment_allow_extra_validate():
             'input_value': 'xyz',
         }
     ]
+
+
+def test_validate_assignment_frozen():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': {'type': 'int', 'frozen': True}, 'b': 'int'}})
+    assert v.validate_python({'a': 1, 'b': 2}) == ({'a': 1, 'b': 2}, {'a', 'b'})
+    assert v.validate_assignment('b', '3', {'a': 1, 'b': 2}) == ({'a': 1, 'b': 3}, {'b'})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_assignment('a', 3, {'a': 1, 'b': 2})
+    assert exc_info.value.errors() == [
+        {'kind': 'frozen_field', 'loc': ['a'], 'message': 'Field is frozen', 'input_value': 3}
+    ]
+
+
+def test_exclude():
+    def check_confirmation(value, data, **kwargs):
+        if value != data.get('password'):
+            raise ValueError('passwords do not match')
+        return True
+
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'username': 'str',
+                'password': {'type': 'str', 'exclude': True, 'min_length': 4},
+                'confirmed': {'type': 'function', 'mode': 'plain', 'function': check_confirmation},
+            },
+            'config': {'extra': 'forbid'},
+        }
+    )
+    input_value = {'username': 'alice', 'password': 'secret', 'confirmed': 'secret'}
+    assert v.validate_python(input_value) == ({'username': 'alice', 'confirmed': True}, {'username', 'confirmed'})
+    assert v.validate_json('{"username": "alice", "password": "secret", "confirmed": "secret"}') == (
+        {'username': 'alice', 'confirmed': True},
+        {'username', 'confirmed'},
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'username': 'alice', 'password': 'abc', 'confirmed': 'abc'})
+    assert [(e['kind'], e['loc']) for e in exc_info.value.errors()] == [
+        ('str_too_short', ['password']),
+        ('value_error', ['confirmed']),
+    ]
+    assert v.construct(input_value) == ({'username': 'alice', 'confirmed': 'secret'}, {'username', 'confirmed'})
+
+
+def test_alias():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {'field_a': {'type': 'str', 'alias': 'FieldA'}, 'field_b': 'int'},
+            'config': {'extra': 'forbid'},
+        }
+    )
+    assert v.validate_python({'FieldA': 'test', 'field_b': 1}) == (
+        {'field_a': 'test', 'field_b': 1},
+        {'field_a', 'field_b'},
+    )
+    assert v.validate_json('{"FieldA": "test", "field_b": 1}') == (
+        {'field_a': 'test', 'field_b': 1},
+        {'field_a', 'field_b'},
+    )
+
+    input_value = {'field_a': 'test', 'field_b': 1}
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(input_value)
+    assert exc_info.value.errors() == [
+        {'kind': 'missing', 'loc': ['field_a'], 'message': 'Field required', 'input_value': input_value},
+        {
+            'kind': 'extra_forbidden',
+            'loc': ['field_a'],
+            'message': 'Extra values are not permitted',
+            'input_value': input_value,
+        },
+    ]
+
+
+def test_extra_forbid_all_keys(py_or_json):
+    v = py_or_json({'type': 'model', 'fields': {'field_a': 'str'}, 'config': {'extra': 'forbid'}})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_test({'field_a': 'x', 'fieldA': 1, 'field_b': 2, 'filed_a': 3})
+    assert [(e['kind'], e['loc']) for e in exc_info.value.errors()] == [
+        ('extra_forbidden', ['fieldA']),
+        ('extra_forbidden', ['field_b']),
+        ('extra_forbidden', ['filed_a']),
+    ]
+
+
+def test_alias_by_name():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {'field_a': {'type': 'str', 'alias': 'FieldA'}, 'field_b': 'int'},
+            'config': {'extra': 'forbid'},
+        }
+    )
+    expected = ({'field_a': 'test', 'field_b': 1}, {'field_a', 'field_b'})
+    assert v.validate_python({'field_a': 'test', 'field_b': 1}, by_alias=False, by_name=True) == expected
+    assert v.validate_json('{"field_a": "test", "field_b": 1}', by_alias=False, by_name=True) == expected
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'FieldA': 'test', 'field_b': 1}, by_alias=False, by_name=True)
+    assert [(e['kind'], e['loc']) for e in exc_info.value.errors()] == [
+        ('missing', ['field_a']),
+        ('extra_forbidden', ['FieldA']),
+    ]
+
+    # with both, the alias is used if it's present
+    assert v.validate_python({'FieldA': 'test', 'field_b': 1}, by_name=True) == expected
+    assert v.validate_python({'field_a': 'test', 'field_b': 1}, by_name=True) == expected
+    assert v.validate_python({'FieldA': 'alias', 'field_a': 'name', 'field_b': 1}, by_name=True) == (
+        {'field_a': 'alias', 'field_b': 1},
+        {'field_a', 'field_b'},
+    )
+    assert v.validate_json('{"FieldA": "test", "field_b": 1}', by_name=True) == expected
+    assert v.validate_json('{"field_a": "test", "field_b": 1}', by_name=True) == expected
+    assert v.validate_json('{"field_a": "name", "FieldA": "alias", "field_b": 1}', by_name=True) == (
+        {'field_a': 'alias', 'field_b': 1},
+        {'field_a', 'field_b'},
+    )
+
+    with pytest.raises(ValueError, match='At least one of by_alias and by_name must be true'):
+        v.validate_python({'FieldA': 'test', 'field_b': 1}, by_alias=False)
+
+
+def test_populate_by_name():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {'field_a': {'type': 'str', 'alias': 'FieldA'}},
+            'config': {'extra': 'forbid', 'populate_by_name': True},
+        }
+    )
+    assert v.validate_python({'FieldA': 'alias'}) == ({'field_a': 'alias'}, {'field_a'})
+    assert v.validate_python({'field_a': 'name'}) == ({'field_a': 'name'}, {'field_a'})
+    # validating by name alone still ignores aliases
+    with pytest.raises(ValidationError, match='field_a\n  Field required'):
+        v.validate_python({'FieldA': 'alias'}, by_alias=False, by_name=True)
+
+
+
+@pytest.mark.parametrize(
+    'error_loc_by,loc_a,loc_b',
+    [
+        (None, 'field_a', 'field_b'),
+        ('name', 'field_a', 'field_b'),
+        ('alias', 'FieldA', 'field_b'),
+        ('both', 'FieldA (field_a)', 'field_b'),
+    ],
+)
+def test_error_loc_by(py_or_json, error_loc_by, loc_a, loc_b):
+    config = {} if error_loc_by is None else {'error_loc_by': error_loc_by}
+    v = py_or_json(
+        {
+            'type': 'model',
+            'fields': {'field_a': {'type': 'int', 'alias': 'FieldA'}, 'field_b': 'int'},
+            'config': config,
+        }
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_test({'FieldA': 'x'})
+    assert [(e['kind'], e['loc']) for e in exc_info.value.errors()] == [('int_parsing', [loc_a]), ('missing', [loc_b])]
+
+
+def test_error_loc_by_nested():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'sub_model': {
+                    'type': 'model',
+                    'fields': {'field_a': {'type': 'int', 'alias': 'fieldA'}},
+                    'alias': 'subModel',
+                },
+                'other': {
+                    'type': 'model',
+                    'fields': {'field_b': {'type': 'int', 'alias': 'fieldB'}},
+                    'error_loc_by': 'name',
+                },
+            },
+            'config': {'error_loc_by': 'alias'},
+        }
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'subModel': {'fieldA': 'x'}, 'other': {'fieldB': 'x'}})
+    assert [e['loc'] for e in exc_info.value.errors()] == [['subModel', 'fieldA'], ['other', 'field_b']]
+
+
+def test_error_loc_by_invalid():
+    with pytest.raises(SchemaError, match='Invalid error_loc_by: "foobar"'):
+        SchemaValidator({'type': 'model', 'fields': {'field_a': 'int'}, 'config': {'error_loc_by': 'foobar'}})
+
+def test_nested_config():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'inner': {'type': 'model', 'fields': {'a': 'str', 'b': 'int'}},
+                'other': {'type': 'model', 'fields': {'c': 'str'}, 'config': {'extra': 'allow', 'strict': False}},
+            },
+            'config': {'extra': 'forbid', 'strict': True, 'str_to_upper': True},
+        }
+    )
+    assert v.validate_python({'inner': {'a': 'x', 'b': 1}, 'other': {'c': 'y', 'd': 2}}) == (
+        {'inner': ({'a': 'X', 'b': 1}, {'a', 'b'}), 'other': ({'c': 'Y', 'd': 2}, {'c', 'd'})},
+        {'inner', 'other'},
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'inner': {'a': 'x', 'b': '1', 'z': 1}, 'other': {'c': 1}})
+    assert [(e['kind'], e['loc']) for e in exc_info.value.errors()] == [
+        ('int_type', ['inner', 'b']),
+        ('extra_forbidden', ['inner', 'z']),
+    ]
+
+
+def test_on_error_default():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {'a': 'int', 'b': {'type': 'int', 'default': 42, 'on_error': 'default'}},
+        }
+    )
+    assert v.validate_python({'a': 1, 'b': 2}) == ({'a': 1, 'b': 2}, {'a', 'b'})
+    assert v.validate_python({'a': 1, 'b': 'wrong'}) == ({'a': 1, 'b': 42}, {'a'})
+    assert v.validate_python({'a': 1}) == ({'a': 1, 'b': 42}, {'a'})
+
+    with pytest.raises(ValidationError, match='a\n  Value must be a valid integer'):
+        v.validate_python({'a': 'wrong', 'b': 'wrong'})
+
+
+def test_on_error_omit():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {'a': 'int', 'b': {'type': 'list', 'items': 'int', 'on_error': 'omit'}},
+        }
+    )
+    assert v.validate_python({'a': 1, 'b': [1, '2']}) == ({'a': 1, 'b': [1, 2]}, {'a', 'b'})
+    assert v.validate_python({'a': 1, 'b': [1, 'x']}) == ({'a': 1}, {'a'})
+    # missing fields are still an error
+    with pytest.raises(ValidationError, match='b\n  Field required'):
+        v.validate_python({'a': 1})
+
+
+def test_on_error_raise():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': {'type': 'int', 'on_error': 'raise'}}})
+    with pytest.raises(ValidationError, match='a\n  Value must be a valid integer'):
+        v.validate_python({'a': 'x'})
+
+
+def test_on_error_default_missing():
+    with pytest.raises(SchemaError, match="Key \"a\":\n  'on_error = default' requires a `default` value"):
+        SchemaValidator({'type': 'model', 'fields': {'a': {'type': 'int', 'on_error': 'default'}}})
+
+
+def test_on_error_invalid():
+    with pytest.raises(SchemaError, match='Invalid on_error: "foobar"'):
+        SchemaValidator({'type': 'model', 'fields': {'a': {'type': 'int', 'on_error': 'foobar'}}})
+
+
+def test_title():
+    v = SchemaValidator({'type': 'model', 'name': 'MyModel', 'title': 'My Model', 'fields': {'a': 'int'}})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 'x'})
+    assert exc_info.value.title == 'My Model'
+    assert str(exc_info.value).startswith('1 validation error for My Model\n')
+    # the top level model's title isn't repeated in each error
+    assert 'title' not in exc_info.value.errors()[0]
+
+
+def test_nested_title():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'name': 'Outer',
+            'fields': {
+                'a': 'int',
+                'sub': {'type': 'model', 'name': 'Inner', 'title': 'Inner Model', 'fields': {'b': 'int'}},
+            },
+        }
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 'x', 'sub': {'b': 'y'}})
+    assert exc_info.value.title == 'Outer'
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'int_parsing',
+            'loc': ['a'],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'x',
+        },
+        {
+            'kind': 'int_parsing',
+            'loc': ['sub', 'b'],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'y',
+            'title': 'Inner Model',
+        },
+    ]
+    assert 'title=Inner Model' in str(exc_info.value)
+
+
+def test_extra_non_str_keys():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': 'int'}, 'config': {'extra': 'allow'}})
+    value, fields_set = v.validate_python({'a': 1, (1, 2): 3, 4: 5})
+    assert value == {'a': 1, (1, 2): 3, 4: 5}
+    assert fields_set == {'a', (1, 2), 4}
+
+
+def test_extra_non_str_keys_forbid():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': 'int'}, 'config': {'extra': 'forbid'}})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 1, (1, 2): 3})
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'extra_forbidden',
+            'loc': [(1, 2)],
+            'message': 'Extra values are not permitted',
+            'input_value': {'a': 1, (1, 2): 3},
+        }
+    ]
+
+
+def test_extra_non_str_keys_ignore():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': 'int'}})
+    assert v.validate_python({'a': 1, (1, 2): 3, 4: 5}) == ({'a': 1}, {'a'})
+
+
+@pytest.mark.parametrize(
+    'input_value,expected',
+    [
+        ({0: 'a', 5: 'b'}, ({'first': 'a', 'sixth': 'b'}, {'first', 'sixth'})),
+        ({'0': 'a', '5': 'b'}, ({'first': 'a', 'sixth': 'b'}, {'first', 'sixth'})),
+        ({0: 'a', '0': 'x', '5': 'b'}, ({'first': 'a', 'sixth': 'b'}, {'first', 'sixth'})),
+        ({5: 'b'}, ({'sixth': 'b'}, {'sixth'})),
+    ],
+)
+def test_int_alias(input_value, expected):
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'first': {'type': 'str', 'alias': 0, 'default': None},
+                'sixth': {'type': 'str', 'alias': 5},
+            },
+        }
+    )
+    value, fields_set = v.validate_python(input_value)
+    assert {k: v for k, v in value.items() if v is not None} == expected[0]
+    assert fields_set == expected[1]
+
+
+def test_int_alias_json():
+    v = SchemaValidator({'type': 'model', 'fields': {'first': {'type': 'int', 'alias': 0}}})
+    assert v.validate_json('{"0": 1}') == ({'first': 1}, {'first'})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_json('{"1": 1}')
+    assert exc_info.value.errors() == [
+        {'kind': 'missing', 'loc': ['first'], 'message': 'Field required', 'input_value': {'1': 1}}
+    ]
+
+
+def test_int_alias_extra():
+    v = SchemaValidator(
+        {'type': 'model', 'fields': {'first': {'type': 'int', 'alias': 0}}, 'config': {'extra': 'allow'}}
+    )
+    assert v.validate_python({0: 1, 1: 2}) == ({'first': 1, 1: 2}, {'first', 1})
+    assert v.validate_python({'0': 1, '1': 2}) == ({'first': 1, '1': 2}, {'first', '1'})
+
+
+def test_int_alias_construct():
+    v = SchemaValidator(
+        {'type': 'model', 'fields': {'first': {'type': 'int', 'alias': 0}}, 'config': {'extra': 'allow'}}
+    )
+    assert v.construct({0: 1, 1: 2}) == ({'first': 1, 1: 2}, {'first', 1})
+
+
+def test_field_order():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {'b': 'int', 'a': 'int', 'c': {'type': 'int', 'default': 3}},
+            'config': {'extra': 'allow'},
+        }
+    )
+    value, _ = v.validate_python({'z': 0, 'a': 1, 'y': 0, 'b': 2})
+    # fields are always in the order they're defined, followed by extra keys in the order they're given
+    assert list(value.items()) == [('b', 2), ('a', 1), ('c', 3), ('z', 0), ('y', 0)]
+    value, _ = v.validate_json('{"y": 0, "b": 2, "z": 0, "a": 1}')
+    assert list(value.items()) == [('b', 2), ('a', 1), ('c', 3), ('y', 0), ('z', 0)]
+
+
+@pytest.mark.parametrize('extra_validator', [None, 'int'])
+def test_sort_extra(extra_validator):
+    schema = {'type': 'model', 'fields': {'b': 'int', 'a': 'int'}, 'config': {'extra': 'allow', 'sort_extra': True}}
+    if extra_validator:
+        schema['extra_validator'] = extra_validator
+    v = SchemaValidator(schema)
+    value, fields_set = v.validate_python({'z': 0, 'a': 1, 'é': 0, 'y': 0, 'b': 2})
+    assert list(value.items()) == [('b', 2), ('a', 1), ('y', 0), ('z', 0), ('é', 0)]
+    assert fields_set == {'a', 'b', 'y', 'z', 'é'}
+
+
+def test_default_factory():
+    calls = []
+
+    def factory():
+        calls.append(1)
+        return []
+
+    v = SchemaValidator({'type': 'model', 'fields': {'a': 'int', 'b': {'type': 'list', 'default_factory': factory}}})
+    value1, fields_set = v.validate_python({'a': 1})
+    value2, _ = v.validate_python({'a': 2})
+    assert value1 == {'a': 1, 'b': []}
+    assert fields_set == {'a'}
+    # a new value is created for each missing field
+    assert value1['b'] is not value2['b']
+    assert v.validate_python({'a': 1, 'b': [1]}) == ({'a': 1, 'b': [1]}, {'a', 'b'})
+    assert len(calls) == 2
+
+
+def test_default_factory_takes_data():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'title': 'str',
+                'slug': {
+                    'type': 'str',
+                    'default_factory': lambda data: data['title'].lower().replace(' ', '-'),
+                    'default_factory_takes_data': True,
+                },
+            },
+        }
+    )
+    assert v.validate_python({'title': 'Hello World'}) == ({'title': 'Hello World', 'slug': 'hello-world'}, {'title'})
+    assert v.validate_python({'title': 'Hello', 'slug': 'x'}) == ({'title': 'Hello', 'slug': 'x'}, {'title', 'slug'})
+    assert v.construct({'title': 'Hello World'}) == ({'title': 'Hello World', 'slug': 'hello-world'}, {'title'})
+
+
+def test_default_factory_error():
+    def factory():
+        raise RuntimeError('broken')
+
+    v = SchemaValidator({'type': 'model', 'fields': {'a': {'type': 'int', 'default_factory': factory}}})
+    with pytest.raises(RuntimeError, match='broken'):
+        v.validate_python({})
+
+
+@pytest.mark.parametrize(
+    'field,message',
+    [
+        ({'type': 'int', 'default': 1, 'default_factory': int}, "'default' and 'default_factory' cannot both be set"),
+        ({'type': 'int', 'default_factory': 1}, "'default_factory' must be callable"),
+    ],
+)
+def test_default_factory_invalid(field, message):
+    with pytest.raises(SchemaError, match=f'Key "a":\n  {message}'):
+        SchemaValidator({'type': 'model', 'fields': {'a': field}})
+
+
+def test_on_error_default_factory():
+    v = SchemaValidator(
+        {'type': 'model', 'fields': {'a': {'type': 'int', 'default_factory': lambda: 42, 'on_error': 'default'}}}
+    )
+    assert v.validate_python({'a': 'wrong'}) == ({'a': 42}, set())
+
+
+def test_output_dict():
+    v = SchemaValidator({'type': 'model', 'output': 'dict', 'fields': {'a': {'type': 'int'}, 'b': {'type': 'str'}}})
+    assert v.validate_python({'a': '1', 'b': 'x'}) == {'a': 1, 'b': 'x'}
+    assert v.construct({'a': 1}) == {'a': 1}
+
+
+def test_output_class():
+    class MyModel:
+        __slots__ = '__dict__', '__fields_set__'
+
+        def __init__(self, **kwargs):
+            raise RuntimeError('__init__ should not be called')
+
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'output_class': MyModel,
+            'fields': {'a': {'type': 'int'}, 'b': {'type': 'int', 'default': 2}},
+        }
+    )
+    m = v.validate_python({'a': '1'})
+    assert isinstance(m, MyModel)
+    assert m.__dict__ == {'a': 1, 'b': 2}
+    assert m.__fields_set__ == {'a'}
+
+
+def test_output_factory():
+    calls = []
+
+    def factory(**kwargs):
+        calls.append(kwargs)
+        return tuple(kwargs.items())
+
+    v = SchemaValidator(
+        {'type': 'model', 'output_factory': factory, 'fields': {'a': {'type': 'int'}, 'b': {'type': 'str'}}}
+    )
+    assert v.validate_python({'a': '1', 'b': 'x'}) == (('a', 1), ('b', 'x'))
+    assert calls == [{'a': 1, 'b': 'x'}]
+
+    with pytest.raises(ValidationError, match='Field required'):
+        v.validate_python({'a': 1})
+    assert len(calls) == 1
+
+
+def test_output_nested():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'sub': {'type': 'model', 'output': 'dict', 'fields': {'x': {'type': 'int'}}},
+            },
+        }
+    )
+    assert v.validate_python({'sub': {'x': '1'}}) == ({'sub': {'x': 1}}, {'sub'})
+
+
+@pytest.mark.parametrize(
+    'schema,message',
+    [
+        ({'output': 'list'}, "Invalid model output: 'list'"),
+        ({'output': 'dict', 'output_factory': dict}, "Only one of 'output', 'output_class' and 'output_factory'"),
+        ({'output_factory': 1}, "'output_factory' must be callable"),
+    ],
+)
+def test_output_invalid(schema, message):
+    with pytest.raises(SchemaError, match=message):
+        SchemaValidator({'type': 'model', 'fields': {'a': {'type': 'int'}}, **schema})
+
+
+def test_output_model_class():
+    class MyModel:
+        pass
+
+    with pytest.raises(SchemaError, match="model-class requires the 'model' schema's default 'tuple' output"):
+        SchemaValidator(
+            {
+                'type': 'model-class',
+                'class_type': MyModel,
+                'model': {'type': 'model', 'output': 'dict', 'fields': {'a': {'type': 'int'}}},
+            }
+        )
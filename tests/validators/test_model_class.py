@@ -148,12 +148,54 @@ def test_model_class_instance_subclass():
 
     m2 = MySubModel()
     assert m2.field_a
+    # instances of subclasses are accepted as is
+    assert v.validate_python(m2) is m2
+
+    v = SchemaValidator(
+        {
+            'type': 'model-class',
+            'class_type': MyModel,
+            'exact_type': True,
+            'model': {'type': 'model', 'fields': {'field_a': {'type': 'str'}}},
+        }
+    )
     m3 = v.validate_python(m2)
     assert m2 != m3
     assert m3.field_a == 'init_a'
     assert not hasattr(m3, 'field_b')
 
 
+def test_model_class_strict_subclass():
+    class MyModel:
+        pass
+
+    class MySubModel(MyModel):
+        pass
+
+    v = SchemaValidator(
+        {
+            'type': 'model-class',
+            'strict': True,
+            'class_type': MyModel,
+            'model': {'type': 'model', 'fields': {'field_a': {'type': 'str'}}},
+        }
+    )
+    m = MySubModel()
+    assert v.validate_python(m) is m
+
+    v = SchemaValidator(
+        {
+            'type': 'model-class',
+            'strict': True,
+            'exact_type': True,
+            'class_type': MyModel,
+            'model': {'type': 'model', 'fields': {'field_a': {'type': 'str'}}},
+        }
+    )
+    with pytest.raises(ValidationError, match='Value must be an instance of MyModel'):
+        v.validate_python(m)
+
+
 def test_model_class_strict():
     class MyModel:
         def __init__(self):
@@ -186,3 +228,47 @@ def test_model_class_strict():
             'context': {'class_name': 'MyModel'},
         }
     ]
+
+
+def test_model_class_strict_json():
+    class MyModel:
+        pass
+
+    v = SchemaValidator(
+        {
+            'type': 'model-class',
+            'strict': True,
+            'class_type': MyModel,
+            'model': {'type': 'model', 'fields': {'field_a': {'type': 'str'}, 'field_b': {'type': 'int'}}},
+        }
+    )
+    # JSON can't contain instances, so in strict mode JSON objects are still validated
+    m = v.validate_json('{"field_a": "test", "field_b": 12}')
+    assert isinstance(m, MyModel)
+    assert m.field_a == 'test'
+    assert m.field_b == 12
+    with pytest.raises(ValidationError, match='field_b\n  Value must be a valid integer'):
+        v.validate_json('{"field_a": "test", "field_b": "x"}')
+    with pytest.raises(ValidationError, match='Value must be an instance of MyModel'):
+        v.validate_python({'field_a': 'test', 'field_b': 12})
+
+
+def test_model_class_strict_coercions():
+    class MyModel:
+        pass
+
+    v = SchemaValidator(
+        {
+            'type': 'model-class',
+            'strict': True,
+            'strict_coercions': {'python': ['object_to_model'], 'json': []},
+            'class_type': MyModel,
+            'model': {'type': 'model', 'fields': {'field_a': {'type': 'str'}}},
+        }
+    )
+    m = v.validate_python({'field_a': 'test'})
+    assert isinstance(m, MyModel)
+    assert m.field_a == 'test'
+    with pytest.raises(ValidationError, match='Value must be an instance of MyModel'):
+        v.validate_json('{"field_a": "test"}')
+
@@ -78,6 +78,7 @@ def test_optional_error():
             'loc': ['sub_branch', 'recursive-ref', 'width'],
             'message': 'Value must be a valid integer, unable to parse string as an integer',
             'input_value': 'wrong',
+            'title': 'Model',
         },
     ]
 
@@ -232,3 +233,78 @@ def test_invalid_schema():
                 },
             }
         )
+
+
+@pytest.fixture(scope='module')
+def branch_validator():
+    return SchemaValidator(
+        {
+            'type': 'recursive-container',
+            'name': 'Branch',
+            'schema': {
+                'type': 'model',
+                'fields': {
+                    'name': 'str',
+                    'sub_branch': {
+                        'type': 'union',
+                        'default': None,
+                        'choices': [{'type': 'none'}, {'type': 'recursive-ref', 'name': 'Branch'}],
+                    },
+                },
+            },
+        }
+    )
+
+
+def test_cyclic_input(branch_validator):
+    data = {'name': 'root'}
+    data['sub_branch'] = data
+    with pytest.raises(ValidationError, match=r'Recursion error - cyclic reference detected \[kind=recursion_loop'):
+        branch_validator.validate_python(data)
+    assert branch_validator.isinstance_python(data) is False
+    # the recursion stack is reset, so the validator can still be used
+    assert branch_validator.validate_python({'name': 'root'}) == ({'name': 'root', 'sub_branch': None}, {'name'})
+
+
+def test_cyclic_input_wrap_validator():
+    v = SchemaValidator(
+        {
+            'type': 'recursive-container',
+            'name': 'Branch',
+            'schema': {
+                'type': 'model',
+                'fields': {
+                    'name': 'str',
+                    'sub_branch': {
+                        'type': 'function',
+                        'mode': 'wrap',
+                        'function': lambda input_value, validator, **kwargs: validator(input_value),
+                        'schema': {'type': 'optional', 'schema': {'type': 'recursive-ref', 'name': 'Branch'}},
+                        'default': None,
+                    },
+                },
+            },
+        }
+    )
+    data = {'name': 'root'}
+    data['sub_branch'] = data
+    with pytest.raises(ValidationError, match=r'Recursion error - cyclic reference detected \[kind=recursion_loop'):
+        v.validate_python(data)
+
+
+def test_recursion_depth():
+    v = SchemaValidator(
+        {
+            'type': 'recursive-container',
+            'name': 'Branch',
+            'schema': {
+                'type': 'model',
+                'fields': {'branches': {'type': 'list', 'items': {'type': 'recursive-ref', 'name': 'Branch'}}},
+            },
+        }
+    )
+    data = {'branches': []}
+    for _ in range(300):
+        data = {'branches': [data]}
+    with pytest.raises(ValidationError, match=r'Input must not be nested more than 255 levels deep \[kind=limit_depth'):
+        v.validate_python(data)
@@ -2,7 +2,7 @@ import re
 
 import pytest
 
-from pydantic_core import SchemaValidator, ValidationError
+from pydantic_core import SchemaError, SchemaValidator, ValidationError
 
 from ..conftest import Err
 
@@ -96,6 +96,119 @@ def test_set_kwargs(kwargs, input_value, expected):
         assert v.validate_python(input_value) == expected
 
 
+
+def test_set_unique():
+    v = SchemaValidator({'type': 'set', 'items': 'int', 'unique': True})
+    assert v.validate_python([1, '2', 3.0]) == {1, 2, 3}
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python([1, '2', '1', 2.0, 'x'])
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'set_duplicate',
+            'loc': [2],
+            'message': 'Set items must be unique, this item is a duplicate of item 0',
+            'input_value': '1',
+            'context': {'first_index': 0},
+        },
+        {
+            'kind': 'set_duplicate',
+            'loc': [3],
+            'message': 'Set items must be unique, this item is a duplicate of item 1',
+            'input_value': 2.0,
+            'context': {'first_index': 1},
+        },
+        {
+            'kind': 'int_parsing',
+            'loc': [4],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'x',
+        },
+    ]
+    # without unique, duplicates collapse
+    assert SchemaValidator({'type': 'set', 'items': 'int'}).validate_python([1, '1']) == {1}
+
+
+def test_set_unique_no_validator():
+    v = SchemaValidator({'type': 'set', 'unique': True})
+    with pytest.raises(ValidationError, match=r'1\n  Set items must be unique, this item is a duplicate of item 0'):
+        v.validate_python(['a', 'a'])
+
+
+def test_set_item_key_serialized():
+    v = SchemaValidator({'type': 'set', 'items': {'type': 'dict', 'values': 'int'}, 'item_key': 'serialized'})
+    # dicts can't be hashed, items are returned as a list in the order they're first found
+    assert v.validate_python([{'a': 1, 'b': 2}, {'b': '2', 'a': 1}, {'a': 2}]) == [{'a': 1, 'b': 2}, {'a': 2}]
+    assert v.validate_json('[{"a": 1}, {"a": 1}, {}]') == [{'a': 1}, {}]
+
+
+def test_set_item_key_serialized_objects():
+    class Foobar:
+        def __init__(self, a, b):
+            self.a = a
+            self.b = b
+            self._private = object()
+
+    v = SchemaValidator({'type': 'set', 'item_key': 'serialized'})
+    f1, f2, f3 = Foobar(1, {2, 3}), Foobar(1, {3, 2}), Foobar(1, [2])
+    assert v.validate_python([f1, f2, f3, None, (True, 1.5, 'x'), [True, 1.5, 'x']]) == [f1, f3, None, (True, 1.5, 'x')]
+
+
+def test_set_item_key_serialized_recursive():
+    recursive = {}
+    recursive['x'] = recursive
+    v = SchemaValidator({'type': 'set', 'item_key': 'serialized'})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python([{}, recursive])
+    assert [(e['kind'], e['loc'], e['message']) for e in exc_info.value.errors()] == [
+        ('value_error', [1], 'item is nested too deeply to be serialized')
+    ]
+
+
+def test_set_item_key_function():
+    v = SchemaValidator({'type': 'set', 'items': {'type': 'dict'}, 'item_key': lambda d: d['id']})
+    assert v.validate_python([{'id': 1}, {'id': 2, 'x': 1}, {'id': 1, 'x': 2}]) == [{'id': 1}, {'id': 2, 'x': 1}]
+
+
+def test_set_item_key_unique():
+    v = SchemaValidator({'type': 'set', 'items': {'type': 'dict'}, 'item_key': lambda d: d['id'], 'unique': True})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python([{'id': 1}, {'id': 2}, {'id': 1, 'x': 2}])
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'set_duplicate',
+            'loc': [2],
+            'message': 'Set items must be unique, this item is a duplicate of item 0',
+            'input_value': {'id': 1, 'x': 2},
+            'context': {'first_index': 0},
+        }
+    ]
+
+
+def test_set_item_key_function_error():
+    def item_key(item):
+        if item < 0:
+            raise ValueError('must not be negative')
+        return item % 10
+
+    v = SchemaValidator({'type': 'set', 'items': 'int', 'item_key': item_key})
+    assert v.validate_python([1, 11, 2]) == [1, 2]
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python([1, '-1', 'x'])
+    assert [(e['kind'], e['loc'], e['message']) for e in exc_info.value.errors()] == [
+        ('value_error', [1], 'must not be negative'),
+        ('int_parsing', [2], 'Value must be a valid integer, unable to parse string as an integer'),
+    ]
+
+
+@pytest.mark.parametrize(
+    'item_key,message',
+    [('foobar', 'Invalid item_key: "foobar"'), (123, "'item_key' must be 'serialized' or a callable")],
+)
+def test_set_item_key_invalid(item_key, message):
+    with pytest.raises(SchemaError, match=message):
+        SchemaValidator({'type': 'set', 'item_key': item_key})
+
+
 @pytest.mark.parametrize('input_value,expected', [({1, 2, 3}, {1, 2, 3}), ([1, 2, 3], [1, 2, 3])])
 def test_union_set_list(input_value, expected):
     v = SchemaValidator({'type': 'union', 'choices': [{'type': 'set'}, {'type': 'list'}]})
@@ -151,3 +264,21 @@ def test_union_set_int_set_str(input_value, expected):
             assert exc_info.value.errors() == expected.errors
     else:
         assert v.validate_python(input_value) == expected
+
+
+def test_set_strict_coercions():
+    v = SchemaValidator({'type': 'set', 'items': 'int', 'strict': True})
+    assert v.validate_json('[1, 2, 2]') == {1, 2}
+    with pytest.raises(ValidationError, match='kind=set_type'):
+        v.validate_python([1, 2])
+
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {'a': {'type': 'set', 'items': 'int', 'strict': True}},
+            'config': {'strict_coercions': {'python': ['array_to_set'], 'json': []}},
+        }
+    )
+    assert v.validate_python({'a': [1, 2, 2]}) == ({'a': {1, 2}}, {'a'})
+    with pytest.raises(ValidationError, match='kind=set_type'):
+        v.validate_json('{"a": [1, 2]}')
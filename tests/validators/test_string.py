@@ -1,5 +1,8 @@
 import re
+import sys
+from array import array
 from decimal import Decimal
+from enum import Enum
 
 import pytest
 
@@ -36,7 +39,20 @@ def test_str(py_or_json, input_value, expected):
         assert v.validate_test(input_value) == expected
 
 
-@pytest.mark.parametrize('input_value,expected', [(123, '123'), (Decimal('123'), '123')])
+@pytest.mark.parametrize(
+    'input_value,expected',
+    [
+        (123, '123'),
+        (Decimal('123'), '123'),
+        (b'foobar', 'foobar'),
+        (bytearray(b'foobar'), 'foobar'),
+        (memoryview(b'foobar'), 'foobar'),
+        (memoryview(b'xfoobarx')[1:-1], 'foobar'),
+        (memoryview(array('B', b'foobar')), 'foobar'),
+        (memoryview(array('i', [1, 2])), Err('Value must be a valid string [kind=str_type')),
+        (bytearray(b'\x81'), Err('Value must be a valid string, unable to parse raw data as a unicode string')),
+    ],
+)
 def test_str_not_json(input_value, expected):
     v = SchemaValidator({'type': 'str'})
     if isinstance(expected, Err):
@@ -83,7 +99,8 @@ def test_invalid_regex():
     with pytest.raises(SchemaError) as exc_info:
         SchemaValidator({'type': 'str', 'pattern': 123})
     assert exc_info.value.args[0] == (
-        'Error building "str" validator:\n  TypeError: \'int\' object cannot be converted to \'PyString\''
+        'Error building "str" validator:\n'
+        '  SchemaError: Invalid "pattern": TypeError: \'int\' object cannot be converted to \'PyString\''
     )
     with pytest.raises(SchemaError) as exc_info:
         SchemaValidator({'type': 'str', 'pattern': '(abc'})
@@ -94,3 +111,143 @@ def test_invalid_regex():
         '    ^\n'
         'error: unclosed group'
     )
+
+
+@pytest.mark.parametrize(
+    'schema',
+    [
+        'str',
+        {'type': 'str', 'strict': True},
+        {'type': 'str', 'min_length': 1},
+        {'type': 'str', 'strip_whitespace': True, 'to_lower': True},
+    ],
+)
+def test_str_returns_input(schema):
+    v = SchemaValidator(schema)
+    input_value = 'a string long enough not to be interned'
+    assert v.validate_python(input_value) is input_value
+
+
+def test_str_transformed_not_input():
+    v = SchemaValidator({'type': 'str', 'strip_whitespace': True})
+    input_value = ' foobar '
+    output = v.validate_python(input_value)
+    assert output == 'foobar'
+    assert output is not input_value
+
+
+@pytest.mark.parametrize(
+    'kwargs,input_value,expected',
+    [
+        ({'max_length': 3}, 'ñññ', 'ñññ'),
+        ({'max_length': 3}, 'ññññ', Err('String must have at most 3 characters')),
+        ({'min_length': 4}, 'ñññ', Err('String must have at least 4 characters')),
+        ({'min_length': 3}, '\U0001f4a9\U0001f4a9\U0001f4a9', '\U0001f4a9\U0001f4a9\U0001f4a9'),
+        ({'normalize': 'NFC'}, 'n\u0303', 'ñ'),
+        ({'normalize': 'NFD'}, 'ñ', 'n\u0303'),
+        ({'normalize': 'NFKC'}, '\ufb01\xb2', 'fi2'),
+        ({'normalize': 'NFKD'}, '\ufb01', 'fi'),
+        # lengths are checked after normalization
+        ({'normalize': 'NFC', 'max_length': 1}, 'n\u0303', 'ñ'),
+        ({'normalize': 'NFC', 'pattern': '^ñ$'}, 'n\u0303', 'ñ'),
+        ({'coerce_numbers_to_str': True}, 123, '123'),
+        ({'coerce_numbers_to_str': True, 'strict': True}, 123, '123'),
+        ({'coerce_numbers_to_str': True, 'strict': True}, 1.5, '1.5'),
+        ({'coerce_numbers_to_str': True, 'strict': True}, True, Err('Value must be a valid string [kind=str_type')),
+        ({'coerce_numbers_to_str': False}, 123, Err('Value must be a valid string [kind=str_type')),
+        ({'coerce_numbers_to_str': False}, '123', '123'),
+    ],
+)
+def test_str_unicode(py_or_json, kwargs, input_value, expected):
+    v = py_or_json({'type': 'str', **kwargs})
+    if isinstance(expected, Err):
+        with pytest.raises(ValidationError, match=re.escape(expected.message)):
+            v.validate_test(input_value)
+    else:
+        assert v.validate_test(input_value) == expected
+
+
+def test_str_normalize_config():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {'a': {'type': 'str', 'strict': True}},
+            'config': {'str_normalize': 'NFC', 'str_coerce_numbers_to_str': True},
+        }
+    )
+    assert v.validate_python({'a': 'n\u0303'}) == ({'a': 'ñ'}, {'a'})
+    assert v.validate_python({'a': 1}) == ({'a': '1'}, {'a'})
+
+
+def test_invalid_normalize():
+    with pytest.raises(SchemaError, match='Invalid normalize: "NFX"'):
+        SchemaValidator({'type': 'str', 'normalize': 'NFX'})
+
+
+class StrColor(str, Enum):
+    red = 'RED'
+
+
+@pytest.mark.parametrize(
+    'kwargs,input_value,expected',
+    [
+        ({}, StrColor.red, StrColor.red),
+        ({'subclass': 'allow', 'strict': True}, StrColor.red, StrColor.red),
+        ({'subclass': 'convert'}, StrColor.red, 'RED'),
+        ({'subclass': 'convert', 'strict': True}, StrColor.red, 'RED'),
+        ({'subclass': 'forbid'}, StrColor.red, 'RED'),
+        (
+            {'subclass': 'forbid', 'strict': True},
+            StrColor.red,
+            Err('Value must be a plain string, not a subclass of str [kind=str_subclass'),
+        ),
+        ({'subclass': 'forbid', 'strict': True}, 'RED', 'RED'),
+    ],
+)
+def test_str_subclass(kwargs, input_value, expected):
+    v = SchemaValidator({'type': 'str', **kwargs})
+    if isinstance(expected, Err):
+        with pytest.raises(ValidationError, match=re.escape(expected.message)):
+            v.validate_python(input_value)
+    else:
+        output = v.validate_python(input_value)
+        assert output == expected
+        assert type(output) is type(expected)
+
+
+def test_str_subclass_config():
+    v = SchemaValidator({'type': 'model', 'fields': {'a': {'type': 'str'}}, 'config': {'str_subclass': 'convert'}})
+    output, _ = v.validate_python({'a': StrColor.red})
+    assert type(output['a']) is str
+
+
+def test_invalid_subclass():
+    with pytest.raises(SchemaError, match='Invalid subclass: "other"'):
+        SchemaValidator({'type': 'str', 'subclass': 'other'})
+
+
+def test_intern():
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'str', 'intern': True}})
+    output = v.validate_json('["category-a", "category-a", "category-b"]')
+    assert output == ['category-a', 'category-a', 'category-b']
+    assert output[0] is output[1]
+    assert output[0] is sys.intern('category-a')
+
+
+def test_intern_transformed():
+    v = SchemaValidator({'type': 'str', 'to_lower': True, 'intern': True})
+    assert v.validate_python('Category-A') is v.validate_python('CATEGORY-a')
+
+
+def test_no_intern():
+    v = SchemaValidator({'type': 'list', 'items': {'type': 'str', 'max_length': 20}})
+    output = v.validate_json('["category-a", "category-a"]')
+    assert output[0] is not output[1]
+
+
+def test_intern_config():
+    v = SchemaValidator(
+        {'type': 'model', 'fields': {'a': {'type': 'str'}, 'b': {'type': 'str'}}, 'config': {'str_intern': True}}
+    )
+    output, _ = v.validate_json('{"a": "category-a", "b": "category-a"}')
+    assert output['a'] is output['b']
@@ -0,0 +1,57 @@
+import sys
+
+import pytest
+
+from pydantic_core import SchemaValidator, ValidationError
+
+pytestmark = pytest.mark.skipif(sys.version_info < (3, 9), reason='zoneinfo requires python 3.9+')
+
+
+@pytest.mark.parametrize(
+    'input_value,expected',
+    [
+        ('Europe/London', 'Europe/London'),
+        ('UTC', 'UTC'),
+        ('america/new_york', 'America/New_York'),
+    ],
+)
+def test_timezone_name(py_or_json, input_value, expected):
+    v = py_or_json('timezone-name')
+    assert v.validate_test(input_value) == expected
+
+
+def test_timezone_name_bytes():
+    v = SchemaValidator('timezone-name')
+    assert v.validate_python(b'Asia/Tokyo') == 'Asia/Tokyo'
+
+
+@pytest.mark.parametrize('input_value', ['Europe/Londres', 'foobar', ''])
+def test_timezone_name_invalid(input_value):
+    v = SchemaValidator('timezone-name')
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(input_value)
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'timezone_name',
+            'loc': [],
+            'message': 'Value must be a valid IANA timezone name',
+            'input_value': input_value,
+        }
+    ]
+
+
+def test_timezone_name_strict():
+    v = SchemaValidator({'type': 'timezone-name', 'strict': True})
+    assert v.validate_python('Europe/London') == 'Europe/London'
+    with pytest.raises(ValidationError, match='Value must be a valid IANA timezone name'):
+        v.validate_python('europe/london')
+    with pytest.raises(ValidationError, match='Value must be a valid string'):
+        v.validate_python(b'Europe/London')
+
+
+def test_return_zoneinfo():
+    from zoneinfo import ZoneInfo
+
+    v = SchemaValidator({'type': 'timezone-name', 'return_zoneinfo': True})
+    assert v.validate_python('europe/paris') == ZoneInfo('Europe/Paris')
+    assert v.validate_json('"Europe/Paris"') is ZoneInfo('Europe/Paris')
@@ -0,0 +1,151 @@
+import re
+from array import array
+
+import pytest
+
+from pydantic_core import SchemaError, SchemaValidator, ValidationError
+
+from ..conftest import Err
+
+
+@pytest.mark.parametrize(
+    'schema,input_value,expected',
+    [
+        ({'type': 'tuple'}, [1, 'a'], (1, 'a')),
+        ({'type': 'tuple', 'items': 'int'}, [1, '2', 3], (1, 2, 3)),
+        ({'type': 'tuple', 'items': 'int'}, [], ()),
+        ({'type': 'tuple', 'prefix_items': ['int', 'str']}, [1, 'a'], (1, 'a')),
+        ({'type': 'tuple', 'prefix_items': ['int', 'str'], 'items': 'float'}, [1, 'a'], (1, 'a')),
+        ({'type': 'tuple', 'prefix_items': ['int', 'str'], 'items': 'float'}, [1, 'a', 2, '3.5'], (1, 'a', 2.0, 3.5)),
+        (
+            {'type': 'tuple', 'prefix_items': ['int', 'str']},
+            [1, 'a', 2],
+            Err('Tuple must have at most 2 items [kind=tuple_too_long, context={max_length: 2}'),
+        ),
+        (
+            {'type': 'tuple', 'prefix_items': ['int', 'str'], 'items': 'float'},
+            [1],
+            Err('Tuple must have at least 2 items [kind=tuple_too_short, context={min_length: 2}'),
+        ),
+        (
+            {'type': 'tuple', 'items': 'int', 'max_items': 2},
+            [1, 2, 3],
+            Err('Tuple must have at most 2 items [kind=tuple_too_long'),
+        ),
+        (
+            {'type': 'tuple', 'prefix_items': ['int'], 'items': 'int', 'min_items': 3},
+            [1, 2],
+            Err('Tuple must have at least 3 items [kind=tuple_too_short'),
+        ),
+        ({'type': 'tuple'}, 'abc', Err('Value must be a valid tuple [kind=tuple_type')),
+    ],
+)
+def test_tuple(py_or_json, schema, input_value, expected):
+    v = py_or_json(schema)
+    if isinstance(expected, Err):
+        with pytest.raises(ValidationError, match=re.escape(expected.message)):
+            v.validate_test(input_value)
+    else:
+        assert v.validate_test(input_value) == expected
+
+
+@pytest.mark.parametrize(
+    'input_value,expected',
+    [((1, 2), (1, 2)), ([1, 2], (1, 2)), ({1}, (1,)), (frozenset([1]), (1,))],
+)
+def test_tuple_lax(input_value, expected):
+    v = SchemaValidator({'type': 'tuple', 'items': 'int'})
+    assert v.validate_python(input_value) == expected
+
+
+def test_tuple_strict():
+    v = SchemaValidator({'type': 'tuple', 'prefix_items': ['int', 'str'], 'strict': True})
+    assert v.validate_python((1, 'a')) == (1, 'a')
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python([1, 'a'])
+    assert exc_info.value.errors() == [
+        {'kind': 'tuple_type', 'loc': [], 'message': 'Value must be a valid tuple', 'input_value': [1, 'a']}
+    ]
+
+
+
+def test_tuple_strict_coercions():
+    v = SchemaValidator({'type': 'tuple', 'items': 'int', 'strict': True})
+    # JSON has no tuples, so arrays are allowed in strict mode by default
+    assert v.validate_json('[1, 2]') == (1, 2)
+
+    v = SchemaValidator(
+        {
+            'type': 'tuple',
+            'items': 'int',
+            'strict': True,
+            'strict_coercions': {'python': ['array_to_tuple'], 'json': []},
+        }
+    )
+    assert v.validate_python([1, 2]) == (1, 2)
+    assert v.validate_python((1, 2)) == (1, 2)
+    with pytest.raises(ValidationError, match=r'Value must be a valid tuple \[kind=tuple_type'):
+        v.validate_json('[1, 2]')
+
+
+def test_strict_coercions_invalid():
+    with pytest.raises(SchemaError, match="Invalid strict coercion: 'str_to_tuple'"):
+        SchemaValidator({'type': 'tuple', 'strict_coercions': {'python': ['str_to_tuple']}})
+    with pytest.raises(SchemaError, match="Invalid strict_coercions source: 'yaml'"):
+        SchemaValidator({'type': 'tuple', 'strict_coercions': {'yaml': []}})
+
+def test_tuple_errors():
+    v = SchemaValidator({'type': 'tuple', 'prefix_items': ['int', 'str'], 'items': 'float'})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(('x', 'a', 1.5, 'y'))
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'int_parsing',
+            'loc': [0],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'x',
+        },
+        {
+            'kind': 'float_parsing',
+            'loc': [3],
+            'message': 'Value must be a valid number, unable to parse string as an number',
+            'input_value': 'y',
+        },
+    ]
+
+
+def test_tuple_repr():
+    v = SchemaValidator({'type': 'tuple', 'prefix_items': ['int', 'str'], 'items': 'float'})
+    assert repr(v).startswith('SchemaValidator(name="tuple-int,str,*float"')
+
+
+def test_max_items_less_than_prefix():
+    with pytest.raises(SchemaError, match=re.escape('max_items (1) is less than the number of prefix_items')):
+        SchemaValidator({'type': 'tuple', 'prefix_items': ['int', 'str'], 'max_items': 1})
+
+
+def test_tuple_from_array():
+    v = SchemaValidator(
+        {'type': 'tuple', 'prefix_items': [{'type': 'int'}, {'type': 'float'}], 'items': {'type': 'int', 'lt': 3}}
+    )
+    assert v.validate_python(array('i', [1, 2])) == (1, 2.0)
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(array('i', [1, 2, 3]))
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'int_less_than',
+            'loc': [2],
+            'message': 'Value must be less than 3',
+            'input_value': 3,
+            'context': {'lt': 3},
+        }
+    ]
+
+    v = SchemaValidator({'type': 'tuple', 'items': {'type': 'float'}})
+    output = v.validate_python(array('d', [1.5, 2.5]))
+    assert output == (1.5, 2.5)
+    assert type(output) is tuple
+
+    v = SchemaValidator({'type': 'tuple', 'strict': True})
+    with pytest.raises(ValidationError, match='Value must be a valid tuple'):
+        v.validate_python(array('i', [1, 2]))
@@ -201,4 +201,241 @@ def test_no_choices():
     with pytest.raises(SchemaError) as exc_info:
         SchemaValidator({'type': 'union'})
 
-    assert exc_info.value.args[0] == ('Error building "union" validator:\n' '  KeyError: \'"choices" is required\'')
+    assert exc_info.value.args[0] == ('Error building "union" validator:\n' '  SchemaError: "choices" is required')
+
+
+def model_union_schema(**extra):
+    return {
+        'type': 'union',
+        'choices': [
+            {'type': 'model', 'name': 'ModelA', 'fields': {'a': 'int', 'b': 'int'}},
+            {'type': 'model', 'name': 'ModelB', 'fields': {'a': 'int', 'c': 'str'}},
+            'int',
+        ],
+        **extra,
+    }
+
+
+def test_error_strategy_all():
+    v = SchemaValidator(model_union_schema())
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 1, 'b': 'x'})
+    assert [e['loc'] for e in exc_info.value.errors()] == [['ModelA', 'b'], ['ModelB', 'c'], ['int']]
+
+
+def test_error_strategy_best_match():
+    v = SchemaValidator(model_union_schema(error_strategy='best_match'))
+    assert v.validate_python({'a': 1, 'b': 2}) == ({'a': 1, 'b': 2}, {'a', 'b'})
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 1, 'b': 'x'})
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'int_parsing',
+            'loc': ['ModelA', 'b'],
+            'message': 'Value must be a valid integer, unable to parse string as an integer',
+            'input_value': 'x',
+        }
+    ]
+
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 'x', 'b': 'y', 'c': 'z'})
+    # ModelB only has one error
+    assert [e['loc'] for e in exc_info.value.errors()] == [['ModelB', 'a']]
+
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({})
+    # ties go to the first choice
+    assert [e['loc'] for e in exc_info.value.errors()] == [['ModelA', 'a'], ['ModelA', 'b']]
+
+
+def test_error_strategy_best_match_input_rejected():
+    v = SchemaValidator(
+        {'type': 'union', 'choices': ['int', 'bool', {'type': 'list', 'items': 'int'}], 'error_strategy': 'best_match'}
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python([1, 'x', 'y'])
+    # int and bool reject the input entirely, so the list's errors are reported even though there are more of them
+    assert [e['loc'] for e in exc_info.value.errors()] == [['list-int', 1], ['list-int', 2]]
+
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({})
+    assert [e['loc'] for e in exc_info.value.errors()] == [['int']]
+
+
+def test_error_strategy_config():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {'x': model_union_schema()},
+            'config': {'union_error_strategy': 'best_match'},
+        }
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'x': {'a': 1, 'b': 'x'}})
+    assert [e['loc'] for e in exc_info.value.errors()] == [['x', 'ModelA', 'b']]
+
+
+def test_error_strategy_invalid():
+    with pytest.raises(SchemaError, match='Invalid error_strategy: "foobar"'):
+        SchemaValidator(model_union_schema(error_strategy='foobar'))
+
+
+def test_custom_error():
+    v = SchemaValidator(
+        {
+            'type': 'union',
+            'choices': ['int', 'bool'],
+            'custom_error_message': 'Value must be either an int or a bool',
+            'custom_error_title': 'IntOrBool',
+        }
+    )
+    assert v.validate_python('1') == 1
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python('x')
+    assert exc_info.value.errors() == [
+        {
+            'kind': 'union_error',
+            'loc': [],
+            'message': 'Value must be either an int or a bool',
+            'input_value': 'x',
+            'title': 'IntOrBool',
+        }
+    ]
+    assert str(exc_info.value) == (
+        '1 validation error for union\n'
+        "  Value must be either an int or a bool [kind=union_error, title=IntOrBool, input_value='x', input_type=str]"
+    )
+
+
+def test_custom_error_prepend():
+    v = SchemaValidator(
+        {
+            'type': 'model',
+            'fields': {
+                'x': {
+                    'type': 'union',
+                    'choices': ['int', 'bool'],
+                    'custom_error_message': 'Value must be either an int or a bool',
+                    'custom_error_mode': 'prepend',
+                }
+            },
+        }
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'x': 'x'})
+    assert [(e['kind'], e['loc'], e['message']) for e in exc_info.value.errors()] == [
+        ('union_error', ['x'], 'Value must be either an int or a bool'),
+        ('int_parsing', ['x', 'int'], 'Value must be a valid integer, unable to parse string as an integer'),
+        ('bool_parsing', ['x', 'bool'], 'Value must be a valid boolean, unable to interpret input'),
+    ]
+
+
+def test_custom_error_best_match():
+    v = SchemaValidator(
+        model_union_schema(
+            error_strategy='best_match',
+            custom_error_message='Value must be a ModelA, a ModelB or an int',
+            custom_error_mode='prepend',
+        )
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'a': 'x', 'b': 'y', 'c': 'z'})
+    assert [e['loc'] for e in exc_info.value.errors()] == [[], ['ModelB', 'a']]
+
+
+@pytest.mark.parametrize(
+    'schema,message',
+    [
+        ({'custom_error_message': 'x', 'custom_error_mode': 'foobar'}, 'Invalid custom_error_mode: "foobar"'),
+        ({'custom_error_title': 'x'}, "require 'custom_error_message'"),
+        ({'custom_error_mode': 'prepend'}, "require 'custom_error_message'"),
+    ],
+)
+def test_custom_error_invalid(schema, message):
+    with pytest.raises(SchemaError, match=message):
+        SchemaValidator({'type': 'union', 'choices': ['int', 'bool'], **schema})
+
+
+def test_choice_locations():
+    v = SchemaValidator(
+        {
+            'type': 'union',
+            'choices': [
+                {'type': 'model', 'fields': {'a': 'int'}},
+                {'type': 'int', 'strict': True},
+                {'type': 'model', 'fields': {'b': 'int'}},
+                {'type': 'model', 'fields': {'c': 'int'}, 'tag': 'c-model'},
+            ],
+        }
+    )
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({})
+    # errors are always in the order of the choices
+    assert [e['loc'] for e in exc_info.value.errors()] == [
+        ['Model[0]', 'a'],
+        ['strict-int'],
+        ['Model[2]', 'b'],
+        ['c-model', 'c'],
+    ]
+
+
+def test_duplicate_tag():
+    with pytest.raises(SchemaError, match='Duplicate union tag: "x"'):
+        SchemaValidator({'type': 'union', 'choices': [{'type': 'int', 'tag': 'x'}, {'type': 'str', 'tag': 'x'}]})
+
+
+def test_literal_union():
+    v = SchemaValidator(
+        {
+            'type': 'union',
+            'choices': [
+                {'type': 'literal', 'expected': ['a']},
+                {'type': 'literal', 'expected': [1, 2]},
+                {'type': 'literal', 'expected': [f'choice_{i}' for i in range(1000)]},
+            ],
+        }
+    )
+    assert v.validate_python('a') == 'a'
+    assert v.validate_python(2) == 2
+    assert v.validate_python('choice_999') == 'choice_999'
+    assert v.validate_json('"choice_0"') == 'choice_0'
+    assert v.validate_json('1') == 1
+    # literals are strict
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python('1')
+    assert [(e['kind'], e['loc']) for e in exc_info.value.errors()] == [
+        ('literal_error', ['literal-single-string']),
+        ('int_type', ['literal-multiple-ints']),
+        ('literal_error', ['literal-multiple-strings']),
+    ]
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python(3)
+    assert [(e['kind'], e['loc']) for e in exc_info.value.errors()] == [
+        ('str_type', ['literal-single-string']),
+        ('literal_error', ['literal-multiple-ints']),
+        ('str_type', ['literal-multiple-strings']),
+    ]
+
+
+def test_model_class_union_instances():
+    classes = [type(f'Model{i}', (), {}) for i in range(20)]
+    v = SchemaValidator(
+        {
+            'type': 'union',
+            'choices': [
+                {'type': 'model-class', 'class_type': cls, 'model': {'type': 'model', 'fields': {'x': 'int'}}}
+                for cls in classes
+            ],
+        }
+    )
+    m = classes[15]()
+    assert v.validate_python(m) is m
+
+    # other input is validated as usual
+    output = v.validate_python({'x': '1'})
+    assert isinstance(output, classes[0])
+    assert output.x == 1
+    assert isinstance(v.validate_json('{"x": 1}'), classes[0])
+    with pytest.raises(ValidationError) as exc_info:
+        v.validate_python({'x': 'wrong'})
+    assert len(exc_info.value.errors()) == 20